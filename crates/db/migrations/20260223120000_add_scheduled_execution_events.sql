@@ -0,0 +1,12 @@
+CREATE TABLE scheduled_execution_events (
+    id                       BLOB PRIMARY KEY,
+    scheduled_execution_id   BLOB NOT NULL,
+    from_status              TEXT NOT NULL,
+    to_status                TEXT NOT NULL,
+    at                       TEXT NOT NULL DEFAULT (datetime('now', 'subsec')),
+    detail                   TEXT,
+    FOREIGN KEY (scheduled_execution_id) REFERENCES scheduled_executions(id) ON DELETE CASCADE
+);
+
+CREATE INDEX idx_scheduled_execution_events_execution
+    ON scheduled_execution_events(scheduled_execution_id, at);
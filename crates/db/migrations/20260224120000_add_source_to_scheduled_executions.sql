@@ -0,0 +1,2 @@
+-- Who created the schedule (e.g. 'ui', 'api', 'mcp'), for auditing.
+ALTER TABLE scheduled_executions ADD COLUMN source TEXT NOT NULL DEFAULT 'api';
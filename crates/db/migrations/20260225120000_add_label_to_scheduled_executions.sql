@@ -0,0 +1,2 @@
+-- Optional free-text label (e.g. 'nightly lint') shown in the schedule list.
+ALTER TABLE scheduled_executions ADD COLUMN label TEXT;
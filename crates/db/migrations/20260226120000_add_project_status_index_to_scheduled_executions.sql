@@ -0,0 +1,3 @@
+-- Serves per-project listings filtered by status, e.g. a "fired schedules" view.
+CREATE INDEX idx_scheduled_executions_project_status
+    ON scheduled_executions(project_id, status, scheduled_at);
@@ -0,0 +1,3 @@
+-- Optional JSON CodingAgent snapshot taken when the schedule was created. When set,
+-- firing uses it instead of resolving executor_profile_id at fire time.
+ALTER TABLE scheduled_executions ADD COLUMN executor_config TEXT;
@@ -86,6 +86,22 @@ impl DBService {
         Ok(DBService { pool })
     }
 
+    /// A fresh, migrated in-memory database, e.g. for tests. Dropped with the
+    /// last clone of the pool.
+    pub async fn new_in_memory() -> Result<DBService, Error> {
+        let options = SqliteConnectOptions::from_str("sqlite::memory:")?;
+        // Every connection to `sqlite::memory:` opens its own empty database, so
+        // keep exactly one connection and never recycle it
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(options)
+            .await?;
+        run_migrations(&pool).await?;
+        Ok(DBService { pool })
+    }
+
     pub async fn new_with_after_connect<F>(after_connect: F) -> Result<DBService, Error>
     where
         F: for<'a> Fn(
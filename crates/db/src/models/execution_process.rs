@@ -304,6 +304,28 @@ impl ExecutionProcess {
         .await
     }
 
+    /// Find running coding agent processes for a specific project
+    pub async fn find_running_agents_by_project(
+        pool: &SqlitePool,
+        project_id: Uuid,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ExecutionProcess,
+            r#"SELECT ep.id as "id!: Uuid", ep.session_id as "session_id!: Uuid", ep.run_reason as "run_reason!: ExecutionProcessRunReason", ep.executor_action as "executor_action!: sqlx::types::Json<ExecutorActionField>",
+                      ep.status as "status!: ExecutionProcessStatus", ep.exit_code,
+                      ep.dropped as "dropped!: bool", ep.started_at as "started_at!: DateTime<Utc>", ep.completed_at as "completed_at?: DateTime<Utc>", ep.created_at as "created_at!: DateTime<Utc>", ep.updated_at as "updated_at!: DateTime<Utc>"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               JOIN workspaces w ON s.workspace_id = w.id
+               JOIN tasks t ON w.task_id = t.id
+               WHERE ep.status = 'running' AND ep.run_reason = 'codingagent' AND t.project_id = ?
+               ORDER BY ep.created_at ASC"#,
+            project_id
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     /// Check if there are running processes (excluding dev servers) for a workspace (across all sessions)
     pub async fn has_running_non_dev_server_processes_for_workspace(
         pool: &SqlitePool,
@@ -323,6 +345,25 @@ impl ExecutionProcess {
         Ok(count > 0)
     }
 
+    /// Check if a coding agent is running for a workspace (across all sessions)
+    pub async fn has_running_coding_agent_for_workspace(
+        pool: &SqlitePool,
+        workspace_id: Uuid,
+    ) -> Result<bool, sqlx::Error> {
+        let count: i64 = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) as "count!: i64"
+               FROM execution_processes ep
+               JOIN sessions s ON ep.session_id = s.id
+               WHERE s.workspace_id = $1
+                 AND ep.status = 'running'
+                 AND ep.run_reason = 'codingagent'"#,
+            workspace_id
+        )
+        .fetch_one(pool)
+        .await?;
+        Ok(count > 0)
+    }
+
     /// Find running dev servers for a specific workspace (across all sessions)
     pub async fn find_running_dev_servers_by_workspace(
         pool: &SqlitePool,
@@ -1,9 +1,12 @@
-use std::path::{Path, PathBuf};
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+};
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_with::rust::double_option;
-use sqlx::{Executor, FromRow, Sqlite, SqlitePool};
+use sqlx::{Executor, FromRow, QueryBuilder, Sqlite, SqlitePool};
 use thiserror::Error;
 use ts_rs::TS;
 use uuid::Uuid;
@@ -19,6 +22,7 @@ pub enum RepoError {
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
 pub struct Repo {
     pub id: Uuid,
+    #[sqlx(try_from = "String")]
     pub path: PathBuf,
     pub name: String,
     pub display_name: String,
@@ -164,14 +168,48 @@ impl Repo {
             return Ok(Vec::new());
         }
 
-        // Fetch each repo individually since SQLite doesn't support array parameters
-        let mut repos = Vec::with_capacity(ids.len());
+        // SQLite doesn't support array parameters, so bind each id into a single IN list
+        let mut query = QueryBuilder::<Sqlite>::new(
+            "SELECT id, path, name, display_name, setup_script, cleanup_script, copy_files, \
+             parallel_setup_script, dev_server_script, default_target_branch, created_at, \
+             updated_at FROM repos WHERE id IN (",
+        );
+        let mut separated = query.separated(", ");
         for id in ids {
-            if let Some(repo) = Self::find_by_id(pool, *id).await? {
-                repos.push(repo);
+            separated.push_bind(*id);
+        }
+        separated.push_unseparated(")");
+
+        // IN doesn't keep the order of its list; callers rely on request order
+        let found: HashMap<Uuid, Repo> = query
+            .build_query_as::<Repo>()
+            .fetch_all(pool)
+            .await?
+            .into_iter()
+            .map(|repo| (repo.id, repo))
+            .collect();
+        Ok(ids.iter().filter_map(|id| found.get(id).cloned()).collect())
+    }
+
+    /// Ids in `requested` that have no matching repo in `found`, in request order.
+    pub fn missing_ids(requested: &[Uuid], found: &[Repo]) -> Vec<Uuid> {
+        requested
+            .iter()
+            .filter(|id| !found.iter().any(|repo| repo.id == **id))
+            .copied()
+            .collect()
+    }
+
+    /// Ids that appear more than once in `requested`, each reported once, in request order.
+    pub fn duplicate_ids(requested: &[Uuid]) -> Vec<Uuid> {
+        let mut seen = HashSet::new();
+        let mut duplicates = Vec::new();
+        for id in requested {
+            if !seen.insert(*id) && !duplicates.contains(id) {
+                duplicates.push(*id);
             }
         }
-        Ok(repos)
+        duplicates
     }
 
     pub async fn find_or_create<'e, E>(
@@ -328,3 +366,26 @@ impl Repo {
         .map_err(RepoError::from)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DBService;
+
+    #[tokio::test]
+    async fn find_by_ids_returns_repos_in_request_order() {
+        let pool = DBService::new_in_memory().await.unwrap().pool;
+        let mut ids = Vec::new();
+        for name in ["a", "b", "c"] {
+            let path = PathBuf::from(format!("/tmp/vk-repos/{name}"));
+            ids.push(Repo::find_or_create(&pool, &path, name).await.unwrap().id);
+        }
+
+        let requested = [ids[2], Uuid::new_v4(), ids[0], ids[1]];
+        let found = Repo::find_by_ids(&pool, &requested).await.unwrap();
+        let found_ids: Vec<Uuid> = found.iter().map(|repo| repo.id).collect();
+        assert_eq!(found_ids, [ids[2], ids[0], ids[1]]);
+        assert_eq!(found[0].display_name, "c");
+        assert_eq!(Repo::missing_ids(&requested, &found), [requested[1]]);
+    }
+}
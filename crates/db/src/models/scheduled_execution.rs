@@ -1,6 +1,7 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
+use executors::profile::ExecutorProfileId;
 use serde::{Deserialize, Serialize};
-use sqlx::{FromRow, SqlitePool, Type};
+use sqlx::{Executor, FromRow, Sqlite, SqlitePool, Type};
 use strum_macros::{Display, EnumString};
 use ts_rs::TS;
 use uuid::Uuid;
@@ -26,14 +27,81 @@ pub struct ScheduledExecution {
     pub scheduled_at: DateTime<Utc>,
     pub status: ScheduledExecutionStatus,
     pub executor_profile_id: String, // JSON ExecutorProfileId
-    pub repos: String,               // JSON Vec<WorkspaceRepoInput>
+    pub repos: String,               // JSON Vec<ScheduledRepoInput>
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub fired_at: Option<DateTime<Utc>>,
     pub error_message: Option<String>,
+    /// Who created the schedule, e.g. `ui`, `api` or `mcp`.
+    pub source: String,
+    /// Optional free-text description, e.g. "nightly lint".
+    pub label: Option<String>,
+    /// JSON CodingAgent frozen at create time; fired with instead of the
+    /// profile's current settings when set.
+    pub executor_config: Option<String>,
+}
+
+/// Repo input stored as JSON in the scheduled_execution record.
+/// Uses camelCase to match the frontend's serialization format.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduledRepoInput {
+    pub repo_id: Uuid,
+    pub target_branch: String,
+}
+
+/// A recorded status transition of a [`ScheduledExecution`].
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize, TS)]
+pub struct ScheduledExecutionEvent {
+    pub id: Uuid,
+    pub scheduled_execution_id: Uuid,
+    pub from_status: ScheduledExecutionStatus,
+    pub to_status: ScheduledExecutionStatus,
+    pub at: DateTime<Utc>,
+    pub detail: Option<String>,
+}
+
+impl ScheduledExecutionEvent {
+    /// Record a transition from the execution's current status to `to_status`.
+    /// Must run before the status is updated.
+    async fn record<'e, E>(
+        executor: E,
+        scheduled_execution_id: Uuid,
+        to_status: ScheduledExecutionStatus,
+        detail: Option<&str>,
+    ) -> Result<(), sqlx::Error>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        let id = Uuid::new_v4();
+        sqlx::query!(
+            r#"INSERT INTO scheduled_execution_events (id, scheduled_execution_id, from_status, to_status, detail)
+               SELECT $1, id, status, $3, $4
+               FROM scheduled_executions
+               WHERE id = $2"#,
+            id,
+            scheduled_execution_id,
+            to_status,
+            detail
+        )
+        .execute(executor)
+        .await?;
+        Ok(())
+    }
 }
 
 impl ScheduledExecution {
+    /// The `repos` column decoded.
+    pub fn parsed_repos(&self) -> Result<Vec<ScheduledRepoInput>, serde_json::Error> {
+        serde_json::from_str(&self.repos)
+    }
+
+    /// The `executor_profile_id` column decoded.
+    pub fn parsed_executor_profile(&self) -> Result<ExecutorProfileId, serde_json::Error> {
+        serde_json::from_str(&self.executor_profile_id)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     pub async fn create(
         pool: &SqlitePool,
         id: Uuid,
@@ -42,11 +110,14 @@ impl ScheduledExecution {
         scheduled_at: DateTime<Utc>,
         executor_profile_id_json: &str,
         repos_json: &str,
+        source: &str,
+        label: Option<&str>,
+        executor_config_json: Option<&str>,
     ) -> Result<Self, sqlx::Error> {
         sqlx::query_as!(
             ScheduledExecution,
-            r#"INSERT INTO scheduled_executions (id, task_id, project_id, scheduled_at, executor_profile_id, repos)
-               VALUES ($1, $2, $3, $4, $5, $6)
+            r#"INSERT INTO scheduled_executions (id, task_id, project_id, scheduled_at, executor_profile_id, repos, source, label, executor_config)
+               VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
                RETURNING
                    id                  AS "id!: Uuid",
                    task_id             AS "task_id!: Uuid",
@@ -58,18 +129,47 @@ impl ScheduledExecution {
                    created_at          AS "created_at!: DateTime<Utc>",
                    updated_at          AS "updated_at!: DateTime<Utc>",
                    fired_at            AS "fired_at: DateTime<Utc>",
-                   error_message"#,
+                   error_message,
+                   source,
+                   label,
+                   executor_config"#,
             id,
             task_id,
             project_id,
             scheduled_at,
             executor_profile_id_json,
             repos_json,
+            source,
+            label,
+            executor_config_json,
         )
         .fetch_one(pool)
         .await
     }
 
+    /// Create a new pending execution with the task, repos, profile, label and
+    /// frozen config of `source`, firing at `scheduled_at`. `source` itself is
+    /// left as it is.
+    pub async fn create_rerun(
+        pool: &SqlitePool,
+        source: &ScheduledExecution,
+        scheduled_at: DateTime<Utc>,
+    ) -> Result<Self, sqlx::Error> {
+        Self::create(
+            pool,
+            Uuid::new_v4(),
+            source.task_id,
+            source.project_id,
+            scheduled_at,
+            &source.executor_profile_id,
+            &source.repos,
+            &source.source,
+            source.label.as_deref(),
+            source.executor_config.as_deref(),
+        )
+        .await
+    }
+
     pub async fn find_by_id(pool: &SqlitePool, id: Uuid) -> Result<Option<Self>, sqlx::Error> {
         sqlx::query_as!(
             ScheduledExecution,
@@ -84,7 +184,10 @@ impl ScheduledExecution {
                    created_at          AS "created_at!: DateTime<Utc>",
                    updated_at          AS "updated_at!: DateTime<Utc>",
                    fired_at            AS "fired_at: DateTime<Utc>",
-                   error_message
+                   error_message,
+                   source,
+                   label,
+                   executor_config
                FROM scheduled_executions
                WHERE id = $1"#,
             id
@@ -108,7 +211,10 @@ impl ScheduledExecution {
                    created_at          AS "created_at!: DateTime<Utc>",
                    updated_at          AS "updated_at!: DateTime<Utc>",
                    fired_at            AS "fired_at: DateTime<Utc>",
-                   error_message
+                   error_message,
+                   source,
+                   label,
+                   executor_config
                FROM scheduled_executions
                WHERE status = 'pending'
                  AND scheduled_at <= $1
@@ -119,6 +225,40 @@ impl ScheduledExecution {
         .await
     }
 
+    /// Pending executions whose `scheduled_at` is more than `threshold` in the
+    /// past, i.e. ones the scheduler should already have fired.
+    pub async fn find_overdue(
+        pool: &SqlitePool,
+        threshold: Duration,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        let cutoff = Utc::now() - threshold;
+        sqlx::query_as!(
+            ScheduledExecution,
+            r#"SELECT
+                   id                  AS "id!: Uuid",
+                   task_id             AS "task_id!: Uuid",
+                   project_id          AS "project_id!: Uuid",
+                   scheduled_at        AS "scheduled_at!: DateTime<Utc>",
+                   status              AS "status!: ScheduledExecutionStatus",
+                   executor_profile_id,
+                   repos,
+                   created_at          AS "created_at!: DateTime<Utc>",
+                   updated_at          AS "updated_at!: DateTime<Utc>",
+                   fired_at            AS "fired_at: DateTime<Utc>",
+                   error_message,
+                   source,
+                   label,
+                   executor_config
+               FROM scheduled_executions
+               WHERE status = 'pending'
+                 AND scheduled_at < $1
+               ORDER BY scheduled_at ASC"#,
+            cutoff
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn find_pending_by_task_id(
         pool: &SqlitePool,
         task_id: Uuid,
@@ -136,7 +276,10 @@ impl ScheduledExecution {
                    created_at          AS "created_at!: DateTime<Utc>",
                    updated_at          AS "updated_at!: DateTime<Utc>",
                    fired_at            AS "fired_at: DateTime<Utc>",
-                   error_message
+                   error_message,
+                   source,
+                   label,
+                   executor_config
                FROM scheduled_executions
                WHERE task_id = $1
                  AND status = 'pending'
@@ -148,22 +291,30 @@ impl ScheduledExecution {
     }
 
     pub async fn mark_fired(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        ScheduledExecutionEvent::record(&mut *tx, id, ScheduledExecutionStatus::Fired, None)
+            .await?;
         sqlx::query!(
             "UPDATE scheduled_executions SET status = 'fired', fired_at = datetime('now', 'subsec'), updated_at = datetime('now', 'subsec') WHERE id = $1",
             id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(())
     }
 
     pub async fn mark_cancelled(pool: &SqlitePool, id: Uuid) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        ScheduledExecutionEvent::record(&mut *tx, id, ScheduledExecutionStatus::Cancelled, None)
+            .await?;
         sqlx::query!(
             "UPDATE scheduled_executions SET status = 'cancelled', updated_at = datetime('now', 'subsec') WHERE id = $1",
             id
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(())
     }
 
@@ -184,7 +335,10 @@ impl ScheduledExecution {
                    created_at          AS "created_at!: DateTime<Utc>",
                    updated_at          AS "updated_at!: DateTime<Utc>",
                    fired_at            AS "fired_at: DateTime<Utc>",
-                   error_message
+                   error_message,
+                   source,
+                   label,
+                   executor_config
                FROM scheduled_executions
                WHERE project_id = $1
                ORDER BY scheduled_at DESC"#,
@@ -194,18 +348,362 @@ impl ScheduledExecution {
         .await
     }
 
+    /// Executions of a project in one status, newest first. Uses the
+    /// `(project_id, status, scheduled_at)` index.
+    pub async fn find_by_status(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        status: ScheduledExecutionStatus,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ScheduledExecution,
+            r#"SELECT
+                   id                  AS "id!: Uuid",
+                   task_id             AS "task_id!: Uuid",
+                   project_id          AS "project_id!: Uuid",
+                   scheduled_at        AS "scheduled_at!: DateTime<Utc>",
+                   status              AS "status!: ScheduledExecutionStatus",
+                   executor_profile_id,
+                   repos,
+                   created_at          AS "created_at!: DateTime<Utc>",
+                   updated_at          AS "updated_at!: DateTime<Utc>",
+                   fired_at            AS "fired_at: DateTime<Utc>",
+                   error_message,
+                   source,
+                   label,
+                   executor_config
+               FROM scheduled_executions
+               WHERE project_id = $1 AND status = $2
+               ORDER BY scheduled_at DESC"#,
+            project_id,
+            status
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Like [`Self::find_by_project_id`], narrowed by status, source and a `created_at` range.
+    /// `None` leaves that dimension unbounded.
+    pub async fn find_by_project_id_filtered(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        status: Option<ScheduledExecutionStatus>,
+        source: Option<&str>,
+        created_after: Option<DateTime<Utc>>,
+        created_before: Option<DateTime<Utc>>,
+    ) -> Result<Vec<Self>, sqlx::Error> {
+        sqlx::query_as!(
+            ScheduledExecution,
+            r#"SELECT
+                   id                  AS "id!: Uuid",
+                   task_id             AS "task_id!: Uuid",
+                   project_id          AS "project_id!: Uuid",
+                   scheduled_at        AS "scheduled_at!: DateTime<Utc>",
+                   status              AS "status!: ScheduledExecutionStatus",
+                   executor_profile_id,
+                   repos,
+                   created_at          AS "created_at!: DateTime<Utc>",
+                   updated_at          AS "updated_at!: DateTime<Utc>",
+                   fired_at            AS "fired_at: DateTime<Utc>",
+                   error_message,
+                   source,
+                   label,
+                   executor_config
+               FROM scheduled_executions
+               WHERE project_id = $1
+                 AND ($2 IS NULL OR status = $2)
+                 AND ($3 IS NULL OR source = $3)
+                 AND ($4 IS NULL OR datetime(created_at, 'subsec') >= datetime($4, 'subsec'))
+                 AND ($5 IS NULL OR datetime(created_at, 'subsec') <= datetime($5, 'subsec'))
+               ORDER BY scheduled_at DESC"#,
+            project_id,
+            status,
+            source,
+            created_after,
+            created_before
+        )
+        .fetch_all(pool)
+        .await
+    }
+
     pub async fn mark_error(
         pool: &SqlitePool,
         id: Uuid,
         message: &str,
     ) -> Result<(), sqlx::Error> {
+        let mut tx = pool.begin().await?;
+        ScheduledExecutionEvent::record(
+            &mut *tx,
+            id,
+            ScheduledExecutionStatus::Fired,
+            Some(message),
+        )
+        .await?;
         sqlx::query!(
             "UPDATE scheduled_executions SET error_message = $2, fired_at = datetime('now', 'subsec'), status = 'fired', updated_at = datetime('now', 'subsec') WHERE id = $1",
             id,
             message
         )
-        .execute(pool)
+        .execute(&mut *tx)
         .await?;
+        tx.commit().await?;
         Ok(())
     }
+
+    /// Status transitions of a scheduled execution, oldest first.
+    pub async fn history(
+        pool: &SqlitePool,
+        id: Uuid,
+    ) -> Result<Vec<ScheduledExecutionEvent>, sqlx::Error> {
+        sqlx::query_as!(
+            ScheduledExecutionEvent,
+            r#"SELECT
+                   id                      AS "id!: Uuid",
+                   scheduled_execution_id  AS "scheduled_execution_id!: Uuid",
+                   from_status             AS "from_status!: ScheduledExecutionStatus",
+                   to_status               AS "to_status!: ScheduledExecutionStatus",
+                   at                      AS "at!: DateTime<Utc>",
+                   detail
+               FROM scheduled_execution_events
+               WHERE scheduled_execution_id = $1
+               ORDER BY at ASC"#,
+            id
+        )
+        .fetch_all(pool)
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        DBService,
+        models::{
+            project::{CreateProject, Project},
+            task::{CreateTask, Task},
+        },
+    };
+
+    /// A migrated in-memory database with one project and one task in it.
+    async fn setup() -> (SqlitePool, Uuid, Uuid) {
+        let pool = DBService::new_in_memory().await.unwrap().pool;
+        let project_id = Uuid::new_v4();
+        Project::create(
+            &pool,
+            &CreateProject {
+                name: "project".to_string(),
+                repositories: Vec::new(),
+            },
+            project_id,
+        )
+        .await
+        .unwrap();
+        let task_id = Uuid::new_v4();
+        Task::create(
+            &pool,
+            &CreateTask::from_title_description(project_id, "task".to_string(), None),
+            task_id,
+        )
+        .await
+        .unwrap();
+        (pool, project_id, task_id)
+    }
+
+    async fn schedule(
+        pool: &SqlitePool,
+        project_id: Uuid,
+        task_id: Uuid,
+        source: &str,
+    ) -> ScheduledExecution {
+        ScheduledExecution::create(
+            pool,
+            Uuid::new_v4(),
+            task_id,
+            project_id,
+            Utc::now(),
+            r#"{"executor":"CLAUDE_CODE"}"#,
+            "[]",
+            source,
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn set_created_at(pool: &SqlitePool, id: Uuid, created_at: &str) {
+        sqlx::query("UPDATE scheduled_executions SET created_at = $1 WHERE id = $2")
+            .bind(created_at)
+            .bind(id)
+            .execute(pool)
+            .await
+            .unwrap();
+    }
+
+    fn at(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw).unwrap().with_timezone(&Utc)
+    }
+
+    fn ids(executions: &[ScheduledExecution]) -> Vec<Uuid> {
+        let mut ids: Vec<Uuid> = executions.iter().map(|e| e.id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[tokio::test]
+    async fn project_listing_is_filtered_by_created_at_and_status() {
+        let (pool, project_id, task_id) = setup().await;
+        let early = schedule(&pool, project_id, task_id, "api").await;
+        let middle = schedule(&pool, project_id, task_id, "api").await;
+        let late = schedule(&pool, project_id, task_id, "api").await;
+        set_created_at(&pool, early.id, "2026-01-01 12:00:00.000").await;
+        set_created_at(&pool, middle.id, "2026-01-10 12:00:00.000").await;
+        set_created_at(&pool, late.id, "2026-01-20 12:00:00.000").await;
+        ScheduledExecution::mark_fired(&pool, middle.id).await.unwrap();
+
+        let filtered = |status, after, before| {
+            ScheduledExecution::find_by_project_id_filtered(
+                &pool, project_id, status, None, after, before,
+            )
+        };
+
+        let unbounded = filtered(None, None, None).await.unwrap();
+        assert_eq!(unbounded.len(), 3);
+
+        let after = filtered(None, Some(at("2026-01-05T00:00:00Z")), None).await.unwrap();
+        assert_eq!(ids(&after), ids(&[middle.clone(), late.clone()]));
+
+        let before = filtered(None, None, Some(at("2026-01-15T00:00:00Z"))).await.unwrap();
+        assert_eq!(ids(&before), ids(&[early.clone(), middle.clone()]));
+
+        let between = filtered(
+            None,
+            Some(at("2026-01-05T00:00:00Z")),
+            Some(at("2026-01-15T00:00:00Z")),
+        )
+        .await
+        .unwrap();
+        assert_eq!(ids(&between), vec![middle.id]);
+
+        // Combined with status: only `middle` has fired
+        let pending_after = filtered(
+            Some(ScheduledExecutionStatus::Pending),
+            Some(at("2026-01-05T00:00:00Z")),
+            None,
+        )
+        .await
+        .unwrap();
+        assert_eq!(ids(&pending_after), vec![late.id]);
+    }
+
+    #[tokio::test]
+    async fn firing_records_a_pending_to_fired_event() {
+        let (pool, project_id, task_id) = setup().await;
+        let scheduled = schedule(&pool, project_id, task_id, "api").await;
+        assert!(ScheduledExecution::history(&pool, scheduled.id).await.unwrap().is_empty());
+
+        ScheduledExecution::mark_fired(&pool, scheduled.id).await.unwrap();
+
+        let history = ScheduledExecution::history(&pool, scheduled.id).await.unwrap();
+        assert_eq!(history.len(), 1);
+        let event = &history[0];
+        assert_eq!(event.scheduled_execution_id, scheduled.id);
+        assert_eq!(event.from_status, ScheduledExecutionStatus::Pending);
+        assert_eq!(event.to_status, ScheduledExecutionStatus::Fired);
+        assert_eq!(event.detail, None);
+    }
+
+    #[tokio::test]
+    async fn source_round_trips_through_create_and_find() {
+        let (pool, project_id, task_id) = setup().await;
+        let created = schedule(&pool, project_id, task_id, "mcp").await;
+        assert_eq!(created.source, "mcp");
+
+        let found = ScheduledExecution::find_by_id(&pool, created.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.source, "mcp");
+
+        let listed = ScheduledExecution::find_by_project_id(&pool, project_id)
+            .await
+            .unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].source, "mcp");
+    }
+
+    #[tokio::test]
+    async fn status_listing_returns_only_that_status() {
+        let (pool, project_id, task_id) = setup().await;
+        let pending = schedule(&pool, project_id, task_id, "api").await;
+        let fired = schedule(&pool, project_id, task_id, "api").await;
+        let cancelled = schedule(&pool, project_id, task_id, "api").await;
+        let errored = schedule(&pool, project_id, task_id, "api").await;
+        ScheduledExecution::mark_fired(&pool, fired.id).await.unwrap();
+        ScheduledExecution::mark_cancelled(&pool, cancelled.id).await.unwrap();
+        ScheduledExecution::mark_error(&pool, errored.id, "boom").await.unwrap();
+
+        let listed =
+            ScheduledExecution::find_by_status(&pool, project_id, ScheduledExecutionStatus::Fired)
+                .await
+                .unwrap();
+        assert_eq!(ids(&listed), vec![fired.id]);
+
+        let listed = ScheduledExecution::find_by_status(
+            &pool,
+            project_id,
+            ScheduledExecutionStatus::Pending,
+        )
+        .await
+        .unwrap();
+        assert_eq!(ids(&listed), vec![pending.id]);
+    }
+
+    #[tokio::test]
+    async fn rerun_copies_the_source_into_a_new_pending_row() {
+        let (pool, project_id, task_id) = setup().await;
+        let repos = serde_json::to_string(&[ScheduledRepoInput {
+            repo_id: Uuid::new_v4(),
+            target_branch: "main".to_string(),
+        }])
+        .unwrap();
+        let original = ScheduledExecution::create(
+            &pool,
+            Uuid::new_v4(),
+            task_id,
+            project_id,
+            Utc::now(),
+            r#"{"executor":"CLAUDE_CODE","variant":"PLAN"}"#,
+            &repos,
+            "ui",
+            Some("nightly lint"),
+            None,
+        )
+        .await
+        .unwrap();
+        ScheduledExecution::mark_fired(&pool, original.id).await.unwrap();
+        let fired = ScheduledExecution::find_by_id(&pool, original.id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let rerun = ScheduledExecution::create_rerun(&pool, &fired, Utc::now())
+            .await
+            .unwrap();
+        assert_ne!(rerun.id, fired.id);
+        assert_eq!(rerun.status, ScheduledExecutionStatus::Pending);
+        assert_eq!(rerun.task_id, fired.task_id);
+        assert_eq!(rerun.repos, fired.repos);
+        assert_eq!(rerun.executor_profile_id, fired.executor_profile_id);
+        assert_eq!(rerun.label.as_deref(), Some("nightly lint"));
+        assert!(rerun.fired_at.is_none());
+
+        // The original keeps its fired state
+        let original = ScheduledExecution::find_by_id(&pool, original.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(original.status, ScheduledExecutionStatus::Fired);
+    }
 }
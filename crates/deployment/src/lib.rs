@@ -31,6 +31,7 @@ use services::services::{
     project::ProjectService,
     queued_message::QueuedMessageService,
     repo::RepoService,
+    scheduler::SchedulerControl,
     worktree_manager::WorktreeError,
 };
 use sqlx::Error as SqlxError;
@@ -110,6 +111,8 @@ pub trait Deployment: Clone + Send + Sync + 'static {
 
     fn auth_context(&self) -> &AuthContext;
 
+    fn scheduler_control(&self) -> &SchedulerControl;
+
     async fn update_sentry_scope(&self) -> Result<(), DeploymentError> {
         let user_id = self.user_id();
         let config = self.config().read().await;
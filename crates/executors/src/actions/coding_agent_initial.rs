@@ -10,7 +10,9 @@ use crate::{
     actions::Executable,
     approvals::ExecutorApprovalService,
     env::ExecutionEnv,
-    executors::{BaseCodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor},
+    executors::{
+        BaseCodingAgent, CodingAgent, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
+    },
     profile::ExecutorProfileId,
 };
 
@@ -25,6 +27,11 @@ pub struct CodingAgentInitialRequest {
     /// If None, uses the container_ref directory directly.
     #[serde(default)]
     pub working_dir: Option<String>,
+    /// Settings to run with instead of resolving `executor_profile_id` at spawn time,
+    /// e.g. a snapshot frozen when a run was scheduled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[ts(optional)]
+    pub executor_config: Option<CodingAgent>,
 }
 
 impl CodingAgentInitialRequest {
@@ -38,6 +45,20 @@ impl CodingAgentInitialRequest {
             None => current_dir.to_path_buf(),
         }
     }
+
+    /// The agent to run: the frozen `executor_config` if there is one, otherwise
+    /// the profile's current settings.
+    #[cfg(not(feature = "qa-mode"))]
+    pub fn coding_agent(&self) -> Result<CodingAgent, ExecutorError> {
+        if let Some(config) = &self.executor_config {
+            return Ok(config.clone());
+        }
+        ExecutorConfigs::get_cached()
+            .get_coding_agent(&self.executor_profile_id)
+            .ok_or(ExecutorError::UnknownExecutorType(
+                self.executor_profile_id.to_string(),
+            ))
+    }
 }
 
 #[async_trait]
@@ -60,12 +81,7 @@ impl Executable for CodingAgentInitialRequest {
 
         #[cfg(not(feature = "qa-mode"))]
         {
-            let executor_profile_id = self.executor_profile_id.clone();
-            let mut agent = ExecutorConfigs::get_cached()
-                .get_coding_agent(&executor_profile_id)
-                .ok_or(ExecutorError::UnknownExecutorType(
-                    executor_profile_id.to_string(),
-                ))?;
+            let mut agent = self.coding_agent()?;
 
             agent.use_approvals(approvals.clone());
 
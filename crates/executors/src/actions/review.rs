@@ -69,12 +69,15 @@ impl Executable for ReviewRequest {
 
         agent.use_approvals(approvals.clone());
 
+        // Reviews are read-only, so reminding the agent to commit is just noise
+        let env = env.clone().without_commit_reminder();
+
         agent
             .spawn_review(
                 &effective_dir,
                 &self.prompt,
                 self.session_id.as_deref(),
-                env,
+                &env,
             )
             .await
     }
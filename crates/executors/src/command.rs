@@ -18,8 +18,13 @@ pub enum CommandBuildError {
     QuoteError(#[from] shlex::QuoteError),
     #[error("invalid shell parameters: {0}")]
     InvalidShellParams(String),
+    #[error("package runner cannot be parsed: {0}")]
+    InvalidPackageRunner(String),
 }
 
+/// How npm-distributed executors are launched unless a profile sets `package_runner`.
+pub const DEFAULT_PACKAGE_RUNNER: &str = "npx -y";
+
 #[derive(Debug, Clone)]
 pub struct CommandParts {
     program: String,
@@ -48,6 +53,24 @@ pub struct CmdOverrides {
     )]
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub base_command_override: Option<String>,
+    #[schemars(
+        title = "Package Runner",
+        description = "Command used to run npm-based executors instead of `npx -y` (e.g. `bunx`). Leave empty to run a globally installed binary"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package_runner: Option<String>,
+    #[schemars(
+        title = "npm Registry",
+        description = "Registry npm-based executors install from (sets NPM_CONFIG_REGISTRY). Leave empty to use the ambient npm config"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub npm_registry: Option<String>,
+    #[schemars(
+        title = "npm Cache Directory",
+        description = "Cache directory for npm-based executors (sets npm_config_cache). Leave empty to use the ambient npm config"
+    )]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub npm_cache_dir: Option<String>,
     #[schemars(
         title = "Additional Parameters",
         description = "Additional parameters to append to the base command"
@@ -68,6 +91,19 @@ pub struct CommandBuilder {
     pub base: String,
     /// Optional parameters to append to the base command
     pub params: Option<Vec<String>>,
+    #[serde(skip)]
+    #[ts(skip)]
+    #[schemars(skip)]
+    package: Option<NpmPackage>,
+}
+
+/// The npm package behind a base command, so the runner can be swapped.
+#[derive(Debug, Clone, PartialEq)]
+struct NpmPackage {
+    /// Package spec passed to the runner, e.g. `opencode-ai@1.1.25`
+    spec: String,
+    /// Command to use when the package is installed globally, e.g. `opencode`
+    bin: String,
 }
 
 impl CommandBuilder {
@@ -75,6 +111,20 @@ impl CommandBuilder {
         Self {
             base: base.into(),
             params: None,
+            package: None,
+        }
+    }
+
+    /// Run an npm package through [`DEFAULT_PACKAGE_RUNNER`]. `bin` is what to run instead
+    /// when a profile sets an empty `package_runner`.
+    pub fn npm_package(spec: &str, bin: &str) -> Self {
+        Self {
+            base: format!("{DEFAULT_PACKAGE_RUNNER} {spec}"),
+            params: None,
+            package: Some(NpmPackage {
+                spec: spec.to_string(),
+                bin: bin.to_string(),
+            }),
         }
     }
 
@@ -92,6 +142,23 @@ impl CommandBuilder {
         self
     }
 
+    /// Launch the npm package with `runner` instead of the default; an empty runner runs the
+    /// globally installed binary. No effect on commands that aren't npm packages.
+    fn with_package_runner(mut self, runner: &str) -> Result<Self, CommandBuildError> {
+        let Some(package) = &self.package else {
+            return Ok(self);
+        };
+        let runner = runner.trim();
+        self.base = if runner.is_empty() {
+            package.bin.clone()
+        } else {
+            split_command_line(runner)
+                .map_err(|_| CommandBuildError::InvalidPackageRunner(runner.to_string()))?;
+            format!("{runner} {}", package.spec)
+        };
+        Ok(self)
+    }
+
     fn extend_shell_params<I>(mut self, more: I) -> Result<Self, CommandBuildError>
     where
         I: IntoIterator,
@@ -178,6 +245,8 @@ pub fn apply_overrides(
 ) -> Result<CommandBuilder, CommandBuildError> {
     let builder = if let Some(ref base) = overrides.base_command_override {
         builder.override_base(base.clone())
+    } else if let Some(ref runner) = overrides.package_runner {
+        builder.with_package_runner(runner)?
     } else {
         builder
     };
@@ -187,3 +256,42 @@ pub fn apply_overrides(
         Ok(builder)
     }
 }
+
+// Windows splits with winsplit, which has its own quoting rules
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    fn args_with(additional_params: &[&str]) -> Vec<String> {
+        let overrides = CmdOverrides {
+            additional_params: Some(additional_params.iter().map(|p| p.to_string()).collect()),
+            ..Default::default()
+        };
+        let parts = apply_overrides(CommandBuilder::new("agent"), &overrides)
+            .unwrap()
+            .build_initial()
+            .unwrap();
+        parts.args
+    }
+
+    #[test]
+    fn override_params_are_split_like_a_shell() {
+        assert_eq!(
+            args_with(&[r#"--foo "bar baz" --verbose"#]),
+            ["--foo", "bar baz", "--verbose"]
+        );
+        assert_eq!(
+            args_with(&["--model", "gpt-5 --effort high"]),
+            ["--model", "gpt-5", "--effort", "high"]
+        );
+
+        let overrides = CmdOverrides {
+            additional_params: Some(vec![r#"--foo "unterminated"#.to_string()]),
+            ..Default::default()
+        };
+        assert!(matches!(
+            apply_overrides(CommandBuilder::new("agent"), &overrides),
+            Err(CommandBuildError::InvalidShellParams(_))
+        ));
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    path::PathBuf,
+};
 
 use tokio::process::Command;
 
@@ -28,8 +32,28 @@ impl RepoContext {
     }
 }
 
+/// Keys that vibe-kanban sets itself and user-defined env must never replace.
+const PROTECTED_ENV_KEYS: &[&str] = &[
+    "OPENCODE_SERVER_PASSWORD",
+    "VK_PROJECT_NAME",
+    "VK_PROJECT_ID",
+    "VK_TASK_ID",
+    "VK_WORKSPACE_ID",
+    "VK_WORKSPACE_BRANCH",
+];
+
+/// Placeholder shown instead of the value of a sensitive variable.
+pub const REDACTED_ENV_VALUE: &str = "<redacted>";
+
+/// Whether a variable name suggests it holds a credential.
+pub fn is_sensitive_env_key(key: &str) -> bool {
+    const SECRET_MARKERS: [&str; 6] = ["KEY", "TOKEN", "SECRET", "PASSWORD", "AUTH", "CREDENTIAL"];
+    let upper = key.to_ascii_uppercase();
+    SECRET_MARKERS.iter().any(|marker| upper.contains(marker))
+}
+
 /// Environment variables to inject into executor processes
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct ExecutionEnv {
     pub vars: HashMap<String, String>,
     pub repo_context: RepoContext,
@@ -56,14 +80,33 @@ impl ExecutionEnv {
             .extend(other.iter().map(|(k, v)| (k.clone(), v.clone())));
     }
 
+    /// Merge user-defined vars that apply to every executor. Protected keys are skipped.
+    ///
+    /// Precedence, lowest to highest: user env, runtime vars, executor profile env.
+    pub fn apply_user_env(&mut self, vars: &HashMap<String, String>) {
+        for (key, value) in vars {
+            if PROTECTED_ENV_KEYS.contains(&key.as_str()) || self.vars.contains_key(key) {
+                continue;
+            }
+            self.vars.insert(key.clone(), value.clone());
+        }
+    }
+
     /// Return a new env with overrides applied. Overrides take precedence.
     pub fn with_overrides(mut self, overrides: &HashMap<String, String>) -> Self {
         self.merge(overrides);
         self
     }
 
-    /// Return a new env with profile env from CmdOverrides merged in.
-    pub fn with_profile(self, cmd: &CmdOverrides) -> Self {
+    /// Return a new env with profile env from CmdOverrides merged in. The npm
+    /// registry and cache options go in first, so an explicit profile env wins.
+    pub fn with_profile(mut self, cmd: &CmdOverrides) -> Self {
+        if let Some(registry) = &cmd.npm_registry {
+            self.insert("NPM_CONFIG_REGISTRY", registry);
+        }
+        if let Some(cache_dir) = &cmd.npm_cache_dir {
+            self.insert("npm_config_cache", cache_dir);
+        }
         if let Some(ref profile_env) = cmd.env {
             self.with_overrides(profile_env)
         } else {
@@ -71,6 +114,12 @@ impl ExecutionEnv {
         }
     }
 
+    /// Return a new env that suppresses the commit reminder, for runs that should not commit.
+    pub fn without_commit_reminder(mut self) -> Self {
+        self.commit_reminder = false;
+        self
+    }
+
     /// Apply all environment variables to a Command
     pub fn apply_to_command(&self, command: &mut Command) {
         for (key, value) in &self.vars {
@@ -85,6 +134,42 @@ impl ExecutionEnv {
     pub fn get(&self, key: &str) -> Option<&String> {
         self.vars.get(key)
     }
+
+    /// Where an executor stores its sessions under `base` (e.g. `qwen_sessions`),
+    /// scoped by `VK_PROJECT_ID` so sessions from different projects can't
+    /// collide. Falls back to `base` when no project is set.
+    pub fn session_namespace(&self, base: &str) -> String {
+        match self.get("VK_PROJECT_ID") {
+            Some(project_id) => format!("{base}/{project_id}"),
+            None => base.to_string(),
+        }
+    }
+
+    /// The vars with sensitive values masked, sorted by key, for logging.
+    pub fn redacted(&self) -> BTreeMap<String, String> {
+        self.vars
+            .iter()
+            .map(|(key, value)| {
+                let value = if is_sensitive_env_key(key) {
+                    REDACTED_ENV_VALUE.to_string()
+                } else {
+                    value.clone()
+                };
+                (key.clone(), value)
+            })
+            .collect()
+    }
+}
+
+// Debug goes through `redacted` so tracing an env can't leak credentials
+impl fmt::Debug for ExecutionEnv {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ExecutionEnv")
+            .field("vars", &self.redacted())
+            .field("repo_context", &self.repo_context)
+            .field("commit_reminder", &self.commit_reminder)
+            .finish()
+    }
 }
 
 #[cfg(test)]
@@ -107,4 +192,74 @@ mod tests {
         assert_eq!(merged.vars.get("FOO").unwrap(), "profile"); // overrides
         assert_eq!(merged.vars.get("BAR").unwrap(), "profile");
     }
+
+    #[test]
+    fn redacted_masks_sensitive_keys() {
+        let mut env = ExecutionEnv::new(RepoContext::default(), false);
+        env.insert("OPENCODE_SERVER_PASSWORD", "hunter2");
+        env.insert("ANTHROPIC_API_KEY", "sk-ant-123");
+        env.insert("github_token", "ghp_123");
+        env.insert("VK_TASK_ID", "task-1");
+
+        let redacted = env.redacted();
+        assert_eq!(redacted["OPENCODE_SERVER_PASSWORD"], REDACTED_ENV_VALUE);
+        assert_eq!(redacted["ANTHROPIC_API_KEY"], REDACTED_ENV_VALUE);
+        assert_eq!(redacted["github_token"], REDACTED_ENV_VALUE);
+        assert_eq!(redacted["VK_TASK_ID"], "task-1");
+
+        let debug = format!("{env:?}");
+        assert!(!debug.contains("hunter2") && !debug.contains("sk-ant-123"));
+    }
+
+    #[test]
+    fn without_commit_reminder_keeps_vars() {
+        let mut env = ExecutionEnv::new(RepoContext::default(), true);
+        env.insert("VK_TASK_ID", "task");
+
+        let review_env = env.without_commit_reminder();
+
+        assert!(!review_env.commit_reminder);
+        assert_eq!(review_env.get("VK_TASK_ID").unwrap(), "task");
+    }
+
+    #[test]
+    fn session_namespace_is_scoped_per_project() {
+        let env_for = |project_id: Option<&str>| {
+            let mut env = ExecutionEnv::new(RepoContext::default(), false);
+            if let Some(project_id) = project_id {
+                env.insert("VK_PROJECT_ID", project_id);
+            }
+            env
+        };
+
+        let first = env_for(Some("project-a")).session_namespace("qwen_sessions");
+        let second = env_for(Some("project-b")).session_namespace("qwen_sessions");
+        assert_eq!(first, "qwen_sessions/project-a");
+        assert_ne!(first, second);
+        // A follow-up in the same project resolves to the same storage
+        assert_eq!(
+            env_for(Some("project-a")).session_namespace("qwen_sessions"),
+            first
+        );
+        assert_eq!(
+            env_for(None).session_namespace("qwen_sessions"),
+            "qwen_sessions"
+        );
+    }
+
+    #[test]
+    fn user_env_does_not_replace_protected_or_runtime_keys() {
+        let mut env = ExecutionEnv::new(RepoContext::default(), false);
+        env.insert("VK_TASK_ID", "runtime");
+
+        let mut user = HashMap::new();
+        user.insert("HTTPS_PROXY".to_string(), "http://proxy".to_string());
+        user.insert("VK_TASK_ID".to_string(), "user".to_string());
+        user.insert("OPENCODE_SERVER_PASSWORD".to_string(), "user".to_string());
+        env.apply_user_env(&user);
+
+        assert_eq!(env.get("HTTPS_PROXY").unwrap(), "http://proxy");
+        assert_eq!(env.get("VK_TASK_ID").unwrap(), "runtime");
+        assert!(!env.contains_key("OPENCODE_SERVER_PASSWORD"));
+    }
 }
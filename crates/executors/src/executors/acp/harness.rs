@@ -1,15 +1,18 @@
 use std::{
+    collections::VecDeque,
+    future::Future,
     path::{Path, PathBuf},
     process::Stdio,
     rc::Rc,
     sync::Arc,
+    time::Duration,
 };
 
 use agent_client_protocol as proto;
 use agent_client_protocol::Agent as _;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
-use futures::StreamExt;
-use tokio::{io::AsyncWriteExt, process::Command, sync::mpsc};
+use command_group::AsyncGroupChild;
+use futures::{Stream, StreamExt};
+use tokio::{io::AsyncWriteExt, sync::mpsc};
 use tokio_util::{
     compat::{TokioAsyncReadCompatExt, TokioAsyncWriteCompatExt},
     io::ReaderStream,
@@ -22,14 +25,27 @@ use crate::{
     approvals::ExecutorApprovalService,
     command::{CmdOverrides, CommandParts},
     env::ExecutionEnv,
-    executors::{ExecutorError, ExecutorExitResult, SpawnedChild, acp::AcpEvent},
+    executors::{
+        ExecutorError, ExecutorExitResult, SpawnedChild,
+        acp::AcpEvent,
+        node::{DEFAULT_SPAWN_RETRIES, spawn_node_executor},
+    },
 };
 
+/// How long to wait for the agent to answer the ACP initialize request. Generous
+/// because `npx` may have to download the agent package on a cold start.
+pub const DEFAULT_HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// How many of the agent's last stderr lines a failed handshake reports.
+const STDERR_TAIL_LINES: usize = 20;
+
 /// Reusable harness for ACP-based conns (Gemini, Qwen, etc.)
 pub struct AcpAgentHarness {
     session_namespace: String,
     model: Option<String>,
     mode: Option<String>,
+    protocol_version: Option<u16>,
+    handshake_timeout: Duration,
 }
 
 impl Default for AcpAgentHarness {
@@ -46,6 +62,8 @@ impl AcpAgentHarness {
             session_namespace: "gemini_sessions".to_string(),
             model: None,
             mode: None,
+            protocol_version: None,
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
         }
     }
 
@@ -55,6 +73,8 @@ impl AcpAgentHarness {
             session_namespace: namespace.into(),
             model: None,
             mode: None,
+            protocol_version: None,
+            handshake_timeout: DEFAULT_HANDSHAKE_TIMEOUT,
         }
     }
 
@@ -68,6 +88,26 @@ impl AcpAgentHarness {
         self
     }
 
+    /// Pin the ACP protocol version sent in the initialize handshake instead of
+    /// the default (V1).
+    pub fn with_protocol_version(mut self, version: u16) -> Self {
+        self.protocol_version = Some(version);
+        self
+    }
+
+    pub fn with_handshake_timeout(mut self, timeout: Duration) -> Self {
+        self.handshake_timeout = timeout;
+        self
+    }
+
+    fn initialize_request(&self) -> proto::InitializeRequest {
+        let version = self
+            .protocol_version
+            .map(proto::ProtocolVersion::from)
+            .unwrap_or(proto::ProtocolVersion::V1);
+        proto::InitializeRequest::new(version)
+    }
+
     pub async fn spawn_with_command(
         &self,
         current_dir: &Path,
@@ -77,23 +117,17 @@ impl AcpAgentHarness {
         cmd_overrides: &CmdOverrides,
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
-        let mut command = Command::new(program_path);
-        command
-            .kill_on_drop(true)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .current_dir(current_dir)
-            .env("NPM_CONFIG_LOGLEVEL", "error")
-            .env("NODE_NO_WARNINGS", "1")
-            .args(&args);
-
-        env.clone()
-            .with_profile(cmd_overrides)
-            .apply_to_command(&mut command);
-
-        let mut child = command.group_spawn()?;
+        let mut child = spawn_node_executor(
+            command_parts,
+            current_dir,
+            env,
+            cmd_overrides,
+            DEFAULT_SPAWN_RETRIES,
+            |command| {
+                command.stdin(Stdio::piped());
+            },
+        )
+        .await?;
 
         let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<ExecutorExitResult>();
         Self::bootstrap_acp_connection(
@@ -102,9 +136,11 @@ impl AcpAgentHarness {
             None,
             prompt,
             Some(exit_tx),
-            self.session_namespace.clone(),
+            env.session_namespace(&self.session_namespace),
             self.model.clone(),
             self.mode.clone(),
+            self.initialize_request(),
+            self.handshake_timeout,
             approvals,
         )
         .await?;
@@ -127,23 +163,17 @@ impl AcpAgentHarness {
         cmd_overrides: &CmdOverrides,
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let (program_path, args) = command_parts.into_resolved().await?;
-        let mut command = Command::new(program_path);
-        command
-            .kill_on_drop(true)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .current_dir(current_dir)
-            .env("NPM_CONFIG_LOGLEVEL", "error")
-            .env("NODE_NO_WARNINGS", "1")
-            .args(&args);
-
-        env.clone()
-            .with_profile(cmd_overrides)
-            .apply_to_command(&mut command);
-
-        let mut child = command.group_spawn()?;
+        let mut child = spawn_node_executor(
+            command_parts,
+            current_dir,
+            env,
+            cmd_overrides,
+            DEFAULT_SPAWN_RETRIES,
+            |command| {
+                command.stdin(Stdio::piped());
+            },
+        )
+        .await?;
 
         let (exit_tx, exit_rx) = tokio::sync::oneshot::channel::<ExecutorExitResult>();
         Self::bootstrap_acp_connection(
@@ -152,9 +182,11 @@ impl AcpAgentHarness {
             Some(session_id.to_string()),
             prompt,
             Some(exit_tx),
-            self.session_namespace.clone(),
+            env.session_namespace(&self.session_namespace),
             self.model.clone(),
             self.mode.clone(),
+            self.initialize_request(),
+            self.handshake_timeout,
             approvals,
         )
         .await?;
@@ -176,6 +208,8 @@ impl AcpAgentHarness {
         session_namespace: String,
         model: Option<String>,
         mode: Option<String>,
+        initialize_request: proto::InitializeRequest,
+        handshake_timeout: Duration,
         approvals: Option<std::sync::Arc<dyn ExecutorApprovalService>>,
     ) -> Result<(), ExecutorError> {
         // Take child's stdio for ACP wiring
@@ -192,6 +226,11 @@ impl AcpAgentHarness {
             ))
         })?;
 
+        // Keep the end of the agent's stderr to explain a handshake that never completes
+        let stderr_tail = StderrTail::default();
+        let stderr_lines = crate::stdout_dup::duplicate_stderr(child)?.lines();
+        tokio::spawn(stderr_tail.clone().collect(stderr_lines));
+
         // Create a fresh stdout pipe for logs
         let writer = crate::stdout_dup::create_stdout_pipe_writer(child)?;
         let shared_writer = Arc::new(tokio::sync::Mutex::new(writer));
@@ -310,9 +349,21 @@ impl AcpAgentHarness {
                         });
 
                         // Initialize
-                        let _ = conn
-                            .initialize(proto::InitializeRequest::new(proto::ProtocolVersion::V1))
-                            .await;
+                        if let Err(message) =
+                            await_handshake(conn.initialize(initialize_request), handshake_timeout)
+                                .await
+                        {
+                            let message = stderr_tail.append_to(message);
+                            error!("{}", message);
+                            let _ = log_tx.send(AcpEvent::Error(message).to_string());
+                            if let Some(tx) = exit_signal_tx.take() {
+                                let _ = tx.send(ExecutorExitResult::Failure);
+                            }
+                            drop(conn);
+                            let _ = shutdown_tx.send(true);
+                            io_handle.abort();
+                            return;
+                        }
 
                         // Handle session creation/forking
                         let (acp_session_id, display_session_id, prompt_to_send) =
@@ -509,3 +560,96 @@ impl AcpAgentHarness {
         Ok(())
     }
 }
+
+/// The agent's most recent stderr lines.
+#[derive(Clone, Default)]
+struct StderrTail(Arc<std::sync::Mutex<VecDeque<String>>>);
+
+impl StderrTail {
+    fn push(&self, line: String) {
+        let mut lines = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.len() == STDERR_TAIL_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    async fn collect(self, mut lines: impl Stream<Item = std::io::Result<String>> + Unpin) {
+        while let Some(Ok(line)) = lines.next().await {
+            self.push(line);
+        }
+    }
+
+    /// `message` followed by the captured stderr, if the agent wrote any.
+    fn append_to(&self, message: String) -> String {
+        let lines = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        if lines.is_empty() {
+            return message;
+        }
+        let tail: Vec<&str> = lines.iter().map(String::as_str).collect();
+        format!("{message}. Agent stderr:\n{}", tail.join("\n"))
+    }
+}
+
+/// Await the ACP initialize handshake. Errors from the agent are tolerated as
+/// before; only an unanswered handshake fails the run.
+async fn await_handshake<T>(
+    handshake: impl Future<Output = T>,
+    timeout: Duration,
+) -> Result<(), String> {
+    tokio::time::timeout(timeout, handshake)
+        .await
+        .map(|_| ())
+        .map_err(|_| {
+            format!(
+                "ACP agent did not complete the initialize handshake within {}s",
+                timeout.as_secs()
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn initialize_request_uses_pinned_protocol_version() {
+        let default = serde_json::to_value(AcpAgentHarness::new().initialize_request()).unwrap();
+        assert_eq!(default["protocolVersion"], 1);
+
+        let pinned = AcpAgentHarness::new().with_protocol_version(0);
+        let request = serde_json::to_value(pinned.initialize_request()).unwrap();
+        assert_eq!(request["protocolVersion"], 0);
+    }
+
+    #[tokio::test]
+    async fn handshake_fails_once_timeout_elapses() {
+        let slow_agent = tokio::time::sleep(Duration::from_secs(30));
+        let err = await_handshake(slow_agent, Duration::from_millis(20))
+            .await
+            .unwrap_err();
+        assert!(err.contains("initialize handshake"));
+
+        let responsive_agent = async { Ok::<_, ()>(()) };
+        assert!(
+            await_handshake(responsive_agent, Duration::from_millis(20))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn handshake_error_includes_the_stderr_tail() {
+        let tail = StderrTail::default();
+        let message = "ACP agent did not complete the initialize handshake".to_string();
+        assert_eq!(tail.append_to(message.clone()), message);
+
+        for i in 0..STDERR_TAIL_LINES + 5 {
+            tail.push(format!("line {i}"));
+        }
+        let message = tail.append_to(message);
+        assert!(message.ends_with(&format!("line {}", STDERR_TAIL_LINES + 4)));
+        assert!(message.contains("Agent stderr:\nline 5\n"));
+        assert!(!message.contains("line 4\n"));
+    }
+}
@@ -12,6 +12,9 @@ use crate::executors::acp::AcpEvent;
 /// Manages session persistence and state for ACP interactions
 pub struct SessionManager {
     base_dir: PathBuf,
+    /// The unscoped namespace root, where sessions were stored before they were
+    /// scoped per project. Follow-ups to those sessions still find them here.
+    legacy_dir: Option<PathBuf>,
 }
 
 impl SessionManager {
@@ -27,10 +30,14 @@ impl SessionManager {
         }
 
         let base_dir = vk_dir.join(&namespace);
+        let legacy_dir = namespace.split_once('/').map(|(root, _)| vk_dir.join(root));
 
         fs::create_dir_all(&base_dir)?;
 
-        Ok(Self { base_dir })
+        Ok(Self {
+            base_dir,
+            legacy_dir,
+        })
     }
 
     /// Get the file path for a session
@@ -38,6 +45,16 @@ impl SessionManager {
         self.base_dir.join(format!("{session_id}.jsonl"))
     }
 
+    /// The file of an existing session, looking in the legacy directory if it
+    /// isn't in the scoped one.
+    fn existing_session_file_path(&self, session_id: &str) -> PathBuf {
+        let path = self.session_file_path(session_id);
+        match &self.legacy_dir {
+            Some(legacy_dir) if !path.exists() => legacy_dir.join(format!("{session_id}.jsonl")),
+            _ => path,
+        }
+    }
+
     /// Append a raw JSON line to the session log
     ///
     /// We normalize ACP payloads by:
@@ -112,7 +129,7 @@ impl SessionManager {
 
     /// Read the raw JSONL content of a session
     pub fn read_session_raw(&self, session_id: &str) -> Result<String> {
-        let path = self.session_file_path(session_id);
+        let path = self.existing_session_file_path(session_id);
         if !path.exists() {
             return Ok(String::new());
         }
@@ -122,7 +139,7 @@ impl SessionManager {
 
     /// Fork a session to create a new one with the same history
     pub fn fork_session(&self, old_id: &str, new_id: &str) -> Result<()> {
-        let old_path = self.session_file_path(old_id);
+        let old_path = self.existing_session_file_path(old_id);
         let new_path = self.session_file_path(new_id);
 
         if old_path.exists() {
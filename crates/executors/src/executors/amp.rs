@@ -34,8 +34,9 @@ pub struct Amp {
 
 impl Amp {
     fn build_command_builder(&self) -> Result<CommandBuilder, CommandBuildError> {
-        let mut builder = CommandBuilder::new("npx -y @sourcegraph/amp@0.0.1764777697-g907e30")
-            .params(["--execute", "--stream-json"]);
+        let mut builder =
+            CommandBuilder::npm_package("@sourcegraph/amp@0.0.1764777697-g907e30", "amp")
+                .params(["--execute", "--stream-json"]);
         if self.dangerously_allow_all.unwrap_or(false) {
             builder = builder.extend_params(["--dangerously-allow-all"]);
         }
@@ -45,6 +46,10 @@ impl Amp {
 
 #[async_trait]
 impl StandardCodingAgentExecutor for Amp {
+    async fn command_preview(&self) -> Result<Option<CommandBuilder>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?))
+    }
+
     async fn spawn(
         &self,
         current_dir: &Path,
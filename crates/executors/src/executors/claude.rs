@@ -1,5 +1,6 @@
 // SDK submodules
 pub mod client;
+pub mod instructions;
 pub mod protocol;
 pub mod slash_commands;
 pub mod types;
@@ -25,6 +26,7 @@ use workspace_utils::{
 
 use self::{
     client::{AUTO_APPROVE_CALLBACK_ID, ClaudeAgentClient, STOP_GIT_CHECK_CALLBACK_ID},
+    instructions::InjectedInstructions,
     protocol::ProtocolPeer,
     types::{ControlRequestType, ControlResponseType, PermissionMode},
 };
@@ -33,8 +35,10 @@ use crate::{
     command::{CmdOverrides, CommandBuildError, CommandBuilder, CommandParts, apply_overrides},
     env::ExecutionEnv,
     executors::{
-        AppendPrompt, AvailabilityInfo, ExecutorError, SpawnedChild, StandardCodingAgentExecutor,
-        codex::client::LogWriter, utils::reorder_slash_commands,
+        AppendPrompt, AvailabilityInfo, ExecutorError, SlashCommandDescription, SpawnedChild,
+        StandardCodingAgentExecutor,
+        codex::client::LogWriter,
+        utils::{filter_slash_commands, reorder_slash_commands},
     },
     logs::{
         ActionType, FileChange, NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
@@ -48,16 +52,42 @@ use crate::{
     stdout_dup::create_stdout_pipe_writer,
 };
 
-fn base_command(claude_code_router: bool) -> &'static str {
+fn base_command(claude_code_router: bool) -> CommandBuilder {
     if claude_code_router {
-        "npx -y @musistudio/claude-code-router@1.0.66 code"
+        CommandBuilder::npm_package("@musistudio/claude-code-router@1.0.66 code", "ccr code")
     } else {
-        "npx -y @anthropic-ai/claude-code@2.1.12"
+        CommandBuilder::npm_package("@anthropic-ai/claude-code@2.1.12", "claude")
     }
 }
 
 use derivative::Derivative;
 
+/// Where to look for custom slash command and skill files.
+///
+/// - `project_only`: the project's `.claude` directory.
+/// - `project_and_global`: the project plus `~/.claude`.
+/// - `all`: the project, `~/.claude` and installed plugins (default).
+///
+/// Commands Claude itself reports from outside the scope are not listed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, TS, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CommandScanScope {
+    ProjectOnly,
+    ProjectAndGlobal,
+    #[default]
+    All,
+}
+
+impl CommandScanScope {
+    fn includes_global(self) -> bool {
+        matches!(self, Self::ProjectAndGlobal | Self::All)
+    }
+
+    fn includes_plugins(self) -> bool {
+        matches!(self, Self::All)
+    }
+}
+
 #[derive(Derivative, Clone, Serialize, Deserialize, TS, JsonSchema)]
 #[derivative(Debug, PartialEq)]
 pub struct ClaudeCode {
@@ -75,6 +105,24 @@ pub struct ClaudeCode {
     pub dangerously_skip_permissions: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_api_key: Option<bool>,
+    /// Path to an instructions file copied into the working directory as
+    /// `.claude/CLAUDE.md` for the run. The copy is git-ignored and removed
+    /// when the session ends, including when it fails to start; an existing
+    /// project CLAUDE.md is left untouched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub instructions_file: Option<String>,
+    /// Where custom slash commands are discovered from (default: all)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_scan_scope: Option<CommandScanScope>,
+    /// Seconds to wait for slash command discovery before giving up (default: 120)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discovery_timeout_secs: Option<u32>,
+    /// Slash commands offered for this profile (default: all discovered commands)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_slash_commands: Option<Vec<String>>,
+    /// Slash commands hidden for this profile, even if also allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deny_slash_commands: Option<Vec<String>>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
 
@@ -85,6 +133,18 @@ pub struct ClaudeCode {
 }
 
 impl ClaudeCode {
+    /// Apply the profile's `allow_slash_commands` / `deny_slash_commands`.
+    fn visible_slash_commands(
+        &self,
+        commands: Vec<SlashCommandDescription>,
+    ) -> Vec<SlashCommandDescription> {
+        filter_slash_commands(
+            commands,
+            self.allow_slash_commands.as_deref(),
+            self.deny_slash_commands.as_deref(),
+        )
+    }
+
     async fn build_command_builder(&self) -> Result<CommandBuilder, CommandBuildError> {
         // If base_command_override is provided and claude_code_router is also set, log a warning
         if self.cmd.base_command_override.is_some() && self.claude_code_router.is_some() {
@@ -93,9 +153,7 @@ impl ClaudeCode {
             );
         }
 
-        let mut builder =
-            CommandBuilder::new(base_command(self.claude_code_router.unwrap_or(false)))
-                .params(["-p"]);
+        let mut builder = base_command(self.claude_code_router.unwrap_or(false)).params(["-p"]);
 
         let plan = self.plan.unwrap_or(false);
         let approvals = self.approvals.unwrap_or(false);
@@ -182,6 +240,10 @@ impl ClaudeCode {
 
 #[async_trait]
 impl StandardCodingAgentExecutor for ClaudeCode {
+    async fn command_preview(&self) -> Result<Option<CommandBuilder>, ExecutorError> {
+        Ok(Some(self.build_command_builder().await?))
+    }
+
     fn use_approvals(&mut self, approvals: Arc<dyn ExecutorApprovalService>) {
         self.approvals_service = Some(approvals);
     }
@@ -260,17 +322,22 @@ impl StandardCodingAgentExecutor for ClaudeCode {
         let this = self.clone();
         let current_dir = current_dir.to_path_buf();
 
-        let initial = patch::slash_commands(defaults.clone(), true, None);
+        let initial =
+            patch::slash_commands(this.visible_slash_commands(defaults.clone()), true, None);
 
         let discovery_stream = futures::stream::once(async move {
             match this.discover_available_slash_commands(&current_dir).await {
                 Ok(commands) => {
                     let merged = reorder_slash_commands([commands, defaults].concat());
-                    patch::slash_commands(merged, false, None)
+                    patch::slash_commands(this.visible_slash_commands(merged), false, None)
                 }
                 Err(e) => {
                     tracing::warn!("Failed to discover Claude Code slash commands: {}", e);
-                    patch::slash_commands(defaults, false, Some(e.to_string()))
+                    patch::slash_commands(
+                        this.visible_slash_commands(defaults),
+                        false,
+                        Some(e.to_string()),
+                    )
                 }
             }
         });
@@ -282,6 +349,24 @@ impl StandardCodingAgentExecutor for ClaudeCode {
 }
 
 impl ClaudeCode {
+    fn inject_instructions(
+        &self,
+        current_dir: &Path,
+    ) -> Result<Option<InjectedInstructions>, ExecutorError> {
+        let Some(source) = self.instructions_file.as_deref() else {
+            return Ok(None);
+        };
+        let injected = InjectedInstructions::inject(Path::new(source), current_dir)
+            .map_err(ExecutorError::Io)?;
+        if injected.is_none() {
+            tracing::warn!(
+                "Not injecting {source}: {} already has a .claude/CLAUDE.md",
+                current_dir.display()
+            );
+        }
+        Ok(injected)
+    }
+
     async fn spawn_internal(
         &self,
         current_dir: &Path,
@@ -312,6 +397,8 @@ impl ClaudeCode {
             tracing::info!("ANTHROPIC_API_KEY removed from environment");
         }
 
+        let instructions = self.inject_instructions(current_dir)?;
+
         let mut child = command.group_spawn()?;
         let child_stdout = child.inner().stdout.take().ok_or_else(|| {
             ExecutorError::Io(std::io::Error::other("Claude Code missing stdout"))
@@ -335,7 +422,7 @@ impl ClaudeCode {
         tokio::spawn(async move {
             let log_writer = LogWriter::new(new_stdout);
             let client = ClaudeAgentClient::new(log_writer.clone(), approvals_clone, repo_context);
-            let protocol_peer =
+            let (protocol_peer, reader) =
                 ProtocolPeer::spawn(child_stdin, child_stdout, client.clone(), interrupt_rx);
 
             // Initialize control protocol
@@ -357,7 +444,14 @@ impl ClaudeCode {
                 let _ = log_writer
                     .log_raw(&format!("Error: Failed to send prompt - {e}"))
                     .await;
+                return;
+            }
+
+            // Keep injected instructions in place until the session is over
+            if instructions.is_some() {
+                let _ = reader.await;
             }
+            drop(instructions);
         });
 
         Ok(SpawnedChild {
@@ -2280,11 +2374,19 @@ mod tests {
             dangerously_skip_permissions: None,
             cmd: crate::command::CmdOverrides {
                 base_command_override: None,
+                package_runner: None,
+                npm_registry: None,
+                npm_cache_dir: None,
                 additional_params: None,
                 env: None,
             },
             approvals_service: None,
             disable_api_key: None,
+            instructions_file: None,
+            command_scan_scope: None,
+            discovery_timeout_secs: None,
+            allow_slash_commands: None,
+            deny_slash_commands: None,
         };
         let msg_store = Arc::new(MsgStore::new());
         let current_dir = std::path::PathBuf::from("/tmp/test-worktree");
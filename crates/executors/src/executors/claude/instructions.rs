@@ -0,0 +1,187 @@
+use std::{
+    fs,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Where Claude Code looks for project memory, relative to the working directory.
+const PROJECT_MEMORY_PATH: &str = ".claude/CLAUDE.md";
+
+/// An external instructions file copied into the working directory for the
+/// duration of a run. Dropping it removes the copy, and the `.claude`
+/// directory too if the injection created it, so the worktree is left as it
+/// was found even when the run fails early. The copy is excluded from git so
+/// the commit made when the agent exits, which can run before the copy is
+/// removed, never includes it.
+#[derive(Debug)]
+pub struct InjectedInstructions {
+    target: PathBuf,
+    created_dir: Option<PathBuf>,
+}
+
+impl InjectedInstructions {
+    /// Copy `source` to `.claude/CLAUDE.md` under `working_dir`. Returns `None`
+    /// without touching anything if the project already has that file.
+    pub fn inject(source: &Path, working_dir: &Path) -> io::Result<Option<Self>> {
+        let target = working_dir.join(PROJECT_MEMORY_PATH);
+        if target.exists() {
+            return Ok(None);
+        }
+
+        let dir = target
+            .parent()
+            .expect("project memory path has a parent")
+            .to_path_buf();
+        let created_dir = if dir.exists() {
+            None
+        } else {
+            fs::create_dir_all(&dir)?;
+            Some(dir)
+        };
+
+        let injected = Self {
+            target,
+            created_dir,
+        };
+        exclude_from_git(working_dir)?;
+        fs::copy(source, &injected.target)?;
+        Ok(Some(injected))
+    }
+}
+
+/// Add the project memory path under `working_dir` to the repository's
+/// `info/exclude`, which all of its worktrees share. The entry is left in place
+/// afterwards: it only names a file the project doesn't have, and removing it
+/// could expose a copy injected by a concurrent run. Outside a git repository
+/// there is nothing to exclude from.
+fn exclude_from_git(working_dir: &Path) -> io::Result<()> {
+    let Ok(output) = Command::new("git")
+        .args(["rev-parse", "--show-prefix", "--git-path", "info/exclude"])
+        .current_dir(working_dir)
+        .output()
+    else {
+        return Ok(());
+    };
+    if !output.status.success() {
+        return Ok(());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let (Some(prefix), Some(exclude_path)) = (lines.next(), lines.next()) else {
+        return Err(io::Error::other("Unexpected `git rev-parse` output"));
+    };
+
+    let exclude_path = working_dir.join(exclude_path);
+    let pattern = format!("/{prefix}{PROJECT_MEMORY_PATH}");
+    let existing = match fs::read_to_string(&exclude_path) {
+        Ok(existing) => existing,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+        Err(e) => return Err(e),
+    };
+    if existing.lines().any(|line| line == pattern) {
+        return Ok(());
+    }
+
+    if let Some(dir) = exclude_path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&exclude_path)?;
+    if !existing.is_empty() && !existing.ends_with('\n') {
+        writeln!(file)?;
+    }
+    writeln!(file, "{pattern}")
+}
+
+impl Drop for InjectedInstructions {
+    fn drop(&mut self) {
+        if let Err(e) = fs::remove_file(&self.target)
+            && e.kind() != io::ErrorKind::NotFound
+        {
+            tracing::warn!(
+                "Failed to remove injected instructions {}: {e}",
+                self.target.display()
+            );
+        }
+        if let Some(dir) = &self.created_dir {
+            // Only succeeds if empty, i.e. the agent didn't add anything else there
+            let _ = fs::remove_dir(dir);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn instructions_exist_while_injected_and_are_removed_after() {
+        let root = std::env::temp_dir().join(format!("vk-claude-md-{}", uuid::Uuid::new_v4()));
+        let worktree = root.join("worktree");
+        fs::create_dir_all(&worktree).unwrap();
+        let source = root.join("team-instructions.md");
+        fs::write(&source, "Always run the linter.").unwrap();
+
+        let injected = InjectedInstructions::inject(&source, &worktree)
+            .unwrap()
+            .expect("injected");
+        let target = worktree.join(PROJECT_MEMORY_PATH);
+        assert_eq!(
+            fs::read_to_string(&target).unwrap(),
+            "Always run the linter."
+        );
+
+        drop(injected);
+        assert!(!target.exists());
+        assert!(!worktree.join(".claude").exists());
+
+        // An existing project CLAUDE.md is never replaced
+        fs::create_dir_all(worktree.join(".claude")).unwrap();
+        fs::write(&target, "project rules").unwrap();
+        assert!(
+            InjectedInstructions::inject(&source, &worktree)
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(fs::read_to_string(&target).unwrap(), "project rules");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn injected_instructions_are_ignored_by_git() {
+        let root = std::env::temp_dir().join(format!("vk-claude-md-{}", uuid::Uuid::new_v4()));
+        let repo = root.join("repo");
+        let working_dir = repo.join("app");
+        fs::create_dir_all(&working_dir).unwrap();
+        let git = |args: &[&str]| {
+            let output = Command::new("git")
+                .args(args)
+                .current_dir(&working_dir)
+                .output()
+                .unwrap();
+            assert!(output.status.success());
+            String::from_utf8(output.stdout).unwrap()
+        };
+        git(&["init", "--quiet", ".."]);
+        let source = root.join("team-instructions.md");
+        fs::write(&source, "Always run the linter.").unwrap();
+
+        let injected = InjectedInstructions::inject(&source, &working_dir)
+            .unwrap()
+            .expect("injected");
+        assert!(working_dir.join(PROJECT_MEMORY_PATH).exists());
+        assert_eq!(git(&["status", "--porcelain", "--untracked-files=all"]), "");
+
+        // Injecting again doesn't repeat the entry
+        drop(injected);
+        drop(InjectedInstructions::inject(&source, &working_dir).unwrap());
+        let exclude = fs::read_to_string(repo.join(".git/info/exclude")).unwrap();
+        assert_eq!(exclude.matches("/app/.claude/CLAUDE.md").count(), 1);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -5,6 +5,7 @@ use tokio::{
     io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
     process::{ChildStdin, ChildStdout},
     sync::{Mutex, oneshot},
+    task::JoinHandle,
 };
 
 use super::types::{CLIMessage, ControlRequestType, ControlResponseMessage, ControlResponseType};
@@ -23,24 +24,26 @@ pub struct ProtocolPeer {
 }
 
 impl ProtocolPeer {
+    /// Start reading the agent's stdout. The returned handle completes once the
+    /// session ends (EOF, a result message or an interrupt).
     pub fn spawn(
         stdin: ChildStdin,
         stdout: ChildStdout,
         client: Arc<ClaudeAgentClient>,
         interrupt_rx: oneshot::Receiver<()>,
-    ) -> Self {
+    ) -> (Self, JoinHandle<()>) {
         let peer = Self {
             stdin: Arc::new(Mutex::new(stdin)),
         };
 
         let reader_peer = peer.clone();
-        tokio::spawn(async move {
+        let reader = tokio::spawn(async move {
             if let Err(e) = reader_peer.read_loop(stdout, client, interrupt_rx).await {
                 tracing::error!("Protocol reader loop error: {}", e);
             }
         });
 
-        peer
+        (peer, reader)
     }
 
     async fn read_loop(
@@ -1,29 +1,164 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{HashMap, HashSet, VecDeque},
+    num::NonZeroUsize,
     path::{Path, PathBuf},
     process::Stdio,
-    sync::OnceLock,
-    time::Duration,
+    sync::{Mutex, OnceLock},
+    time::{Duration, SystemTime},
 };
 
 use command_group::AsyncCommandGroup;
+use lru::LruCache;
 use tokio::{
-    io::{AsyncBufReadExt, BufReader},
+    io::{AsyncBufRead, AsyncReadExt, BufReader},
     process::Command,
 };
 use walkdir::WalkDir;
+use workspace_utils::stream_lines::lossy_lines;
 
-use super::{ClaudeCode, ClaudeJson, ClaudePlugin, base_command};
+use super::{ClaudeCode, ClaudeJson, ClaudePlugin, CommandScanScope, base_command};
 use crate::{
     command::{CommandBuildError, CommandBuilder, apply_overrides},
     env::{ExecutionEnv, RepoContext},
     executors::{
-        BaseCodingAgent, ExecutorError, SlashCommandDescription,
-        utils::{SlashCommandCache, SlashCommandCacheKey},
+        BaseCodingAgent, ExecutorError, SlashCommandDescription, SlashCommandScope,
+        utils::{
+            SLASH_COMMANDS_CACHE_CAPACITY, SlashCommandCache, SlashCommandCacheKey, discover_within,
+        },
     },
 };
 
+/// Used when the profile doesn't set `discovery_timeout_secs`.
 const SLASH_COMMANDS_DISCOVERY_TIMEOUT: Duration = Duration::from_secs(120);
+/// Bytes of discovery output read without seeing the init event before giving
+/// up. Counts every byte, so a single line that never ends can't grow unbounded.
+const SLASH_COMMANDS_DISCOVERY_MAX_BYTES: u64 = 4 * 1024 * 1024;
+/// Lines of discovery output kept for the error when giving up.
+const SLASH_COMMANDS_DISCOVERY_TAIL_LINES: usize = 12;
+
+/// Modification times of the `.claude` directories that Claude Code reads
+/// commands and plugins from, plus the entries inside them whose changes don't
+/// touch the directory itself: the `commands`, `skills` and `plugins`
+/// subdirectories, the installed plugin list, and the settings files that
+/// enable plugins.
+type ClaudeDirStamp = Vec<Option<SystemTime>>;
+
+const CLAUDE_DIR_STAMPED_ENTRIES: [&str; 6] = [
+    "commands",
+    "skills",
+    "plugins",
+    "plugins/installed_plugins.json",
+    "settings.json",
+    "settings.local.json",
+];
+
+fn claude_dir_stamp(current_dir: &Path, home_dir: Option<&Path>) -> ClaudeDirStamp {
+    let mtime = |path: PathBuf| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    std::iter::once(current_dir)
+        .chain(home_dir)
+        .map(|dir| dir.join(".claude"))
+        .flat_map(|claude_dir| {
+            CLAUDE_DIR_STAMPED_ENTRIES
+                .iter()
+                .map(|entry| mtime(claude_dir.join(entry)))
+                .chain([mtime(claude_dir.clone())])
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Command names and plugins from the slow `claude -p /` run, reused until the
+/// `.claude` directories change. Outlives [`SlashCommandCache`]'s TTL, so an
+/// expired entry there only costs the file walk.
+struct PluginDiscoveryCache {
+    cache: Mutex<LruCache<PathBuf, (ClaudeDirStamp, Vec<String>, Vec<ClaudePlugin>)>>,
+}
+
+impl PluginDiscoveryCache {
+    fn new() -> Self {
+        Self {
+            cache: Mutex::new(LruCache::new(
+                NonZeroUsize::new(SLASH_COMMANDS_CACHE_CAPACITY).unwrap(),
+            )),
+        }
+    }
+
+    fn instance() -> &'static Self {
+        static INSTANCE: OnceLock<PluginDiscoveryCache> = OnceLock::new();
+        INSTANCE.get_or_init(Self::new)
+    }
+
+    fn get(
+        &self,
+        current_dir: &Path,
+        stamp: &ClaudeDirStamp,
+    ) -> Option<(Vec<String>, Vec<ClaudePlugin>)> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        match cache.get(current_dir) {
+            Some((cached_stamp, names, plugins)) if cached_stamp == stamp => {
+                Some((names.clone(), plugins.clone()))
+            }
+            Some(_) => {
+                cache.pop(current_dir);
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn put(
+        &self,
+        current_dir: &Path,
+        stamp: ClaudeDirStamp,
+        names: Vec<String>,
+        plugins: Vec<ClaudePlugin>,
+    ) {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.put(current_dir.to_path_buf(), (stamp, names, plugins));
+    }
+}
+
+/// Read Claude Code's stream-json output up to its init event and return the
+/// command names and plugins it lists, or `None` if the output ends first.
+/// Gives up after `max_bytes` bytes without one, with the last few lines in the
+/// error.
+async fn read_init_event<R: AsyncBufRead + Unpin>(
+    reader: R,
+    max_bytes: u64,
+) -> Result<Option<(Vec<String>, Vec<ClaudePlugin>)>, ExecutorError> {
+    let mut lines = lossy_lines(reader.take(max_bytes));
+    let mut tail = VecDeque::with_capacity(SLASH_COMMANDS_DISCOVERY_TAIL_LINES);
+    while let Some(line) = lines.next_line().await.map_err(ExecutorError::Io)? {
+        if let Ok(ClaudeJson::System {
+            subtype,
+            slash_commands,
+            plugins,
+            ..
+        }) = serde_json::from_str::<ClaudeJson>(&line)
+            && matches!(subtype.as_deref(), Some("init"))
+        {
+            return Ok(Some((slash_commands, plugins)));
+        }
+
+        if tail.len() == SLASH_COMMANDS_DISCOVERY_TAIL_LINES {
+            tail.pop_front();
+        }
+        tail.push_back(line);
+    }
+    if lines.get_ref().limit() == 0 {
+        let tail = Vec::from(tail).join("\n");
+        return Err(ExecutorError::Io(std::io::Error::other(format!(
+            "No init event in the first {max_bytes} bytes of Claude Code output. Output tail:\n{tail}"
+        ))));
+    }
+    Ok(None)
+}
+
+/// A command or skill found on disk.
+struct CustomCommand {
+    description: Option<String>,
+    scope: SlashCommandScope,
+}
 
 impl ClaudeCode {
     fn extract_description(content: &str) -> Option<String> {
@@ -44,13 +179,24 @@ impl ClaudeCode {
         None
     }
 
-    fn discover_custom_command_descriptions(
+    fn discover_custom_commands(
         current_dir: &Path,
+        home_dir: Option<&Path>,
         plugins: &[ClaudePlugin],
-    ) -> HashMap<String, String> {
-        let mut descriptions = HashMap::new();
+        scope: CommandScanScope,
+    ) -> HashMap<String, CustomCommand> {
+        let mut commands = HashMap::new();
+
+        let mut scan = |base_path: PathBuf, scope: SlashCommandScope| {
+            let prefix = match &scope {
+                SlashCommandScope::Plugin { name } => Some(name.clone()),
+                _ => None,
+            };
+            let key = |name: &str| match &prefix {
+                Some(p) => format!("{}:{}", p, name),
+                None => name.to_string(),
+            };
 
-        let mut scan = |base_path: PathBuf, prefix: Option<&str>| {
             // Commands: base_path/commands/*.md
             let commands_dir = base_path.join("commands");
             if commands_dir.exists() {
@@ -63,15 +209,14 @@ impl ClaudeCode {
                     if path.is_file()
                         && path.extension().is_some_and(|ext| ext == "md")
                         && let Some(name) = path.file_stem().and_then(|s| s.to_str())
-                        && let Ok(content) = std::fs::read_to_string(path)
-                        && let Some(desc) = Self::extract_description(&content)
                     {
-                        let key = if let Some(p) = prefix {
-                            format!("{}:{}", p, name)
-                        } else {
-                            name.to_string()
-                        };
-                        descriptions.insert(key, desc);
+                        let description = std::fs::read_to_string(path)
+                            .ok()
+                            .and_then(|content| Self::extract_description(&content));
+                        commands.entry(key(name)).or_insert(CustomCommand {
+                            description,
+                            scope: scope.clone(),
+                        });
                     }
                 }
             }
@@ -92,36 +237,129 @@ impl ClaudeCode {
                             .parent()
                             .and_then(|p| p.file_name())
                             .and_then(|s| s.to_str())
-                            && let Ok(content) = std::fs::read_to_string(path)
-                            && let Some(desc) = Self::extract_description(&content)
                         {
-                            let key = if let Some(p) = prefix {
-                                format!("{}:{}", p, parent)
-                            } else {
-                                parent.to_string()
-                            };
-                            descriptions.insert(key, desc);
+                            let description = std::fs::read_to_string(path)
+                                .ok()
+                                .and_then(|content| Self::extract_description(&content));
+                            commands.entry(key(parent)).or_insert(CustomCommand {
+                                description,
+                                scope: scope.clone(),
+                            });
                         }
                     }
                 }
             }
         };
 
-        // Project specific
-        scan(current_dir.join(".claude"), None);
+        // Project specific. Scanned first so a project command keeps its entry
+        // over a global one with the same name.
+        scan(current_dir.join(".claude"), SlashCommandScope::Project);
 
         // Global
-        if let Some(home) = dirs::home_dir() {
-            scan(home.join(".claude"), None);
+        if scope.includes_global()
+            && let Some(home) = home_dir
+        {
+            scan(home.join(".claude"), SlashCommandScope::Global);
         }
 
         // Plugins
+        let plugins = if scope.includes_plugins() {
+            plugins
+        } else {
+            &[]
+        };
         for plugin in plugins {
-            scan(plugin.path.clone(), Some(&plugin.name));
-            scan(plugin.path.join(".claude"), Some(&plugin.name));
+            let scope = SlashCommandScope::Plugin {
+                name: plugin.name.clone(),
+            };
+            scan(plugin.path.clone(), scope.clone());
+            scan(plugin.path.join(".claude"), scope);
         }
 
-        descriptions
+        commands
+    }
+
+    /// Names of the commands and skills under `base_path`, without reading them.
+    fn custom_command_names(base_path: &Path) -> Vec<String> {
+        let entries = |dir: PathBuf| {
+            std::fs::read_dir(dir)
+                .into_iter()
+                .flatten()
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+        };
+        let commands = entries(base_path.join("commands")).filter_map(|path| {
+            (path.is_file() && path.extension().is_some_and(|ext| ext == "md"))
+                .then(|| path.file_stem()?.to_str().map(str::to_string))
+                .flatten()
+        });
+        let skills = entries(base_path.join("skills")).filter_map(|path| {
+            path.join("SKILL.md")
+                .is_file()
+                .then(|| path.file_name()?.to_str().map(str::to_string))
+                .flatten()
+        });
+        commands.chain(skills).collect()
+    }
+
+    /// Commands defined only outside `scope`: in `~/.claude` when global
+    /// commands are excluded, and in plugins when plugins are excluded.
+    fn out_of_scope_command_names(
+        home_dir: Option<&Path>,
+        plugins: &[ClaudePlugin],
+        scope: CommandScanScope,
+    ) -> HashSet<String> {
+        let mut names = HashSet::new();
+        if !scope.includes_global()
+            && let Some(home) = home_dir
+        {
+            names.extend(Self::custom_command_names(&home.join(".claude")));
+        }
+        if !scope.includes_plugins() {
+            for plugin in plugins {
+                for base_path in [plugin.path.clone(), plugin.path.join(".claude")] {
+                    names.extend(
+                        Self::custom_command_names(&base_path)
+                            .into_iter()
+                            .map(|name| format!("{}:{}", plugin.name, name)),
+                    );
+                }
+            }
+        }
+        names
+    }
+
+    /// Describe the command `names` Claude Code reported, dropping built-ins
+    /// and duplicates, and commands that only exist outside `scope`.
+    fn scoped_slash_commands(
+        names: Vec<String>,
+        current_dir: &Path,
+        home_dir: Option<&Path>,
+        plugins: &[ClaudePlugin],
+        scope: CommandScanScope,
+    ) -> Vec<SlashCommandDescription> {
+        let custom = Self::discover_custom_commands(current_dir, home_dir, plugins, scope);
+        let out_of_scope = Self::out_of_scope_command_names(home_dir, plugins, scope);
+        let builtin: HashSet<String> = Self::hardcoded_slash_commands()
+            .iter()
+            .map(|c| c.name.clone())
+            .collect();
+
+        let mut seen = HashSet::new();
+        names
+            .into_iter()
+            .filter(|name| !name.is_empty() && !builtin.contains(name) && seen.insert(name.clone()))
+            // A project command shadows a global one with the same name
+            .filter(|name| custom.contains_key(name) || !out_of_scope.contains(name))
+            .map(|name| {
+                let custom = custom.get(&name);
+                SlashCommandDescription {
+                    description: custom.and_then(|c| c.description.clone()),
+                    scope: custom.map(|c| c.scope.clone()),
+                    name,
+                }
+            })
+            .collect()
     }
 
     pub(super) fn hardcoded_slash_commands() -> Vec<SlashCommandDescription> {
@@ -134,10 +372,12 @@ impl ClaudeCode {
                         "Clear conversation history but keep a summary in context. Optional: /compact [instructions for summarization]"
                             .to_string(),
                     ),
+                    scope: None,
                 },
                 SlashCommandDescription {
                     name: "review".to_string(),
                     description: Some("Review a pull request".to_string()),
+                    scope: None,
                 },
                 SlashCommandDescription {
                     name: "security-review".to_string(),
@@ -145,32 +385,38 @@ impl ClaudeCode {
                         "Complete a security review of the pending changes on the current branch"
                             .to_string(),
                     ),
+                    scope: None,
                 },
                 SlashCommandDescription {
                     name: "init".to_string(),
                     description: Some(
                         "Initialize a new CLAUDE.md file with codebase documentation".to_string(),
                     ),
+                    scope: None,
                 },
                 SlashCommandDescription {
                     name: "pr-comments".to_string(),
                     description: Some("Get comments from a GitHub pull request".to_string()),
+                    scope: None,
                 },
                 SlashCommandDescription {
                     name: "context".to_string(),
                     description: Some(
                         "Visualize current context usage as a colored grid".to_string(),
                     ),
+                    scope: None,
                 },
                 SlashCommandDescription {
                     name: "cost".to_string(),
                     description: Some(
                         "Show the total cost and duration of the current session".to_string(),
                     ),
+                    scope: None,
                 },
                 SlashCommandDescription {
                     name: "release-notes".to_string(),
                     description: Some("View release notes".to_string()),
+                    scope: None,
                 },
             ]
         }).clone()
@@ -179,9 +425,7 @@ impl ClaudeCode {
     async fn build_slash_commands_discovery_command_builder(
         &self,
     ) -> Result<CommandBuilder, CommandBuildError> {
-        let mut builder =
-            CommandBuilder::new(base_command(self.claude_code_router.unwrap_or(false)))
-                .params(["-p"]);
+        let mut builder = base_command(self.claude_code_router.unwrap_or(false)).params(["-p"]);
 
         builder = builder.extend_params([
             "--verbose",
@@ -227,83 +471,290 @@ impl ClaudeCode {
             ExecutorError::Io(std::io::Error::other("Claude Code missing stdout"))
         })?;
 
-        let mut lines = BufReader::new(stdout).lines();
-
-        let mut discovered: Option<(Vec<String>, Vec<ClaudePlugin>)> = None;
-        let discovery = async {
-            while let Some(line) = lines.next_line().await.map_err(ExecutorError::Io)? {
-                if let Ok(json) = serde_json::from_str::<ClaudeJson>(&line)
-                    && let ClaudeJson::System {
-                        subtype,
-                        slash_commands,
-                        plugins,
-                        ..
-                    } = &json
-                    && matches!(subtype.as_deref(), Some("init"))
-                {
-                    discovered = Some((slash_commands.clone(), plugins.clone()));
-                    break;
-                }
-            }
-
-            Ok::<(), ExecutorError>(())
-        };
+        let discovery = read_init_event(BufReader::new(stdout), SLASH_COMMANDS_DISCOVERY_MAX_BYTES);
 
-        let res = tokio::time::timeout(SLASH_COMMANDS_DISCOVERY_TIMEOUT, discovery).await;
+        let timeout = self
+            .discovery_timeout_secs
+            .map(|secs| Duration::from_secs(secs.into()))
+            .unwrap_or(SLASH_COMMANDS_DISCOVERY_TIMEOUT);
+        let res = discover_within("Claude Code", timeout, discovery).await;
         let _ = child.kill().await;
 
-        match res {
-            Ok(Ok(())) => Ok(discovered.unwrap_or_else(|| (vec![], vec![]))),
-            Ok(Err(e)) => Err(e),
-            Err(_) => Err(ExecutorError::Io(std::io::Error::other(
-                "Timed out discovering Claude Code slash commands",
-            ))),
-        }
+        Ok(res?.unwrap_or_else(|| (vec![], vec![])))
     }
 
     pub async fn discover_available_slash_commands(
         &self,
         current_dir: &Path,
     ) -> Result<Vec<SlashCommandDescription>, ExecutorError> {
-        let key = SlashCommandCacheKey::new(current_dir, &BaseCodingAgent::ClaudeCode);
+        let scan_scope = self.command_scan_scope.unwrap_or_default();
+        let key = SlashCommandCacheKey::new(current_dir, &BaseCodingAgent::ClaudeCode)
+            .with_variant(format!("{scan_scope:?}"));
         if let Some(cached) = SlashCommandCache::instance().get(&key) {
             return Ok(cached.as_ref().clone());
         }
 
-        // Run claude-code to discover commands and plugins
-        let (names, plugins) = self
-            .discover_available_command_and_plugins(current_dir)
-            .await?;
+        // Run claude-code to discover commands and plugins, unless nothing it
+        // reads from has changed since the last run
+        let stamp = claude_dir_stamp(current_dir, dirs::home_dir().as_deref());
+        let plugin_cache = PluginDiscoveryCache::instance();
+        let (names, plugins) = match plugin_cache.get(current_dir, &stamp) {
+            Some(discovered) => discovered,
+            None => {
+                let (names, plugins) = self
+                    .discover_available_command_and_plugins(current_dir)
+                    .await?;
+                plugin_cache.put(current_dir, stamp, names.clone(), plugins.clone());
+                (names, plugins)
+            }
+        };
 
-        // Run file walk to discover command descriptions, including from plugins
+        // Run file walk to discover command descriptions and origins, including from plugins
         let current_dir_owned = current_dir.to_owned();
-        let descriptions = tokio::task::spawn_blocking(move || {
-            Self::discover_custom_command_descriptions(&current_dir_owned, &plugins)
+        let commands = tokio::task::spawn_blocking(move || {
+            Self::scoped_slash_commands(
+                names,
+                &current_dir_owned,
+                dirs::home_dir().as_deref(),
+                &plugins,
+                scan_scope,
+            )
         })
         .await
         .map_err(|e| ExecutorError::Io(std::io::Error::other(e)))?;
 
-        let builtin: HashSet<String> = Self::hardcoded_slash_commands()
-            .iter()
-            .map(|c| c.name.clone())
-            .collect();
+        SlashCommandCache::instance().put(key, commands.clone());
 
-        let mut seen = HashSet::new();
-        let names = names
-            .into_iter()
-            .filter(|name| !name.is_empty() && !builtin.contains(name) && seen.insert(name.clone()))
-            .collect::<Vec<_>>();
+        Ok(commands)
+    }
+}
 
-        let commands: Vec<SlashCommandDescription> = names
-            .into_iter()
-            .map(|name| SlashCommandDescription {
-                name: name.to_string(),
-                description: descriptions.get(&name).cloned(),
-            })
-            .collect();
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        SlashCommandCache::instance().put(key, commands.clone());
+    fn write(path: PathBuf, content: &str) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
 
-        Ok(commands)
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn discovery_gives_up_after_the_configured_timeout() {
+        // Ignores the discovery arguments and never prints an init event
+        let executor: ClaudeCode = serde_json::from_value(serde_json::json!({
+            "base_command_override": "sh -c 'sleep 30'",
+            "discovery_timeout_secs": 1
+        }))
+        .unwrap();
+
+        let started = std::time::Instant::now();
+        let err = executor
+            .discover_available_command_and_plugins(&std::env::temp_dir())
+            .await
+            .unwrap_err();
+
+        assert!(
+            err.to_string()
+                .contains("Timed out discovering Claude Code"),
+            "{err}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[test]
+    fn custom_commands_are_tagged_with_their_origin() {
+        let root = std::env::temp_dir().join(format!("vk-claude-scope-{}", uuid::Uuid::new_v4()));
+        let project = root.join("project");
+        let home = root.join("home");
+        let plugin = root.join("plugin");
+
+        write(
+            project.join(".claude/commands/build.md"),
+            "---\ndescription: Build it\n---\n",
+        );
+        write(home.join(".claude/commands/deploy.md"), "no frontmatter");
+        write(
+            home.join(".claude/commands/build.md"),
+            "---\ndescription: Global build\n---\n",
+        );
+        write(
+            plugin.join("skills/lint/SKILL.md"),
+            "---\ndescription: Lint\n---\n",
+        );
+
+        let plugins = vec![ClaudePlugin {
+            name: "tools".to_string(),
+            path: plugin,
+        }];
+        let commands = ClaudeCode::discover_custom_commands(
+            &project,
+            Some(&home),
+            &plugins,
+            CommandScanScope::All,
+        );
+        let project_only = ClaudeCode::discover_custom_commands(
+            &project,
+            Some(&home),
+            &plugins,
+            CommandScanScope::ProjectOnly,
+        );
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(project_only.len(), 1);
+        assert!(project_only.contains_key("build"));
+
+        let build = &commands["build"];
+        assert_eq!(build.scope, SlashCommandScope::Project);
+        assert_eq!(build.description.as_deref(), Some("Build it"));
+
+        let deploy = &commands["deploy"];
+        assert_eq!(deploy.scope, SlashCommandScope::Global);
+        assert_eq!(deploy.description, None);
+
+        let lint = &commands["tools:lint"];
+        assert_eq!(
+            lint.scope,
+            SlashCommandScope::Plugin {
+                name: "tools".to_string()
+            }
+        );
+        assert_eq!(lint.description.as_deref(), Some("Lint"));
+    }
+
+    #[test]
+    fn project_only_drops_reported_global_and_plugin_commands() {
+        let root = std::env::temp_dir().join(format!("vk-claude-scope-{}", uuid::Uuid::new_v4()));
+        let project = root.join("project");
+        let home = root.join("home");
+        let plugin = root.join("plugin");
+        write(project.join(".claude/commands/build.md"), "");
+        write(home.join(".claude/commands/build.md"), "");
+        write(home.join(".claude/commands/deploy.md"), "");
+        write(home.join(".claude/skills/notes/SKILL.md"), "");
+        write(plugin.join("commands/lint.md"), "");
+        let plugins = vec![ClaudePlugin {
+            name: "tools".to_string(),
+            path: plugin,
+        }];
+        // As Claude Code reports them, including its own `todos`
+        let reported = || {
+            ["build", "deploy", "notes", "tools:lint", "todos"]
+                .map(String::from)
+                .to_vec()
+        };
+
+        let scoped = |scope| {
+            ClaudeCode::scoped_slash_commands(reported(), &project, Some(&home), &plugins, scope)
+                .into_iter()
+                .map(|c| c.name)
+                .collect::<Vec<_>>()
+        };
+        let project_only = scoped(CommandScanScope::ProjectOnly);
+        let project_and_global = scoped(CommandScanScope::ProjectAndGlobal);
+        let all = scoped(CommandScanScope::All);
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(project_only, ["build", "todos"]);
+        assert_eq!(project_and_global, ["build", "deploy", "notes", "todos"]);
+        assert_eq!(all, reported());
+    }
+
+    #[test]
+    fn plugin_list_is_rediscovered_only_when_claude_dir_changes() {
+        let project =
+            std::env::temp_dir().join(format!("vk-claude-plugins-{}", uuid::Uuid::new_v4()));
+        let claude_dir = project.join(".claude");
+        std::fs::create_dir_all(&claude_dir).unwrap();
+        let set_mtime = |secs: u64| {
+            std::fs::File::open(&claude_dir)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+                .unwrap();
+        };
+
+        let cache = PluginDiscoveryCache::new();
+        set_mtime(1_000);
+        let plugins = vec![ClaudePlugin {
+            name: "tools".to_string(),
+            path: project.join("plugin"),
+        }];
+        cache.put(
+            &project,
+            claude_dir_stamp(&project, None),
+            vec!["tools:lint".to_string()],
+            plugins,
+        );
+
+        let (names, plugins) = cache
+            .get(&project, &claude_dir_stamp(&project, None))
+            .expect("unchanged .claude dir reuses the discovery");
+        assert_eq!(names, ["tools:lint"]);
+        assert_eq!(plugins[0].name, "tools");
+
+        set_mtime(2_000);
+        assert!(
+            cache
+                .get(&project, &claude_dir_stamp(&project, None))
+                .is_none()
+        );
+
+        let _ = std::fs::remove_dir_all(&project);
+    }
+
+    #[test]
+    fn editing_plugins_or_settings_changes_the_stamp() {
+        let home = std::env::temp_dir().join(format!("vk-claude-home-{}", uuid::Uuid::new_v4()));
+        let project = home.join("project");
+        std::fs::create_dir_all(project.join(".claude")).unwrap();
+        for file in [
+            home.join(".claude/plugins/installed_plugins.json"),
+            home.join(".claude/settings.json"),
+            project.join(".claude/settings.local.json"),
+        ] {
+            write(file.clone(), "{}");
+            let before = claude_dir_stamp(&project, Some(&home));
+            // Rewriting a file in place leaves its directory's mtime alone
+            std::fs::File::options()
+                .write(true)
+                .open(&file)
+                .unwrap()
+                .set_modified(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000))
+                .unwrap();
+            assert_ne!(
+                claude_dir_stamp(&project, Some(&home)),
+                before,
+                "{}",
+                file.display()
+            );
+        }
+
+        let _ = std::fs::remove_dir_all(&home);
+    }
+
+    #[tokio::test]
+    async fn discovery_gives_up_on_output_without_an_init_event() {
+        // "noise 0\n" through "noise 19\n" take 170 bytes
+        let output: String = (0..50).map(|i| format!("noise {i}\n")).collect();
+        let err = read_init_event(output.as_bytes(), 170).await.unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("first 170 bytes"));
+        assert!(message.ends_with("noise 19"));
+        assert!(!message.contains("noise 7\n"));
+
+        // A line that never ends is cut off at the limit rather than buffered whole
+        let output = "x".repeat(10_000);
+        let err = read_init_event(output.as_bytes(), 1024).await.unwrap_err();
+        assert!(err.to_string().ends_with(&"x".repeat(1024)));
+        assert!(!err.to_string().contains(&"x".repeat(1025)));
+
+        let init = r#"{"type":"system","subtype":"init","slash_commands":["build"],"plugins":[]}"#;
+        let output = format!("noise\n{init}\n");
+        let (names, plugins) = read_init_event(output.as_bytes(), 1024)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(names, ["build"]);
+        assert!(plugins.is_empty());
     }
 }
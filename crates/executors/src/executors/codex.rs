@@ -165,6 +165,10 @@ pub struct Codex {
 
 #[async_trait]
 impl StandardCodingAgentExecutor for Codex {
+    async fn command_preview(&self) -> Result<Option<CommandBuilder>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?))
+    }
+
     fn use_approvals(&mut self, approvals: Arc<dyn ExecutorApprovalService>) {
         self.approvals = Some(approvals);
     }
@@ -179,20 +183,24 @@ impl StandardCodingAgentExecutor for Codex {
                 description: Some(
                     "summarize conversation to prevent hitting the context limit".to_string(),
                 ),
+                scope: None,
             },
             SlashCommandDescription {
                 name: "init".to_string(),
                 description: Some(
                     "create an AGENTS.md file with instructions for Codex".to_string(),
                 ),
+                scope: None,
             },
             SlashCommandDescription {
                 name: "status".to_string(),
                 description: Some("show current session configuration and token usage".to_string()),
+                scope: None,
             },
             SlashCommandDescription {
                 name: "mcp".to_string(),
                 description: Some("list configured MCP tools".to_string()),
+                scope: None,
             },
         ];
         Ok(Box::pin(futures::stream::once(async move {
@@ -277,12 +285,12 @@ impl StandardCodingAgentExecutor for Codex {
 }
 
 impl Codex {
-    pub fn base_command() -> &'static str {
-        "npx -y @openai/codex@0.86.0"
+    pub fn base_command() -> CommandBuilder {
+        CommandBuilder::npm_package("@openai/codex@0.86.0", "codex")
     }
 
     fn build_command_builder(&self) -> Result<CommandBuilder, CommandBuildError> {
-        let mut builder = CommandBuilder::new(Self::base_command());
+        let mut builder = Self::base_command();
         builder = builder.extend_params(["app-server"]);
         if self.oss.unwrap_or(false) {
             builder = builder.extend_params(["--oss"]);
@@ -56,13 +56,8 @@ pub struct Copilot {
 
 impl Copilot {
     fn build_command_builder(&self, log_dir: &str) -> Result<CommandBuilder, CommandBuildError> {
-        let mut builder = CommandBuilder::new("npx -y @github/copilot@0.0.375").params([
-            "--no-color",
-            "--log-level",
-            "debug",
-            "--log-dir",
-            log_dir,
-        ]);
+        let mut builder = CommandBuilder::npm_package("@github/copilot@0.0.375", "copilot")
+            .params(["--no-color", "--log-level", "debug", "--log-dir", log_dir]);
 
         if self.allow_all_tools.unwrap_or(false) {
             builder = builder.extend_params(["--allow-all-tools"]);
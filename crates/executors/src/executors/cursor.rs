@@ -71,6 +71,10 @@ impl CursorAgent {
 
 #[async_trait]
 impl StandardCodingAgentExecutor for CursorAgent {
+    async fn command_preview(&self) -> Result<Option<CommandBuilder>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?))
+    }
+
     async fn spawn(
         &self,
         current_dir: &Path,
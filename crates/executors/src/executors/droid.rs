@@ -140,6 +140,10 @@ async fn spawn_droid(
 
 #[async_trait]
 impl StandardCodingAgentExecutor for Droid {
+    async fn command_preview(&self) -> Result<Option<CommandBuilder>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?))
+    }
+
     async fn spawn(
         &self,
         current_dir: &Path,
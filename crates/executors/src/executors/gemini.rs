@@ -1,4 +1,4 @@
-use std::{path::Path, sync::Arc};
+use std::{path::Path, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use derivative::Derivative;
@@ -26,6 +26,10 @@ pub struct Gemini {
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub yolo: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acp_protocol_version: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acp_handshake_timeout_secs: Option<u64>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
     #[serde(skip)]
@@ -36,7 +40,7 @@ pub struct Gemini {
 
 impl Gemini {
     fn build_command_builder(&self) -> Result<CommandBuilder, CommandBuildError> {
-        let mut builder = CommandBuilder::new("npx -y @google/gemini-cli@0.23.0");
+        let mut builder = CommandBuilder::npm_package("@google/gemini-cli@0.23.0", "gemini");
 
         if let Some(model) = &self.model {
             builder = builder.extend_params(["--model", model.as_str()]);
@@ -51,10 +55,26 @@ impl Gemini {
 
         apply_overrides(builder, &self.cmd)
     }
+
+    fn harness(&self) -> AcpAgentHarness {
+        let harness = AcpAgentHarness::new();
+        let harness = match self.acp_protocol_version {
+            Some(version) => harness.with_protocol_version(version),
+            None => harness,
+        };
+        match self.acp_handshake_timeout_secs {
+            Some(secs) => harness.with_handshake_timeout(Duration::from_secs(secs)),
+            None => harness,
+        }
+    }
 }
 
 #[async_trait]
 impl StandardCodingAgentExecutor for Gemini {
+    async fn command_preview(&self) -> Result<Option<CommandBuilder>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?))
+    }
+
     fn use_approvals(&mut self, approvals: Arc<dyn ExecutorApprovalService>) {
         self.approvals = Some(approvals);
     }
@@ -65,7 +85,7 @@ impl StandardCodingAgentExecutor for Gemini {
         prompt: &str,
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let harness = AcpAgentHarness::new();
+        let harness = self.harness();
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
         let gemini_command = self.build_command_builder()?.build_initial()?;
         let approvals = if self.yolo.unwrap_or(false) {
@@ -92,7 +112,7 @@ impl StandardCodingAgentExecutor for Gemini {
         session_id: &str,
         env: &ExecutionEnv,
     ) -> Result<SpawnedChild, ExecutorError> {
-        let harness = AcpAgentHarness::new();
+        let harness = self.harness();
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
         let gemini_command = self.build_command_builder()?.build_follow_up(&[])?;
         let approvals = if self.yolo.unwrap_or(false) {
@@ -1,4 +1,4 @@
-use std::{path::Path, sync::Arc};
+use std::{path::Path, str::FromStr, sync::Arc};
 
 use async_trait::async_trait;
 use command_group::AsyncGroupChild;
@@ -8,7 +8,8 @@ use futures_io::Error as FuturesIoError;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use sqlx::Type;
-use strum_macros::{Display, EnumDiscriminants, EnumString, VariantNames};
+use strum::IntoEnumIterator;
+use strum_macros::{Display, EnumDiscriminants, EnumIter, EnumString, VariantNames};
 use thiserror::Error;
 use ts_rs::TS;
 use workspace_utils::msg_store::MsgStore;
@@ -18,7 +19,7 @@ use crate::executors::qa_mock::QaMockExecutor;
 use crate::{
     actions::{ExecutorAction, review::RepoReviewContext},
     approvals::ExecutorApprovalService,
-    command::CommandBuildError,
+    command::{CommandBuildError, CommandBuilder},
     env::ExecutionEnv,
     executors::{
         amp::Amp, claude::ClaudeCode, codex::Codex, copilot::Copilot, cursor::CursorAgent,
@@ -36,6 +37,7 @@ pub mod copilot;
 pub mod cursor;
 pub mod droid;
 pub mod gemini;
+pub mod node;
 pub mod opencode;
 #[cfg(feature = "qa-mode")]
 pub mod qa_mock;
@@ -48,6 +50,21 @@ pub struct SlashCommandDescription {
     pub name: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
+    /// Where the command was defined, when the executor can tell.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scope: Option<SlashCommandScope>,
+}
+
+/// Origin of a custom slash command.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
+#[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum SlashCommandScope {
+    /// Defined in the user's home directory.
+    Global,
+    /// Defined in the project being worked on.
+    Project,
+    /// Provided by a plugin.
+    Plugin { name: String },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, TS)]
@@ -85,6 +102,8 @@ pub enum ExecutorError {
     SetupHelperNotSupported,
     #[error("Auth required: {0}")]
     AuthRequired(String),
+    #[error("Failed to build HTTP client: {0}")]
+    ClientBuild(String),
 }
 
 #[enum_dispatch]
@@ -96,7 +115,7 @@ pub enum ExecutorError {
 #[strum_discriminants(
     name(BaseCodingAgent),
     // Only add Hash; Eq/PartialEq are already provided by EnumDiscriminants.
-    derive(EnumString, Hash, strum_macros::Display, Serialize, Deserialize, TS, Type),
+    derive(EnumString, EnumIter, Hash, strum_macros::Display, Serialize, Deserialize, TS, Type),
     strum(serialize_all = "SCREAMING_SNAKE_CASE"),
     ts(use_ts_enum),
     serde(rename_all = "SCREAMING_SNAKE_CASE"),
@@ -190,6 +209,49 @@ impl CodingAgent {
     }
 }
 
+impl BaseCodingAgent {
+    /// Every agent, in declaration order.
+    pub fn all() -> Vec<Self> {
+        Self::iter().collect()
+    }
+
+    /// Human-readable name, matching the labels the frontend shows.
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::ClaudeCode => "Claude Code",
+            Self::Amp => "AMP",
+            Self::Gemini => "Gemini",
+            Self::Codex => "Codex",
+            Self::Opencode => "OpenCode",
+            Self::CursorAgent => "Cursor",
+            Self::QwenCode => "Qwen",
+            Self::Copilot => "Copilot",
+            Self::Droid => "Droid",
+            #[cfg(feature = "qa-mode")]
+            Self::QaMock => "QA Mock",
+        }
+    }
+
+    /// Build this agent's executor with every option at its default, independent of the
+    /// user's profiles. Useful when only agent-level behaviour (availability, MCP paths) matters.
+    pub fn default_executor(self) -> CodingAgent {
+        serde_json::from_value(serde_json::json!({ self.to_string(): {} }))
+            .expect("every executor deserializes from an empty config")
+    }
+
+    /// Parse an executor name leniently: case-insensitive, with `-` or spaces in place of `_`,
+    /// and accepting a few short aliases (`claude`, `qwen`, `open_code`).
+    pub fn parse_flexible(input: &str) -> Option<Self> {
+        let normalized = input.trim().replace(['-', ' '], "_").to_ascii_uppercase();
+        match normalized.as_str() {
+            "CLAUDE" => Some(Self::ClaudeCode),
+            "QWEN" => Some(Self::QwenCode),
+            "OPEN_CODE" => Some(Self::Opencode),
+            _ => Self::from_str(&normalized).ok(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[serde(tag = "type", rename_all = "SCREAMING_SNAKE_CASE")]
 #[ts(export)]
@@ -222,6 +284,12 @@ pub trait StandardCodingAgentExecutor {
         })))
     }
 
+    /// The command `spawn` launches, with profile overrides applied, so it can be previewed.
+    /// `None` when the executor doesn't launch a fixed command line.
+    async fn command_preview(&self) -> Result<Option<CommandBuilder>, ExecutorError> {
+        Ok(None)
+    }
+
     async fn spawn(
         &self,
         current_dir: &Path,
@@ -252,6 +320,11 @@ pub trait StandardCodingAgentExecutor {
 
     fn normalize_logs(&self, _raw_logs_event_store: Arc<MsgStore>, _worktree_path: &Path);
 
+    /// Extra outputs for the normalized logs of a run as it happens. Unlike
+    /// [`Self::normalize_logs`], this is not called when stored logs are
+    /// normalized again for display.
+    fn mirror_live_logs(&self, _msg_store: Arc<MsgStore>, _worktree_path: &Path) {}
+
     // MCP configuration methods
     fn default_mcp_config_path(&self) -> Option<std::path::PathBuf>;
 
@@ -392,4 +465,43 @@ mod tests {
         assert!(result.is_ok(), "CURSOR should deserialize via serde");
         assert_eq!(result.unwrap(), BaseCodingAgent::CursorAgent);
     }
+
+    #[test]
+    fn parse_flexible_normalizes_executor_names() {
+        for input in ["Claude-Code", "claude_code", "CLAUDE CODE", " claude "] {
+            assert_eq!(
+                BaseCodingAgent::parse_flexible(input),
+                Some(BaseCodingAgent::ClaudeCode),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            BaseCodingAgent::parse_flexible("cursor"),
+            Some(BaseCodingAgent::CursorAgent)
+        );
+        assert_eq!(BaseCodingAgent::parse_flexible("not-an-agent"), None);
+        assert_eq!(BaseCodingAgent::parse_flexible(""), None);
+    }
+
+    #[test]
+    fn every_agent_has_a_default_executor() {
+        use strum::VariantNames;
+
+        for name in CodingAgent::VARIANTS {
+            let agent = BaseCodingAgent::from_str(name).unwrap();
+            assert_eq!(BaseCodingAgent::from(&agent.default_executor()), agent);
+        }
+    }
+
+    #[test]
+    fn all_lists_every_agent_with_a_name() {
+        use strum::VariantNames;
+
+        let all = BaseCodingAgent::all();
+        assert_eq!(all.len(), CodingAgent::VARIANTS.len());
+        for name in CodingAgent::VARIANTS {
+            assert!(all.contains(&BaseCodingAgent::from_str(name).unwrap()));
+        }
+        assert!(all.iter().all(|agent| !agent.display_name().is_empty()));
+    }
 }
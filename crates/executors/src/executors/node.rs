@@ -0,0 +1,158 @@
+use std::{io, path::Path, process::Stdio, time::Duration};
+
+use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use tokio::process::Command;
+
+use crate::{
+    command::{CmdOverrides, CommandParts},
+    env::ExecutionEnv,
+    executors::ExecutorError,
+};
+
+/// Retries for a spawn that failed for a transient reason, e.g. `npx` having
+/// just written the binary it's about to exec.
+pub const DEFAULT_SPAWN_RETRIES: u32 = 2;
+
+const SPAWN_RETRY_DELAY: Duration = Duration::from_millis(250);
+
+/// Spawn a Node-based agent CLI in its own process group, killed on drop, with
+/// stdout and stderr piped and npm/node warnings quietened. `customize` runs
+/// before the execution env is applied, for per-executor stdin or extra env.
+pub async fn spawn_node_executor(
+    command_parts: CommandParts,
+    current_dir: &Path,
+    env: &ExecutionEnv,
+    overrides: &CmdOverrides,
+    retries: u32,
+    customize: impl Fn(&mut Command),
+) -> Result<AsyncGroupChild, ExecutorError> {
+    let (program_path, args) = command_parts.into_resolved().await?;
+    let env = env.clone().with_profile(overrides);
+
+    let child = retry_spawn(retries, || {
+        let mut command = Command::new(&program_path);
+        command
+            .kill_on_drop(true)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .current_dir(current_dir)
+            .env("NPM_CONFIG_LOGLEVEL", "error")
+            .env("NODE_NO_WARNINGS", "1")
+            .args(&args);
+        customize(&mut command);
+        env.apply_to_command(&mut command);
+        command.group_spawn()
+    })
+    .await?;
+    Ok(child)
+}
+
+fn is_transient_spawn_error(err: &io::Error) -> bool {
+    matches!(
+        err.kind(),
+        io::ErrorKind::ExecutableFileBusy
+            | io::ErrorKind::ResourceBusy
+            | io::ErrorKind::WouldBlock
+            | io::ErrorKind::Interrupted
+    )
+}
+
+async fn retry_spawn<T>(retries: u32, mut spawn: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+    let mut attempt = 0;
+    loop {
+        match spawn() {
+            Err(err) if attempt < retries && is_transient_spawn_error(&err) => {
+                attempt += 1;
+                tracing::warn!("Transient error spawning executor (attempt {attempt}): {err}");
+                tokio::time::sleep(SPAWN_RETRY_DELAY * attempt).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn transient_spawn_failures_are_retried() {
+        let mut calls = 0;
+        let spawned = retry_spawn(DEFAULT_SPAWN_RETRIES, || {
+            calls += 1;
+            if calls <= 2 {
+                Err(io::Error::from(io::ErrorKind::ExecutableFileBusy))
+            } else {
+                Ok(calls)
+            }
+        })
+        .await;
+        assert_eq!(spawned.unwrap(), 3);
+
+        // Out of retries
+        let mut calls = 0;
+        let spawned: io::Result<()> = retry_spawn(1, || {
+            calls += 1;
+            Err(io::Error::from(io::ErrorKind::ResourceBusy))
+        })
+        .await;
+        assert!(spawned.is_err());
+        assert_eq!(calls, 2);
+
+        // A missing binary won't appear by waiting
+        let mut calls = 0;
+        let spawned: io::Result<()> = retry_spawn(DEFAULT_SPAWN_RETRIES, || {
+            calls += 1;
+            Err(io::Error::from(io::ErrorKind::NotFound))
+        })
+        .await;
+        assert_eq!(spawned.unwrap_err().kind(), io::ErrorKind::NotFound);
+        assert_eq!(calls, 1);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn npm_options_reach_the_spawned_command() {
+        use tokio::io::AsyncReadExt;
+
+        use crate::env::RepoContext;
+
+        let overrides = CmdOverrides {
+            npm_registry: Some("https://npm.internal.example/".to_string()),
+            npm_cache_dir: Some("/tmp/vk-npm-cache".to_string()),
+            ..Default::default()
+        };
+        let command_parts = CommandParts::new(
+            "sh".to_string(),
+            vec![
+                "-c".to_string(),
+                r#"printf '%s|%s' "$NPM_CONFIG_REGISTRY" "$npm_config_cache""#.to_string(),
+            ],
+        );
+        let env = ExecutionEnv::new(RepoContext::default(), false);
+
+        let mut child = spawn_node_executor(
+            command_parts,
+            &std::env::temp_dir(),
+            &env,
+            &overrides,
+            0,
+            |_| {},
+        )
+        .await
+        .unwrap();
+        let mut output = String::new();
+        child
+            .inner()
+            .stdout
+            .take()
+            .unwrap()
+            .read_to_string(&mut output)
+            .await
+            .unwrap();
+        child.wait().await.unwrap();
+
+        assert_eq!(output, "https://npm.internal.example/|/tmp/vk-npm-cache");
+    }
+}
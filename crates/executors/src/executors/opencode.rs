@@ -1,25 +1,38 @@
-use std::{path::Path, sync::Arc, time::Duration};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, LazyLock, Mutex},
+    time::Duration,
+};
 
 use async_trait::async_trait;
-use command_group::{AsyncCommandGroup, AsyncGroupChild};
+use command_group::AsyncGroupChild;
 use derivative::Derivative;
 use futures::StreamExt;
+use regex::RegexSet;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
-use tokio::{io::AsyncBufReadExt, process::Command};
+use thiserror::Error;
+use tokio::io::AsyncRead;
 use ts_rs::TS;
-use workspace_utils::msg_store::MsgStore;
+use workspace_utils::{msg_store::MsgStore, stream_lines::lossy_lines};
 
 use crate::{
     approvals::ExecutorApprovalService,
     command::{CmdOverrides, CommandBuildError, CommandBuilder, apply_overrides},
     env::ExecutionEnv,
     executors::{
-        AppendPrompt, AvailabilityInfo, ExecutorError, ExecutorExitResult, SpawnedChild,
-        StandardCodingAgentExecutor, opencode::types::OpencodeExecutorEvent,
+        AppendPrompt, AvailabilityInfo, ExecutorError, ExecutorExitResult, SlashCommandDescription,
+        SpawnedChild, StandardCodingAgentExecutor,
+        node::{DEFAULT_SPAWN_RETRIES, spawn_node_executor},
+        opencode::types::OpencodeExecutorEvent,
+        utils::filter_slash_commands,
+    },
+    logs::{
+        jsonl::{resolve_jsonl_path, spawn_jsonl_writer},
+        utils::patch,
     },
-    logs::utils::patch,
     stdout_dup::create_stdout_pipe_writer,
 };
 
@@ -41,14 +54,48 @@ pub struct Opencode {
     pub model: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub variant: Option<String>,
+    /// OpenCode agent to run the session with, e.g. `build` or `plan`. OpenCode folded its former modes into agents, so this is sent as the prompt's `agent`; the key `agent` is accepted as an alias
     #[serde(default, skip_serializing_if = "Option::is_none", alias = "agent")]
     pub mode: Option<String>,
     /// Auto-approve agent actions
     #[serde(default = "default_to_true")]
     pub auto_approve: bool,
-    /// Enable auto-compaction when the context length approaches the model's context window limit
-    #[serde(default = "default_to_true")]
-    pub auto_compact: bool,
+    /// Enable auto-compaction when the context length approaches the model's context window limit (null leaves OpenCode's own compaction config untouched)
+    #[serde(default = "default_auto_compact")]
+    pub auto_compact: Option<bool>,
+    /// Default timeout in seconds for shell commands run by the agent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command_timeout_secs: Option<u32>,
+    /// Stop the session after the agent has taken this many turns (null means no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_turns: Option<u32>,
+    /// Title for new OpenCode sessions, shown in OpenCode's own session list. Follow-ups keep the existing title
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_title: Option<String>,
+    /// Additional MCP servers merged into OpenCode's `mcp` config for this profile
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_mcp_servers: Option<Value>,
+    /// Append the normalized log entries of each run to this file as JSON lines (relative paths resolve against the app's data directory, never the worktree)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jsonl_log_path: Option<String>,
+    /// Provider ids left out of the `/models` listing, e.g. providers that always fail to enumerate their models
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skip_providers: Option<Vec<String>>,
+    /// Seconds to wait for slash command discovery, including starting a server for it, before giving up (null keeps only the server's own startup timeouts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub discovery_timeout_secs: Option<u32>,
+    /// Respawn the server and retry slash command discovery once when a server that started fails mid-discovery (default: true). Disable to avoid doubling discovery time on persistent failures
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_discovery_with_fresh_server: Option<bool>,
+    /// Slash commands offered for this profile (default: all discovered commands)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub allow_slash_commands: Option<Vec<String>>,
+    /// Slash commands hidden for this profile, even if also allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deny_slash_commands: Option<Vec<String>>,
+    /// Regular expressions for server startup output lines to leave out of the log, e.g. npm noise. Ignored lines still show in startup error reports
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub startup_log_ignore: Option<Vec<String>>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
     #[serde(skip)]
@@ -67,9 +114,80 @@ struct OpencodeServer {
 
 type ServerPassword = String;
 
+impl OpencodeServer {
+    fn connection(&self) -> ServerConnection {
+        ServerConnection {
+            base_url: self.base_url.clone(),
+            password: self.server_password.clone(),
+        }
+    }
+}
+
+/// Address and password of a running OpenCode server.
+#[derive(Debug, Clone)]
+pub struct ServerConnection {
+    pub base_url: String,
+    pub password: ServerPassword,
+}
+
+/// Servers of running sessions by working directory, so slash command discovery
+/// can ask one of them instead of spawning its own.
+static SESSION_SERVERS: LazyLock<Mutex<HashMap<PathBuf, ServerConnection>>> =
+    LazyLock::new(Default::default);
+
+/// The server of a session running in `directory`, if any.
+fn session_server(directory: &Path) -> Option<ServerConnection> {
+    SESSION_SERVERS.lock().unwrap().get(directory).cloned()
+}
+
+/// Keeps a session's server listed in [`SESSION_SERVERS`] until dropped.
+struct SessionServerRegistration {
+    directory: PathBuf,
+    base_url: String,
+}
+
+impl SessionServerRegistration {
+    fn register(directory: PathBuf, connection: ServerConnection) -> Self {
+        let base_url = connection.base_url.clone();
+        SESSION_SERVERS
+            .lock()
+            .unwrap()
+            .insert(directory.clone(), connection);
+        Self {
+            directory,
+            base_url,
+        }
+    }
+}
+
+impl Drop for SessionServerRegistration {
+    fn drop(&mut self) {
+        let mut servers = SESSION_SERVERS.lock().unwrap();
+        // A newer session in the same directory may have replaced this entry
+        if servers
+            .get(&self.directory)
+            .is_some_and(|server| server.base_url == self.base_url)
+        {
+            servers.remove(&self.directory);
+        }
+    }
+}
+
 impl Opencode {
+    /// Apply the profile's `allow_slash_commands` / `deny_slash_commands`.
+    fn visible_slash_commands(
+        &self,
+        commands: Vec<SlashCommandDescription>,
+    ) -> Vec<SlashCommandDescription> {
+        filter_slash_commands(
+            commands,
+            self.allow_slash_commands.as_deref(),
+            self.deny_slash_commands.as_deref(),
+        )
+    }
+
     fn build_command_builder(&self) -> Result<CommandBuilder, CommandBuildError> {
-        let builder = CommandBuilder::new("npx -y opencode-ai@1.1.25")
+        let builder = CommandBuilder::npm_package("opencode-ai@1.1.25", "opencode")
             // Pass hostname/port as separate args so OpenCode treats them as explicitly set
             // (it checks `process.argv.includes(\"--port\")` / `\"--hostname\"`).
             .extend_params(["serve", "--hostname", "127.0.0.1", "--port", "0"]);
@@ -88,28 +206,21 @@ impl Opencode {
         env: &ExecutionEnv,
     ) -> Result<(AsyncGroupChild, ServerPassword), ExecutorError> {
         let command_parts = self.build_command_builder()?.build_initial()?;
-        let (program_path, args) = command_parts.into_resolved().await?;
-
         let server_password = generate_server_password();
 
-        let mut command = Command::new(program_path);
-        command
-            .kill_on_drop(true)
-            .stdin(std::process::Stdio::null())
-            .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped())
-            .current_dir(current_dir)
-            .env("NPM_CONFIG_LOGLEVEL", "error")
-            .env("NODE_NO_WARNINGS", "1")
-            .env("NO_COLOR", "1")
-            .env("OPENCODE_SERVER_PASSWORD", &server_password)
-            .args(&args);
-
-        env.clone()
-            .with_profile(&self.cmd)
-            .apply_to_command(&mut command);
-
-        let child = command.group_spawn()?;
+        let child = spawn_node_executor(
+            command_parts,
+            current_dir,
+            env,
+            &self.cmd,
+            DEFAULT_SPAWN_RETRIES,
+            |command| {
+                command
+                    .env("NO_COLOR", "1")
+                    .env("OPENCODE_SERVER_PASSWORD", &server_password);
+            },
+        )
+        .await?;
 
         Ok((child, server_password))
     }
@@ -125,7 +236,7 @@ impl Opencode {
             ExecutorError::Io(std::io::Error::other("OpenCode server missing stdout"))
         })?;
 
-        let base_url = wait_for_server_url(server_stdout, None).await?;
+        let base_url = wait_for_server_url(server_stdout, None, None).await?;
 
         Ok(OpencodeServer {
             child,
@@ -134,6 +245,33 @@ impl Opencode {
         })
     }
 
+    /// The compiled `startup_log_ignore` patterns, if any are set.
+    fn startup_log_ignore_set(&self) -> Result<Option<RegexSet>, ExecutorError> {
+        self.startup_log_ignore
+            .as_ref()
+            .filter(|patterns| !patterns.is_empty())
+            .map(RegexSet::new)
+            .transpose()
+            .map_err(|e| {
+                ExecutorError::Io(std::io::Error::other(format!(
+                    "Invalid startup_log_ignore pattern: {e}"
+                )))
+            })
+    }
+
+    /// The title to create a session with; `None` when resuming, so a
+    /// follow-up never overwrites the title the session already has.
+    fn session_title_for(&self, resume_session: Option<&str>) -> Option<String> {
+        if resume_session.is_some() {
+            return None;
+        }
+        self.session_title
+            .as_deref()
+            .map(str::trim)
+            .filter(|title| !title.is_empty())
+            .map(str::to_string)
+    }
+
     async fn spawn_inner(
         &self,
         current_dir: &Path,
@@ -148,6 +286,7 @@ impl Opencode {
             self.append_prompt.combine_prompt(prompt)
         };
 
+        let startup_log_ignore = self.startup_log_ignore_set()?;
         let (mut child, server_password) = self.spawn_server_process(current_dir, env).await?;
         let server_stdout = child.inner().stdout.take().ok_or_else(|| {
             ExecutorError::Io(std::io::Error::other("OpenCode server missing stdout"))
@@ -161,6 +300,7 @@ impl Opencode {
 
         // Prepare config values that will be moved into the spawned task
         let directory = current_dir.to_string_lossy().to_string();
+        let session_dir = current_dir.to_path_buf();
         let approvals = if self.auto_approve {
             None
         } else {
@@ -174,10 +314,18 @@ impl Opencode {
         let models_cache_key = self.compute_models_cache_key();
         let commit_reminder = env.commit_reminder;
         let repo_context = env.repo_context.clone();
+        let max_turns = self.max_turns;
+        let session_title = self.session_title_for(resume_session);
+        let skip_providers = self.skip_providers.clone().unwrap_or_default();
 
         tokio::spawn(async move {
             // Wait for server to print listening URL
-            let base_url = match wait_for_server_url(server_stdout, Some(log_writer.clone())).await
+            let base_url = match wait_for_server_url(
+                server_stdout,
+                Some(log_writer.clone()),
+                startup_log_ignore.as_ref(),
+            )
+            .await
             {
                 Ok(url) => url,
                 Err(err) => {
@@ -188,6 +336,13 @@ impl Opencode {
                     return;
                 }
             };
+            let _registration = SessionServerRegistration::register(
+                session_dir,
+                ServerConnection {
+                    base_url: base_url.clone(),
+                    password: server_password.clone(),
+                },
+            );
 
             let config = RunConfig {
                 base_url,
@@ -203,6 +358,9 @@ impl Opencode {
                 models_cache_key,
                 commit_reminder,
                 repo_context,
+                max_turns,
+                session_title,
+                skip_providers,
             };
 
             let result = match slash_command {
@@ -243,35 +401,91 @@ fn format_tail(captured: Vec<String>) -> String {
         .join("\n")
 }
 
+/// Why the OpenCode server failed to start, recognised from its captured output.
+#[derive(Debug, Error)]
+pub enum OpencodeStartupError {
+    #[error(
+        "OpenCode server could not bind its port because the address is already in use. Stop the other process and retry."
+    )]
+    PortInUse,
+    #[error("Node.js was not found on PATH. Install Node.js (with npx) to run OpenCode.")]
+    NodeMissing,
+    #[error(
+        "npm failed to authenticate while fetching OpenCode. Check your npm registry credentials (e.g. `npm login` or .npmrc)."
+    )]
+    NpmAuth,
+    #[error("{reason}.\nServer output tail:\n{tail}")]
+    Unknown { reason: String, tail: String },
+}
+
+impl OpencodeStartupError {
+    /// Match the captured server output against known failure signatures, falling back
+    /// to `Unknown` with `reason` and the output tail.
+    fn classify(reason: &str, captured: Vec<String>) -> Self {
+        let output = captured.join("\n").to_ascii_lowercase();
+        let matches = |signatures: &[&str]| signatures.iter().any(|sig| output.contains(sig));
+
+        if matches(&["eaddrinuse", "address already in use"]) {
+            Self::PortInUse
+        } else if matches(&[
+            "node: not found",
+            "node: command not found",
+            "node: no such file or directory",
+            "'node': no such file or directory",
+            "npx: not found",
+            "npx: command not found",
+        ]) {
+            Self::NodeMissing
+        } else if matches(&["e401", "eneedauth", "unable to authenticate"]) {
+            Self::NpmAuth
+        } else {
+            Self::Unknown {
+                reason: reason.to_string(),
+                tail: format_tail(captured),
+            }
+        }
+    }
+}
+
+/// Read the server's stdout until it prints its listening URL, forwarding each
+/// line not matched by `ignore` to `log_writer` as a startup log. Every line
+/// counts toward the output captured for startup errors.
 async fn wait_for_server_url(
-    stdout: tokio::process::ChildStdout,
+    stdout: impl AsyncRead + Unpin + Send + 'static,
     log_writer: Option<LogWriter>,
+    ignore: Option<&RegexSet>,
 ) -> Result<String, ExecutorError> {
-    let mut lines = tokio::io::BufReader::new(stdout).lines();
+    let mut lines = lossy_lines(tokio::io::BufReader::new(stdout));
     let deadline = tokio::time::Instant::now() + Duration::from_secs(180);
     let mut captured: Vec<String> = Vec::new();
 
     loop {
         if tokio::time::Instant::now() > deadline {
-            return Err(ExecutorError::Io(std::io::Error::other(format!(
-                "Timed out waiting for OpenCode server to print listening URL.\nServer output tail:\n{}",
-                format_tail(captured)
-            ))));
+            return Err(ExecutorError::Io(std::io::Error::other(
+                OpencodeStartupError::classify(
+                    "Timed out waiting for OpenCode server to print listening URL",
+                    captured,
+                ),
+            )));
         }
 
         let line = match tokio::time::timeout_at(deadline, lines.next_line()).await {
             Ok(Ok(Some(line))) => line,
             Ok(Ok(None)) => {
-                return Err(ExecutorError::Io(std::io::Error::other(format!(
-                    "OpenCode server exited before printing listening URL.\nServer output tail:\n{}",
-                    format_tail(captured)
-                ))));
+                return Err(ExecutorError::Io(std::io::Error::other(
+                    OpencodeStartupError::classify(
+                        "OpenCode server exited before printing listening URL",
+                        captured,
+                    ),
+                )));
             }
             Ok(Err(err)) => return Err(ExecutorError::Io(err)),
             Err(_) => continue,
         };
 
-        if let Some(log_writer) = &log_writer {
+        if let Some(log_writer) = &log_writer
+            && !ignore.is_some_and(|ignore| ignore.is_match(&line))
+        {
             log_writer
                 .log_event(&OpencodeExecutorEvent::StartupLog {
                     message: line.clone(),
@@ -283,18 +497,42 @@ async fn wait_for_server_url(
         }
 
         if let Some(url) = line.trim().strip_prefix("opencode server listening on ") {
+            let base_url = parse_server_url(url)?;
             // Keep draining stdout to avoid backpressure on the server, but don't block startup.
-            tokio::spawn(async move {
-                let mut lines = tokio::io::BufReader::new(lines.into_inner()).lines();
-                while let Ok(Some(_)) = lines.next_line().await {}
-            });
-            return Ok(url.trim().to_string());
+            tokio::spawn(async move { while let Ok(Some(_)) = lines.next_line().await {} });
+            return Ok(base_url);
         }
     }
 }
 
+/// Validate the URL OpenCode says it's listening on and reduce it to a base URL
+/// that API paths can be appended to, e.g. `http://[::1]:4096`.
+fn parse_server_url(raw: &str) -> Result<String, ExecutorError> {
+    let invalid = |reason: &str| {
+        ExecutorError::Io(std::io::Error::other(format!(
+            "OpenCode printed an invalid server URL `{}`: {reason}",
+            raw.trim()
+        )))
+    };
+    let mut url = reqwest::Url::parse(raw.trim()).map_err(|err| invalid(&err.to_string()))?;
+    if !matches!(url.scheme(), "http" | "https") {
+        return Err(invalid("expected an http(s) URL"));
+    }
+    if url.host().is_none() {
+        return Err(invalid("missing host"));
+    }
+    url.set_path("");
+    url.set_query(None);
+    url.set_fragment(None);
+    Ok(url.as_str().trim_end_matches('/').to_string())
+}
+
 #[async_trait]
 impl StandardCodingAgentExecutor for Opencode {
+    async fn command_preview(&self) -> Result<Option<CommandBuilder>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?))
+    }
+
     fn use_approvals(&mut self, approvals: Arc<dyn ExecutorApprovalService>) {
         self.approvals = Some(approvals);
     }
@@ -307,14 +545,21 @@ impl StandardCodingAgentExecutor for Opencode {
         let this = self.clone();
         let current_dir = current_dir.to_path_buf();
 
-        let initial = patch::slash_commands(defaults.clone(), true, None);
+        let initial =
+            patch::slash_commands(this.visible_slash_commands(defaults.clone()), true, None);
 
         let discovery_stream = futures::stream::once(async move {
             match this.discover_slash_commands(&current_dir).await {
-                Ok(commands) => patch::slash_commands(commands, false, None),
+                Ok(commands) => {
+                    patch::slash_commands(this.visible_slash_commands(commands), false, None)
+                }
                 Err(e) => {
                     tracing::warn!("Failed to discover OpenCode slash commands: {}", e);
-                    patch::slash_commands(defaults, false, Some(e.to_string()))
+                    patch::slash_commands(
+                        this.visible_slash_commands(defaults),
+                        false,
+                        Some(e.to_string()),
+                    )
                 }
             }
         });
@@ -332,6 +577,8 @@ impl StandardCodingAgentExecutor for Opencode {
     ) -> Result<SpawnedChild, ExecutorError> {
         let env = setup_permissions_env(self.auto_approve, env);
         let env = setup_compaction_env(self.auto_compact, &env);
+        let env = setup_mcp_env(self.extra_mcp_servers.as_ref(), &env);
+        let env = setup_command_timeout_env(self.command_timeout_secs, &env);
         self.spawn_inner(current_dir, prompt, None, &env).await
     }
 
@@ -344,6 +591,8 @@ impl StandardCodingAgentExecutor for Opencode {
     ) -> Result<SpawnedChild, ExecutorError> {
         let env = setup_permissions_env(self.auto_approve, env);
         let env = setup_compaction_env(self.auto_compact, &env);
+        let env = setup_mcp_env(self.extra_mcp_servers.as_ref(), &env);
+        let env = setup_command_timeout_env(self.command_timeout_secs, &env);
         self.spawn_inner(current_dir, prompt, Some(session_id), &env)
             .await
     }
@@ -352,6 +601,12 @@ impl StandardCodingAgentExecutor for Opencode {
         normalize_logs::normalize_logs(msg_store, worktree_path);
     }
 
+    fn mirror_live_logs(&self, msg_store: Arc<MsgStore>, _worktree_path: &Path) {
+        if let Some(path) = &self.jsonl_log_path {
+            spawn_jsonl_writer(msg_store, resolve_jsonl_path(path));
+        }
+    }
+
     fn default_mcp_config_path(&self) -> Option<std::path::PathBuf> {
         // Try multiple config file names (.json and .jsonc) in XDG/platform config dirs
         #[cfg(unix)]
@@ -420,6 +675,10 @@ fn default_to_true() -> bool {
     true
 }
 
+fn default_auto_compact() -> Option<bool> {
+    Some(true)
+}
+
 fn setup_permissions_env(auto_approve: bool, env: &ExecutionEnv) -> ExecutionEnv {
     let mut env = env.clone();
 
@@ -452,18 +711,24 @@ fn merge_question_deny(existing_json: &str) -> String {
     serde_json::to_string(&permissions).unwrap_or_else(|_| r#"{"question":"deny"}"#.to_string())
 }
 
-fn setup_compaction_env(auto_compact: bool, env: &ExecutionEnv) -> ExecutionEnv {
-    if !auto_compact {
-        return env.clone();
-    }
-
+fn setup_compaction_env(auto_compact: Option<bool>, env: &ExecutionEnv) -> ExecutionEnv {
     let mut env = env.clone();
-    let merged = merge_compaction_config(env.get("OPENCODE_CONFIG_CONTENT").map(String::as_str));
-    env.insert("OPENCODE_CONFIG_CONTENT", merged);
+    if let Some(merged) = merge_compaction_config(
+        env.get("OPENCODE_CONFIG_CONTENT").map(String::as_str),
+        auto_compact,
+    ) {
+        env.insert("OPENCODE_CONFIG_CONTENT", merged);
+    }
     env
 }
 
-fn merge_compaction_config(existing_json: Option<&str>) -> String {
+/// Force `compaction.auto` in the config, or return `None` to leave the
+/// user's own compaction settings untouched.
+fn merge_compaction_config(
+    existing_json: Option<&str>,
+    auto_compact: Option<bool>,
+) -> Option<String> {
+    let auto_compact = auto_compact?;
     let mut config: Map<String, Value> = existing_json
         .and_then(|value| serde_json::from_str(value.trim()).ok())
         .unwrap_or_default();
@@ -472,8 +737,245 @@ fn merge_compaction_config(existing_json: Option<&str>) -> String {
         .remove("compaction")
         .and_then(|value| value.as_object().cloned())
         .unwrap_or_default();
-    compaction.insert("auto".to_string(), Value::Bool(true));
+    compaction.insert("auto".to_string(), Value::Bool(auto_compact));
     config.insert("compaction".to_string(), Value::Object(compaction));
 
-    serde_json::to_string(&config).unwrap_or_else(|_| r#"{"compaction":{"auto":true}}"#.to_string())
+    Some(
+        serde_json::to_string(&config)
+            .unwrap_or_else(|_| format!(r#"{{"compaction":{{"auto":{auto_compact}}}}}"#)),
+    )
+}
+
+fn setup_mcp_env(extra_mcp_servers: Option<&Value>, env: &ExecutionEnv) -> ExecutionEnv {
+    let Some(servers) = extra_mcp_servers.and_then(Value::as_object) else {
+        return env.clone();
+    };
+
+    let mut env = env.clone();
+    let merged = merge_mcp_config(
+        env.get("OPENCODE_CONFIG_CONTENT").map(String::as_str),
+        servers,
+    );
+    env.insert("OPENCODE_CONFIG_CONTENT", merged);
+    env
+}
+
+fn merge_mcp_config(existing_json: Option<&str>, servers: &Map<String, Value>) -> String {
+    let mut config: Map<String, Value> = existing_json
+        .and_then(|value| serde_json::from_str(value.trim()).ok())
+        .unwrap_or_default();
+
+    let mut mcp = config
+        .remove("mcp")
+        .and_then(|value| value.as_object().cloned())
+        .unwrap_or_default();
+    mcp.extend(servers.clone());
+    config.insert("mcp".to_string(), Value::Object(mcp));
+
+    serde_json::to_string(&config).unwrap_or_default()
+}
+
+/// OpenCode reads its default bash tool timeout (in milliseconds) from this variable.
+const BASH_TIMEOUT_ENV: &str = "OPENCODE_EXPERIMENTAL_BASH_DEFAULT_TIMEOUT_MS";
+
+fn setup_command_timeout_env(timeout_secs: Option<u32>, env: &ExecutionEnv) -> ExecutionEnv {
+    let mut env = env.clone();
+    if let Some(secs) = timeout_secs {
+        env.insert(BASH_TIMEOUT_ENV, (u64::from(secs) * 1000).to_string());
+    }
+    env
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::RepoContext;
+
+    #[tokio::test]
+    async fn ignored_startup_lines_are_not_logged_but_kept_for_errors() {
+        use tokio::io::AsyncReadExt;
+
+        let (writer, mut events) = tokio::io::duplex(64 * 1024);
+        let ignore = RegexSet::new([r"^npm (warn|notice)"]).unwrap();
+        let stdout: &'static [u8] = b"npm warn deprecated inflight@1.0.6\nloading config\n";
+
+        let err = wait_for_server_url(stdout, Some(LogWriter::new(writer)), Some(&ignore))
+            .await
+            .unwrap_err();
+        let tail = err.to_string();
+        assert!(
+            tail.contains("npm warn deprecated inflight@1.0.6"),
+            "{tail}"
+        );
+        assert!(tail.contains("loading config"), "{tail}");
+
+        let mut logged = String::new();
+        events.read_to_string(&mut logged).await.unwrap();
+        assert!(!logged.contains("npm warn"), "{logged}");
+        assert!(logged.contains("loading config"), "{logged}");
+    }
+
+    #[test]
+    fn server_urls_are_validated_and_normalized() {
+        assert_eq!(
+            parse_server_url("http://127.0.0.1:4096").unwrap(),
+            "http://127.0.0.1:4096"
+        );
+        assert_eq!(
+            parse_server_url(" http://[::1]:4096/ ").unwrap(),
+            "http://[::1]:4096"
+        );
+        assert_eq!(
+            parse_server_url("http://localhost:4096/api?x=1").unwrap(),
+            "http://localhost:4096"
+        );
+
+        for malformed in [
+            "127.0.0.1:4096",
+            "http://[::1:4096",
+            "ws://127.0.0.1:4096",
+            "",
+        ] {
+            let err = parse_server_url(malformed).unwrap_err();
+            assert!(
+                err.to_string().contains("invalid server URL"),
+                "{malformed}: {err}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn command_preview_includes_serve_args() {
+        let opencode: Opencode = serde_json::from_value(serde_json::json!({})).unwrap();
+        let builder = opencode.command_preview().await.unwrap().unwrap();
+        assert_eq!(
+            builder.params.unwrap(),
+            ["serve", "--hostname", "127.0.0.1", "--port", "0"]
+        );
+    }
+
+    #[test]
+    fn session_title_is_only_set_on_new_sessions() {
+        let opencode: Opencode =
+            serde_json::from_value(serde_json::json!({ "session_title": " Fix login " })).unwrap();
+        assert_eq!(
+            opencode.session_title_for(None).as_deref(),
+            Some("Fix login")
+        );
+        assert_eq!(opencode.session_title_for(Some("ses_123")), None);
+    }
+
+    #[test]
+    fn mode_and_agent_keys_select_the_same_agent() {
+        for key in ["mode", "agent"] {
+            let opencode: Opencode =
+                serde_json::from_value(serde_json::json!({ key: "plan" })).unwrap();
+            assert_eq!(opencode.mode.as_deref(), Some("plan"));
+        }
+    }
+
+    #[test]
+    fn package_runner_replaces_npx() {
+        let opencode = |package_runner: Option<&str>| -> Opencode {
+            serde_json::from_value(serde_json::json!({ "package_runner": package_runner })).unwrap()
+        };
+
+        let default = opencode(None).build_command_builder().unwrap();
+        assert_eq!(default.base, "npx -y opencode-ai@1.1.25");
+
+        let global = opencode(Some("")).build_command_builder().unwrap();
+        assert_eq!(global.base, "opencode");
+
+        let bunx = opencode(Some("bunx")).build_command_builder().unwrap();
+        assert_eq!(bunx.base, "bunx opencode-ai@1.1.25");
+
+        assert!(matches!(
+            opencode(Some("bunx 'unterminated")).build_command_builder(),
+            Err(CommandBuildError::InvalidPackageRunner(_))
+        ));
+    }
+
+    #[test]
+    fn startup_errors_are_classified_from_output() {
+        let classify = |lines: &[&str]| {
+            OpencodeStartupError::classify(
+                "OpenCode server exited before printing listening URL",
+                lines.iter().map(|line| line.to_string()).collect(),
+            )
+        };
+
+        assert!(matches!(
+            classify(&["Error: listen EADDRINUSE: address already in use 127.0.0.1:4096"]),
+            OpencodeStartupError::PortInUse
+        ));
+        assert!(matches!(
+            classify(&["/usr/bin/env: 'node': No such file or directory"]),
+            OpencodeStartupError::NodeMissing
+        ));
+        assert!(matches!(
+            classify(&["npm error code E401", "npm error Unable to authenticate"]),
+            OpencodeStartupError::NpmAuth
+        ));
+
+        let unknown = classify(&["starting", "segmentation fault"]);
+        assert!(matches!(unknown, OpencodeStartupError::Unknown { .. }));
+        assert!(
+            unknown
+                .to_string()
+                .ends_with("Server output tail:\nstarting\nsegmentation fault")
+        );
+    }
+
+    #[test]
+    fn command_timeout_sets_bash_timeout_env() {
+        let base = ExecutionEnv::new(RepoContext::default(), false);
+
+        let env = setup_command_timeout_env(Some(90), &base);
+        assert_eq!(env.get(BASH_TIMEOUT_ENV).unwrap(), "90000");
+
+        let env = setup_command_timeout_env(None, &base);
+        assert!(!env.contains_key(BASH_TIMEOUT_ENV));
+    }
+
+    #[test]
+    fn auto_compact_forces_or_leaves_compaction_config() {
+        let existing = r#"{"compaction":{"auto":false,"prune":true}}"#;
+
+        let forced_on: Value =
+            serde_json::from_str(&merge_compaction_config(Some(existing), Some(true)).unwrap())
+                .unwrap();
+        assert_eq!(forced_on["compaction"]["auto"], true);
+        assert_eq!(forced_on["compaction"]["prune"], true);
+
+        let forced_off: Value =
+            serde_json::from_str(&merge_compaction_config(None, Some(false)).unwrap()).unwrap();
+        assert_eq!(forced_off["compaction"]["auto"], false);
+
+        assert_eq!(merge_compaction_config(Some(existing), None), None);
+        let mut base = ExecutionEnv::new(RepoContext::default(), false);
+        base.insert("OPENCODE_CONFIG_CONTENT", existing);
+        let env = setup_compaction_env(None, &base);
+        assert_eq!(env.get("OPENCODE_CONFIG_CONTENT").unwrap(), existing);
+    }
+
+    #[test]
+    fn extra_mcp_servers_are_merged_alongside_compaction() {
+        let mut base = ExecutionEnv::new(RepoContext::default(), false);
+        base.insert(
+            "OPENCODE_CONFIG_CONTENT",
+            r#"{"mcp":{"existing":{"type":"local","command":["existing"]}}}"#,
+        );
+        let env = setup_compaction_env(Some(true), &base);
+
+        let extra = serde_json::json!({
+            "docs": { "type": "remote", "url": "https://example.com/mcp" }
+        });
+        let env = setup_mcp_env(Some(&extra), &env);
+
+        let config: Value =
+            serde_json::from_str(env.get("OPENCODE_CONFIG_CONTENT").unwrap()).unwrap();
+        assert_eq!(config["compaction"]["auto"], true);
+        assert_eq!(config["mcp"]["docs"]["url"], "https://example.com/mcp");
+        assert_eq!(config["mcp"]["existing"]["command"][0], "existing");
+    }
 }
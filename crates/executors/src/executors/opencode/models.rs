@@ -1,6 +1,7 @@
 use std::{
     collections::{HashMap, HashSet},
     sync::{LazyLock, Mutex},
+    time::{Duration, Instant},
 };
 
 use serde_json::Value;
@@ -17,31 +18,48 @@ type ContextWindowTokens = u32;
 // Maps (Provider, Model) -> Context Window
 type ModelContextWindows = HashMap<(ProviderId, ModelId), ContextWindowTokens>;
 
+/// How long fetched context windows are trusted before the provider list is fetched again.
+const CONTEXT_WINDOWS_TTL: Duration = Duration::from_secs(60 * 60);
+
 /// Cache entry for model context windows.
 /// Keyed by a config-derived cache key (based on env vars + base command)
 /// rather than directory, since configuration determines available models.
-#[derive(Default)]
 struct ModelCacheEntry {
     context_windows: ModelContextWindows,
     /// Negative cache for models that were requested but not found.
     /// Prevents repeated API calls for models that don't return context info.
     unknown_models: HashSet<(ProviderId, ModelId)>,
+    fetched_at: Instant,
+}
+
+impl ModelCacheEntry {
+    fn new() -> Self {
+        Self {
+            context_windows: HashMap::new(),
+            unknown_models: HashSet::new(),
+            fetched_at: Instant::now(),
+        }
+    }
 }
 
 struct ModelContextCache {
     entries: Mutex<HashMap<String, ModelCacheEntry>>,
+    ttl: Duration,
 }
 
 impl ModelContextCache {
-    fn new() -> Self {
+    fn new(ttl: Duration) -> Self {
         Self {
             entries: Mutex::new(HashMap::new()),
+            ttl,
         }
     }
 
     fn get(&self, cache_key: &str, provider: &str, model: &str) -> Option<u32> {
         let map = self.entries.lock().unwrap();
-        let entry = map.get(cache_key)?;
+        let entry = map
+            .get(cache_key)
+            .filter(|entry| entry.fetched_at.elapsed() < self.ttl)?;
 
         entry
             .context_windows
@@ -63,7 +81,15 @@ impl ModelContextCache {
         fetched_windows: ModelContextWindows,
     ) -> u32 {
         let mut cache = self.entries.lock().unwrap();
-        let entry = cache.entry(cache_key.to_string()).or_default();
+        let entry = cache
+            .entry(cache_key.to_string())
+            .or_insert_with(ModelCacheEntry::new);
+
+        // Drop stale data so models removed by the provider don't linger
+        if entry.fetched_at.elapsed() >= self.ttl {
+            *entry = ModelCacheEntry::new();
+        }
+        entry.fetched_at = Instant::now();
 
         entry.context_windows.extend(fetched_windows);
         entry
@@ -83,7 +109,8 @@ impl ModelContextCache {
     }
 }
 
-static CONTEXT_WINDOWS_CACHE: LazyLock<ModelContextCache> = LazyLock::new(ModelContextCache::new);
+static CONTEXT_WINDOWS_CACHE: LazyLock<ModelContextCache> =
+    LazyLock::new(|| ModelContextCache::new(CONTEXT_WINDOWS_TTL));
 
 async fn get_model_context_window(
     client: &reqwest::Client,
@@ -201,3 +228,37 @@ async fn fetch_model_context_windows(
 
     Some(windows)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn windows(model: &str, tokens: u32) -> ModelContextWindows {
+        HashMap::from([(("anthropic".to_string(), model.to_string()), tokens)])
+    }
+
+    #[test]
+    fn fresh_entries_are_served_from_cache() {
+        let cache = ModelContextCache::new(Duration::from_secs(60));
+        cache.update("key", "anthropic", "sonnet", windows("sonnet", 200_000));
+
+        assert_eq!(cache.get("key", "anthropic", "sonnet"), Some(200_000));
+        assert_eq!(cache.get("other-key", "anthropic", "sonnet"), None);
+    }
+
+    #[test]
+    fn expired_entries_are_refetched_and_replaced() {
+        let cache = ModelContextCache::new(Duration::ZERO);
+        cache.update("key", "anthropic", "sonnet", windows("sonnet", 200_000));
+        assert_eq!(cache.get("key", "anthropic", "sonnet"), None);
+
+        let window = cache.update("key", "anthropic", "opus", windows("opus", 100_000));
+        assert_eq!(window, 100_000);
+        let entries = cache.entries.lock().unwrap();
+        assert!(
+            !entries["key"]
+                .context_windows
+                .contains_key(&("anthropic".to_string(), "sonnet".to_string()))
+        );
+    }
+}
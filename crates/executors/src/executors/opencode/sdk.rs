@@ -19,15 +19,18 @@ use tokio::{
     sync::{Mutex as AsyncMutex, mpsc, mpsc::error::TryRecvError, oneshot},
 };
 use tokio_util::sync::CancellationToken;
-use workspace_utils::{approvals::ApprovalStatus, git};
+use workspace_utils::{approvals::ApprovalStatus, git, tls};
 
-use super::{slash_commands, types::OpencodeExecutorEvent};
+use super::{
+    slash_commands,
+    types::{MessageRole, OpencodeExecutorEvent, SdkEvent},
+};
 use crate::{
     approvals::{ExecutorApprovalError, ExecutorApprovalService},
     env::RepoContext,
     executors::{
         ExecutorError,
-        opencode::{OpencodeServer, models::maybe_emit_token_usage},
+        opencode::{ServerConnection, models::maybe_emit_token_usage},
     },
 };
 
@@ -88,6 +91,12 @@ pub struct RunConfig {
     pub models_cache_key: String,
     pub commit_reminder: bool,
     pub repo_context: RepoContext,
+    /// Stop the session once the agent starts more than this many turns.
+    pub max_turns: Option<u32>,
+    /// Title given to the session when a new one is created.
+    pub session_title: Option<String>,
+    /// Provider ids hidden from the `/models` listing.
+    pub skip_providers: Vec<String>,
 }
 
 /// Generate a cryptographically secure random password for OpenCode server auth.
@@ -208,6 +217,7 @@ pub enum ControlEvent {
     Idle,
     AuthRequired { message: String },
     SessionError { message: String },
+    TurnLimitReached { max_turns: u32 },
     Disconnected,
 }
 
@@ -218,13 +228,7 @@ pub async fn run_session(
 ) -> Result<(), ExecutorError> {
     let cancel = CancellationToken::new();
 
-    let client = reqwest::Client::builder()
-        .default_headers(build_default_headers(
-            &config.directory,
-            &config.server_password,
-        ))
-        .build()
-        .map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
+    let client = build_authenticated_client(&config.directory, &config.server_password)?;
 
     let mut interrupted = false;
     let interrupt_rx = interrupt_rx.fuse();
@@ -251,14 +255,11 @@ pub async fn run_session(
 }
 
 pub(super) async fn discover_commands(
-    server: &OpencodeServer,
+    server: &ServerConnection,
     directory: &Path,
 ) -> Result<Vec<CommandInfo>, ExecutorError> {
     let directory = directory.to_string_lossy();
-    let client = reqwest::Client::builder()
-        .default_headers(build_default_headers(&directory, &server.server_password))
-        .build()
-        .map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
+    let client = build_authenticated_client(&directory, &server.password)?;
 
     wait_for_health(&client, &server.base_url).await?;
     list_commands(&client, &server.base_url, &directory).await
@@ -272,13 +273,7 @@ pub async fn run_slash_command(
 ) -> Result<(), ExecutorError> {
     let cancel = CancellationToken::new();
 
-    let client = reqwest::Client::builder()
-        .default_headers(build_default_headers(
-            &config.directory,
-            &config.server_password,
-        ))
-        .build()
-        .map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
+    let client = build_authenticated_client(&config.directory, &config.server_password)?;
 
     let mut interrupted = false;
     let interrupt_rx = interrupt_rx.fuse();
@@ -325,7 +320,12 @@ async fn run_session_inner(
         }
         None => tokio::select! {
             _ = cancel.cancelled() => return Ok(()),
-            res = create_session(&client, &config.base_url, &config.directory) => res?,
+            res = create_session(
+                &client,
+                &config.base_url,
+                &config.directory,
+                config.session_title.as_deref(),
+            ) => res?,
         },
     };
 
@@ -354,6 +354,7 @@ async fn run_session_inner(
             auto_approve: config.auto_approve,
             control_tx,
             models_cache_key: config.models_cache_key.clone(),
+            max_turns: config.max_turns,
         },
         event_resp,
     ));
@@ -377,6 +378,8 @@ async fn run_session_inner(
     }
 
     if let Err(err) = prompt_result {
+        // Stop the server-side session too, e.g. when the turn limit was hit
+        send_abort(&client, &config.base_url, &config.directory, &session_id).await;
         event_handle.abort();
         return Err(err);
     }
@@ -431,6 +434,19 @@ async fn run_session_inner(
     Ok(())
 }
 
+/// Client for the OpenCode server. Like the app's other HTTP clients it also
+/// trusts any extra CA from `VK_CA_BUNDLE`.
+fn build_authenticated_client(
+    directory: &str,
+    password: &str,
+) -> Result<reqwest::Client, ExecutorError> {
+    tls::with_custom_ca(reqwest::Client::builder())
+        .map_err(|err| ExecutorError::ClientBuild(format!("custom CA bundle: {err}")))?
+        .default_headers(build_default_headers(directory, password))
+        .build()
+        .map_err(|err| ExecutorError::ClientBuild(err.to_string()))
+}
+
 fn build_default_headers(directory: &str, password: &str) -> HeaderMap {
     let mut headers = HeaderMap::new();
     if let Ok(value) = HeaderValue::from_str(directory) {
@@ -474,6 +490,9 @@ where
             Ok(ControlEvent::SessionError { message }) => {
                 append_session_error(&mut session_error, message);
             }
+            Ok(ControlEvent::TurnLimitReached { max_turns }) => {
+                return Err(turn_limit_error(max_turns));
+            }
             Ok(ControlEvent::Disconnected) if !cancel.is_cancelled() => {
                 return Err(ExecutorError::Io(io::Error::other(
                     "OpenCode event stream disconnected before request started",
@@ -491,6 +510,7 @@ where
             event = control_rx.recv() => match event {
                 Some(ControlEvent::AuthRequired { message }) => return Err(ExecutorError::AuthRequired(message)),
                 Some(ControlEvent::SessionError { message }) => append_session_error(&mut session_error, message),
+                Some(ControlEvent::TurnLimitReached { max_turns }) => return Err(turn_limit_error(max_turns)),
                 Some(ControlEvent::Disconnected) if !cancel.is_cancelled() => {
                     return Err(ExecutorError::Io(io::Error::other("OpenCode event stream disconnected while request was running")));
                 }
@@ -518,6 +538,7 @@ where
                     Some(ControlEvent::Idle) | None => break,
                     Some(ControlEvent::AuthRequired { message }) => return Err(ExecutorError::AuthRequired(message)),
                     Some(ControlEvent::SessionError { message }) => append_session_error(&mut session_error, message),
+                    Some(ControlEvent::TurnLimitReached { max_turns }) => return Err(turn_limit_error(max_turns)),
                     Some(ControlEvent::Disconnected) if !cancel.is_cancelled() => {
                         return Err(ExecutorError::Io(io::Error::other(
                             "OpenCode event stream disconnected while waiting for session to go idle",
@@ -539,6 +560,12 @@ where
     Ok(())
 }
 
+fn turn_limit_error(max_turns: u32) -> ExecutorError {
+    ExecutorError::Io(io::Error::other(format!(
+        "OpenCode session stopped after reaching the limit of {max_turns} turns"
+    )))
+}
+
 pub async fn wait_for_health(
     client: &reqwest::Client,
     base_url: &str,
@@ -581,11 +608,16 @@ pub async fn create_session(
     client: &reqwest::Client,
     base_url: &str,
     directory: &str,
+    title: Option<&str>,
 ) -> Result<String, ExecutorError> {
+    let body = match title {
+        Some(title) => serde_json::json!({ "title": title }),
+        None => serde_json::json!({}),
+    };
     let resp = client
         .post(format!("{base_url}/session"))
         .query(&[("directory", directory)])
-        .json(&serde_json::json!({}))
+        .json(&body)
         .send()
         .await
         .map_err(|err| ExecutorError::Io(io::Error::other(err)))?;
@@ -1137,6 +1169,7 @@ pub struct EventListenerConfig {
     pub auto_approve: bool,
     pub control_tx: mpsc::UnboundedSender<ControlEvent>,
     pub models_cache_key: String,
+    pub max_turns: Option<u32>,
 }
 
 pub async fn spawn_event_listener(config: EventListenerConfig, initial_resp: reqwest::Response) {
@@ -1150,9 +1183,11 @@ pub async fn spawn_event_listener(config: EventListenerConfig, initial_resp: req
         auto_approve,
         control_tx,
         models_cache_key,
+        max_turns,
     } = config;
 
     let mut seen_permissions: HashSet<String> = HashSet::new();
+    let mut seen_turns: HashSet<String> = HashSet::new();
     let mut last_event_id: Option<String> = None;
     let mut base_retry_delay = Duration::from_millis(3000);
     let mut attempt: u32 = 0;
@@ -1193,6 +1228,8 @@ pub async fn spawn_event_listener(config: EventListenerConfig, initial_resp: req
         let outcome = process_event_stream(
             EventStreamContext {
                 seen_permissions: &mut seen_permissions,
+                seen_turns: &mut seen_turns,
+                max_turns,
                 client: &client,
                 base_url: &base_url,
                 directory: &directory,
@@ -1248,6 +1285,9 @@ enum EventStreamOutcome {
 
 pub(super) struct EventStreamContext<'a> {
     seen_permissions: &'a mut HashSet<String>,
+    /// IDs of assistant messages seen so far; each one is a turn.
+    seen_turns: &'a mut HashSet<String>,
+    max_turns: Option<u32>,
     pub client: &'a reqwest::Client,
     pub base_url: &'a str,
     pub directory: &'a str,
@@ -1310,6 +1350,14 @@ async fn process_event_stream(
 
         match event_type {
             "message.updated" => {
+                if let Some(max_turns) = ctx.max_turns
+                    && record_turn(ctx.seen_turns, &data) > max_turns as usize
+                {
+                    let _ = ctx
+                        .control_tx
+                        .send(ControlEvent::TurnLimitReached { max_turns });
+                    return Ok(EventStreamOutcome::Terminal);
+                }
                 maybe_emit_token_usage(&ctx, &data).await;
             }
             "session.idle" => {
@@ -1440,6 +1488,17 @@ async fn process_event_stream(
     Ok(EventStreamOutcome::Disconnected)
 }
 
+/// Record the assistant message in a `message.updated` event as a turn and
+/// return the number of turns seen so far.
+fn record_turn(seen_turns: &mut HashSet<String>, event: &Value) -> usize {
+    if let Some(SdkEvent::MessageUpdated(event)) = SdkEvent::parse(event)
+        && event.info.role == MessageRole::Assistant
+    {
+        seen_turns.insert(event.info.id);
+    }
+    seen_turns.len()
+}
+
 fn event_matches_session(event_type: &str, event: &Value, session_id: &str) -> bool {
     let extracted = match event_type {
         "message.updated" => event
@@ -1497,3 +1556,90 @@ async fn request_permission_approval(
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use tokio::io::AsyncReadExt;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn concurrent_log_events_write_whole_lines() {
+        // A tiny pipe buffer forces many partial writes per line, so any
+        // interleaving between callers would corrupt the JSON lines.
+        let (writer, mut reader) = tokio::io::duplex(16);
+        let log_writer = LogWriter::new(writer);
+        let reader_task = tokio::spawn(async move {
+            let mut output = String::new();
+            reader.read_to_string(&mut output).await.unwrap();
+            output
+        });
+
+        let writers: Vec<_> = (0..64)
+            .map(|i| {
+                let log_writer = log_writer.clone();
+                tokio::spawn(async move {
+                    log_writer
+                        .log_error(format!("error {i} {}", "x".repeat(100)))
+                        .await
+                        .unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap();
+        }
+        drop(log_writer);
+
+        let output = reader_task.await.unwrap();
+        let mut messages = HashSet::new();
+        for line in output.lines() {
+            let event: OpencodeExecutorEvent =
+                serde_json::from_str(line).expect("each line is a complete event");
+            let OpencodeExecutorEvent::Error { message } = event else {
+                panic!("unexpected event: {line}");
+            };
+            messages.insert(message);
+        }
+        assert_eq!(messages.len(), 64);
+    }
+
+    #[test]
+    fn turns_count_distinct_assistant_messages() {
+        let message = |id: &str, role: &str| {
+            serde_json::json!({
+                "type": "message.updated",
+                "properties": { "info": { "id": id, "role": role, "sessionID": "ses" } }
+            })
+        };
+
+        let mut seen_turns = HashSet::new();
+        assert_eq!(
+            record_turn(&mut seen_turns, &message("msg_user", "user")),
+            0
+        );
+        assert_eq!(
+            record_turn(&mut seen_turns, &message("msg_1", "assistant")),
+            1
+        );
+        // Streaming updates to the same message are still one turn.
+        assert_eq!(
+            record_turn(&mut seen_turns, &message("msg_1", "assistant")),
+            1
+        );
+        assert_eq!(
+            record_turn(&mut seen_turns, &message("msg_2", "assistant")),
+            2
+        );
+    }
+
+    #[test]
+    fn client_build_failures_keep_their_cause() {
+        let err = ExecutorError::ClientBuild("custom CA bundle: no PEM certificates".into());
+        assert!(matches!(&err, ExecutorError::ClientBuild(cause) if cause.contains("CA bundle")));
+        assert_eq!(
+            err.to_string(),
+            "Failed to build HTTP client: custom CA bundle: no PEM certificates"
+        );
+    }
+}
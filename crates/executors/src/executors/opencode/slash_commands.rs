@@ -10,6 +10,7 @@ use std::{
     io,
     path::Path,
     pin::Pin,
+    time::Duration,
 };
 
 use serde_json::Value;
@@ -28,10 +29,10 @@ use crate::{
     env::{ExecutionEnv, RepoContext},
     executors::{
         BaseCodingAgent, ExecutorError, SlashCommandDescription,
-        opencode::Opencode,
+        opencode::{Opencode, ServerConnection, session_server},
         utils::{
-            SlashCommandCache, SlashCommandCacheKey, SlashCommandCall, parse_slash_command,
-            reorder_slash_commands,
+            SlashCommandCache, SlashCommandCacheKey, SlashCommandCall, discover_within,
+            parse_slash_command, reorder_slash_commands,
         },
     },
 };
@@ -55,18 +56,52 @@ pub enum OpencodeSlashCommand {
 }
 
 impl Opencode {
+    /// Discover slash commands, asking the server of a session already running in
+    /// `current_dir` when there is one.
     pub async fn discover_slash_commands(
         &self,
         current_dir: &Path,
+    ) -> Result<Vec<SlashCommandDescription>, ExecutorError> {
+        self.discover_slash_commands_with_server(current_dir, session_server(current_dir))
+            .await
+    }
+
+    /// Discover slash commands, reusing `existing` (e.g. the server of an active session)
+    /// instead of spawning a fresh OpenCode server when provided.
+    pub async fn discover_slash_commands_with_server(
+        &self,
+        current_dir: &Path,
+        existing: Option<ServerConnection>,
     ) -> Result<Vec<SlashCommandDescription>, ExecutorError> {
         let key = SlashCommandCacheKey::new(current_dir, &BaseCodingAgent::Opencode);
         if let Some(cached) = SlashCommandCache::instance().get(&key) {
             return Ok((*cached).clone());
         }
 
-        let env = ExecutionEnv::new(RepoContext::default(), false);
-        let server = self.spawn_server(current_dir, &env).await?;
-        let commands = sdk::discover_commands(&server, current_dir).await?;
+        let discovery = async {
+            let first_attempt = match existing {
+                Some(connection) => sdk::discover_commands(&connection, current_dir)
+                    .await
+                    .map_err(DiscoveryError::Request),
+                None => self.discover_commands_with_fresh_server(current_dir).await,
+            };
+            retry_with_fresh_server(
+                first_attempt,
+                self.retry_discovery_with_fresh_server.unwrap_or(true),
+                || async {
+                    self.discover_commands_with_fresh_server(current_dir)
+                        .await
+                        .map_err(DiscoveryError::into_inner)
+                },
+            )
+            .await
+        };
+        let commands = match self.discovery_timeout_secs {
+            Some(secs) => {
+                discover_within("OpenCode", Duration::from_secs(secs.into()), discovery).await?
+            }
+            None => discovery.await?,
+        };
 
         let defaults = hardcoded_slash_commands();
         let mut seen: HashSet<String> = defaults.iter().map(|cmd| cmd.name.clone()).collect();
@@ -78,6 +113,7 @@ impl Opencode {
                 SlashCommandDescription {
                     name,
                     description: cmd.description,
+                    scope: None,
                 }
             })
             .filter(|cmd| seen.insert(cmd.name.clone()))
@@ -90,6 +126,60 @@ impl Opencode {
 
         Ok(commands)
     }
+
+    async fn discover_commands_with_fresh_server(
+        &self,
+        current_dir: &Path,
+    ) -> Result<Vec<CommandInfo>, DiscoveryError> {
+        let env = ExecutionEnv::new(RepoContext::default(), false);
+        let server = self
+            .spawn_server(current_dir, &env)
+            .await
+            .map_err(DiscoveryError::Startup)?;
+        sdk::discover_commands(&server.connection(), current_dir)
+            .await
+            .map_err(DiscoveryError::Request)
+    }
+}
+
+/// Where a discovery attempt failed.
+#[derive(Debug)]
+enum DiscoveryError {
+    /// The server never came up; a fresh one would most likely fail the same way.
+    Startup(ExecutorError),
+    /// The server was running and then failed a request, e.g. because it died
+    /// during provider enumeration.
+    Request(ExecutorError),
+}
+
+impl DiscoveryError {
+    fn into_inner(self) -> ExecutorError {
+        match self {
+            Self::Startup(err) | Self::Request(err) => err,
+        }
+    }
+}
+
+/// Respawn the server and retry discovery once when `retry` is set and a
+/// server that had started fails mid-discovery. Startup failures aren't
+/// retried, so a server that can't start doesn't cost two startup timeouts.
+async fn retry_with_fresh_server<T, F, Fut>(
+    first_attempt: Result<T, DiscoveryError>,
+    retry: bool,
+    fresh_attempt: F,
+) -> Result<T, ExecutorError>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T, ExecutorError>>,
+{
+    match first_attempt {
+        Ok(value) => Ok(value),
+        Err(DiscoveryError::Request(err)) if retry => {
+            tracing::warn!("OpenCode discovery failed, retrying with a fresh server: {err}");
+            fresh_attempt().await
+        }
+        Err(err) => Err(err.into_inner()),
+    }
 }
 
 impl OpencodeSlashCommand {
@@ -134,26 +224,32 @@ pub fn hardcoded_slash_commands() -> Vec<SlashCommandDescription> {
         SlashCommandDescription {
             name: "compact".to_string(),
             description: Some("compact the session".to_string()),
+            scope: None,
         },
         SlashCommandDescription {
             name: "commands".to_string(),
             description: Some("show all commands".to_string()),
+            scope: None,
         },
         SlashCommandDescription {
             name: "models".to_string(),
             description: Some("list models".to_string()),
+            scope: None,
         },
         SlashCommandDescription {
             name: "agents".to_string(),
             description: Some("list agents".to_string()),
+            scope: None,
         },
         SlashCommandDescription {
             name: "status".to_string(),
             description: Some("show status".to_string()),
+            scope: None,
         },
         SlashCommandDescription {
             name: "mcp".to_string(),
             description: Some("show MCP status".to_string()),
+            scope: None,
         },
     ]
 }
@@ -203,13 +299,24 @@ fn format_agents(agents: &[AgentInfo]) -> String {
     lines.join("\n")
 }
 
-/// Format models list as markdown.
+/// Format models list as markdown, leaving out the providers in `skip_providers`.
 fn format_models(
     config_providers: &ConfigProvidersResponse,
     provider_list: Option<&ProviderListResponse>,
     provider_filter: Option<&str>,
+    skip_providers: &[String],
 ) -> String {
-    let mut providers: Vec<_> = config_providers.providers.iter().collect();
+    let mut providers: Vec<_> = config_providers
+        .providers
+        .iter()
+        .filter(|provider| {
+            let skip = skip_providers.contains(&provider.id);
+            if skip {
+                tracing::debug!("Skipping OpenCode provider {}", provider.id);
+            }
+            !skip
+        })
+        .collect();
     providers.sort_by(|a, b| a.id.cmp(&b.id));
 
     if providers.is_empty() {
@@ -247,12 +354,17 @@ fn format_models(
         lines.push(String::new());
     }
 
-    if let Some(list) = provider_list
-        && !list.connected.is_empty()
-    {
-        let mut connected = list.connected.clone();
-        connected.sort();
-        lines.push(format!("**Connected:** {}", connected.join(", ")));
+    if let Some(list) = provider_list {
+        let mut connected: Vec<_> = list
+            .connected
+            .iter()
+            .filter(|id| !skip_providers.contains(id))
+            .cloned()
+            .collect();
+        if !connected.is_empty() {
+            connected.sort();
+            lines.push(format!("**Connected:** {}", connected.join(", ")));
+        }
     }
 
     lines.join("\n").trim_end().to_string()
@@ -416,6 +528,7 @@ pub async fn execute(
                     &config_providers,
                     provider_list.as_ref(),
                     provider.as_deref(),
+                    &config.skip_providers,
                 ),
             )
             .await?;
@@ -475,7 +588,12 @@ pub async fn execute(
         Some(existing) => existing.to_string(),
         None => tokio::select! {
             _ = cancel.cancelled() => return Ok(()),
-            res = sdk::create_session(&client, &config.base_url, &config.directory) => res?,
+            res = sdk::create_session(
+                &client,
+                &config.base_url,
+                &config.directory,
+                config.session_title.as_deref(),
+            ) => res?,
         },
     };
 
@@ -516,6 +634,7 @@ pub async fn execute(
             auto_approve: config.auto_approve,
             control_tx,
             models_cache_key: config.models_cache_key.clone(),
+            max_turns: config.max_turns,
         },
         event_resp,
     ));
@@ -578,3 +697,169 @@ pub async fn execute(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::{Json, Router, routing::get};
+    use serde_json::json;
+
+    use super::*;
+    use crate::executors::opencode::SessionServerRegistration;
+
+    /// Serve a fake OpenCode server that reports a single `/deploy` command.
+    async fn command_server() -> ServerConnection {
+        let router = Router::new()
+            .route(
+                "/global/health",
+                get(|| async { Json(json!({ "healthy": true, "version": "test" })) }),
+            )
+            .route(
+                "/command",
+                get(|| async { Json(json!([{ "name": "/deploy", "description": "ship it" }])) }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+        ServerConnection {
+            base_url,
+            password: "secret".to_string(),
+        }
+    }
+
+    /// An executor whose server can never be spawned, proving discovery never tried to.
+    fn unspawnable_executor() -> Opencode {
+        serde_json::from_value(json!({
+            "base_command_override": "vk-nonexistent-opencode-binary"
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn discovery_reuses_existing_server_without_spawning() {
+        let server = command_server().await;
+        let dir = std::env::temp_dir().join(format!("vk-opencode-{}", uuid::Uuid::new_v4()));
+
+        let commands = unspawnable_executor()
+            .discover_slash_commands_with_server(&dir, Some(server))
+            .await
+            .unwrap();
+
+        assert!(commands.iter().any(|cmd| cmd.name == "deploy"));
+    }
+
+    #[tokio::test]
+    async fn discovery_asks_the_server_of_a_running_session() {
+        let server = command_server().await;
+        let dir = std::env::temp_dir().join(format!("vk-opencode-{}", uuid::Uuid::new_v4()));
+
+        let registration = SessionServerRegistration::register(dir.clone(), server);
+        let commands = unspawnable_executor()
+            .discover_slash_commands(&dir)
+            .await
+            .unwrap();
+        assert!(commands.iter().any(|cmd| cmd.name == "deploy"));
+
+        drop(registration);
+        assert!(session_server(&dir).is_none());
+    }
+
+    #[tokio::test]
+    async fn discovery_gives_up_after_the_configured_timeout() {
+        // A server that never reports healthy
+        let router = Router::new().route(
+            "/global/health",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(30)).await;
+                Json(json!({ "healthy": true, "version": "test" }))
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        let executor: Opencode = serde_json::from_value(json!({
+            "base_command_override": "vk-nonexistent-opencode-binary",
+            "discovery_timeout_secs": 1
+        }))
+        .unwrap();
+        let dir = std::env::temp_dir().join(format!("vk-opencode-{}", uuid::Uuid::new_v4()));
+
+        let started = std::time::Instant::now();
+        let err = executor
+            .discover_slash_commands_with_server(
+                &dir,
+                Some(ServerConnection {
+                    base_url,
+                    password: "secret".to_string(),
+                }),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(
+            err.to_string().contains("Timed out discovering OpenCode"),
+            "{err}"
+        );
+        assert!(started.elapsed() < Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn failed_discovery_is_retried_once_with_a_fresh_server() {
+        let error = || ExecutorError::Io(io::Error::other("server exited"));
+        let failed = || Err::<Vec<u8>, _>(DiscoveryError::Request(error()));
+
+        let retried = retry_with_fresh_server(failed(), true, || async { Ok(vec![1]) }).await;
+        assert_eq!(retried.unwrap(), vec![1]);
+
+        let not_retried = retry_with_fresh_server(failed(), false, || async { Ok(vec![1]) }).await;
+        assert!(not_retried.is_err());
+
+        let still_failing =
+            retry_with_fresh_server(failed(), true, || async { Err(error()) }).await;
+        assert!(still_failing.is_err());
+
+        // A server that never started isn't respawned
+        let mut respawned = false;
+        let startup_failed = retry_with_fresh_server(
+            Err::<Vec<u8>, _>(DiscoveryError::Startup(error())),
+            true,
+            || {
+                respawned = true;
+                async { Ok(vec![1]) }
+            },
+        )
+        .await;
+        assert!(startup_failed.is_err());
+        assert!(!respawned);
+    }
+
+    #[test]
+    fn skipped_providers_are_left_out_of_the_model_list() {
+        let config_providers: ConfigProvidersResponse = serde_json::from_value(json!({
+            "providers": [
+                { "id": "anthropic", "models": { "claude-sonnet": {} } },
+                { "id": "broken", "models": { "never-loads": {} } }
+            ],
+            "default": { "anthropic": "claude-sonnet", "broken": "never-loads" }
+        }))
+        .unwrap();
+        let provider_list: ProviderListResponse = serde_json::from_value(json!({
+            "all": [],
+            "default": {},
+            "connected": ["anthropic", "broken"]
+        }))
+        .unwrap();
+
+        let models = format_models(
+            &config_providers,
+            Some(&provider_list),
+            None,
+            &["broken".to_string()],
+        );
+        assert!(models.contains("`anthropic/claude-sonnet`"));
+        assert!(!models.contains("broken"));
+
+        let unskipped = format_models(&config_providers, Some(&provider_list), None, &[]);
+        assert!(unskipped.contains("`broken/never-loads`"));
+    }
+}
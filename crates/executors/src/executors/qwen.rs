@@ -1,4 +1,4 @@
-use std::{path::Path, sync::Arc};
+use std::{path::Path, sync::Arc, time::Duration};
 
 use async_trait::async_trait;
 use derivative::Derivative;
@@ -24,6 +24,10 @@ pub struct QwenCode {
     pub append_prompt: AppendPrompt,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub yolo: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acp_protocol_version: Option<u16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acp_handshake_timeout_secs: Option<u64>,
     #[serde(flatten)]
     pub cmd: CmdOverrides,
     #[serde(skip)]
@@ -34,7 +38,7 @@ pub struct QwenCode {
 
 impl QwenCode {
     fn build_command_builder(&self) -> Result<CommandBuilder, CommandBuildError> {
-        let mut builder = CommandBuilder::new("npx -y @qwen-code/qwen-code@0.2.1");
+        let mut builder = CommandBuilder::npm_package("@qwen-code/qwen-code@0.2.1", "qwen");
 
         if self.yolo.unwrap_or(false) {
             builder = builder.extend_params(["--yolo"]);
@@ -42,10 +46,26 @@ impl QwenCode {
         builder = builder.extend_params(["--experimental-acp"]);
         apply_overrides(builder, &self.cmd)
     }
+
+    fn harness(&self) -> AcpAgentHarness {
+        let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
+        let harness = match self.acp_protocol_version {
+            Some(version) => harness.with_protocol_version(version),
+            None => harness,
+        };
+        match self.acp_handshake_timeout_secs {
+            Some(secs) => harness.with_handshake_timeout(Duration::from_secs(secs)),
+            None => harness,
+        }
+    }
 }
 
 #[async_trait]
 impl StandardCodingAgentExecutor for QwenCode {
+    async fn command_preview(&self) -> Result<Option<CommandBuilder>, ExecutorError> {
+        Ok(Some(self.build_command_builder()?))
+    }
+
     fn use_approvals(&mut self, approvals: Arc<dyn ExecutorApprovalService>) {
         self.approvals = Some(approvals);
     }
@@ -58,7 +78,7 @@ impl StandardCodingAgentExecutor for QwenCode {
     ) -> Result<SpawnedChild, ExecutorError> {
         let qwen_command = self.build_command_builder()?.build_initial()?;
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
-        let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
+        let harness = self.harness();
         let approvals = if self.yolo.unwrap_or(false) {
             None
         } else {
@@ -85,7 +105,7 @@ impl StandardCodingAgentExecutor for QwenCode {
     ) -> Result<SpawnedChild, ExecutorError> {
         let qwen_command = self.build_command_builder()?.build_follow_up(&[])?;
         let combined_prompt = self.append_prompt.combine_prompt(prompt);
-        let harness = AcpAgentHarness::with_session_namespace("qwen_sessions");
+        let harness = self.harness();
         let approvals = if self.yolo.unwrap_or(false) {
             None
         } else {
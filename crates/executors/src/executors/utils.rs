@@ -1,4 +1,5 @@
 use std::{
+    future::Future,
     num::NonZeroUsize,
     path::PathBuf,
     sync::{Arc, Mutex, OnceLock},
@@ -8,7 +9,7 @@ use std::{
 use lru::LruCache;
 
 use super::SlashCommandDescription;
-use crate::executors::BaseCodingAgent;
+use crate::executors::{BaseCodingAgent, ExecutorError};
 
 /// Parsed slash command with name and arguments.
 
@@ -27,12 +28,23 @@ pub struct SlashCommandCall<'a> {
 ///
 /// The return type `T` must implement `From<SlashCommandCall>`.
 pub fn parse_slash_command<'a, T>(prompt: &'a str) -> Option<T>
+where
+    T: From<SlashCommandCall<'a>>,
+{
+    parse_slash_command_with_prefix(prompt, '/')
+}
+
+/// Parse a command from a prompt string using a custom prefix character (e.g. `:`).
+///
+/// The prefix must be the first non-whitespace character of the prompt; a prefix
+/// appearing later in the text does not match.
+pub fn parse_slash_command_with_prefix<'a, T>(prompt: &'a str, prefix: char) -> Option<T>
 where
     T: From<SlashCommandCall<'a>>,
 {
     let trimmed = prompt.trim_start();
-    let without_slash = trimmed.strip_prefix('/')?;
-    let mut parts = without_slash.splitn(2, |ch: char| ch.is_whitespace());
+    let without_prefix = trimmed.strip_prefix(prefix)?;
+    let mut parts = without_prefix.splitn(2, |ch: char| ch.is_whitespace());
     let name = parts.next()?.trim().to_lowercase();
     if name.is_empty() {
         return None;
@@ -41,6 +53,23 @@ where
     Some(T::from(SlashCommandCall { name, arguments }))
 }
 
+/// Run `agent`'s slash command discovery, failing once `timeout` has passed
+/// rather than waiting on a CLI that hangs.
+pub async fn discover_within<T>(
+    agent: &str,
+    timeout: Duration,
+    discovery: impl Future<Output = Result<T, ExecutorError>>,
+) -> Result<T, ExecutorError> {
+    tokio::time::timeout(timeout, discovery)
+        .await
+        .unwrap_or_else(|_| {
+            Err(ExecutorError::Io(std::io::Error::other(format!(
+                "Timed out discovering {agent} slash commands after {}s",
+                timeout.as_secs()
+            ))))
+        })
+}
+
 pub const SLASH_COMMANDS_CACHE_CAPACITY: usize = 32;
 const TTL: Duration = Duration::from_secs(60 * 5);
 
@@ -68,6 +97,26 @@ pub fn reorder_slash_commands(
         .collect()
 }
 
+/// Keep only the slash commands a profile exposes: those in `allow` (every
+/// command when `None`) and not in `deny`. Deny wins when a name is in both.
+#[must_use]
+pub fn filter_slash_commands(
+    commands: Vec<SlashCommandDescription>,
+    allow: Option<&[String]>,
+    deny: Option<&[String]>,
+) -> Vec<SlashCommandDescription> {
+    let listed = |names: &[String], command: &SlashCommandDescription| {
+        names
+            .iter()
+            .any(|name| name.trim_start_matches('/') == command.name)
+    };
+    commands
+        .into_iter()
+        .filter(|command| allow.is_none_or(|names| listed(names, command)))
+        .filter(|command| !deny.is_some_and(|names| listed(names, command)))
+        .collect()
+}
+
 /// Executors can use this key to cache expensive slash command retrievals.
 pub struct SlashCommandCache {
     cache: Mutex<LruCache<SlashCommandCacheKey, CachedEntry>>,
@@ -77,6 +126,7 @@ pub struct SlashCommandCache {
 pub struct SlashCommandCacheKey {
     path: PathBuf,
     executor_id: String,
+    variant: Option<String>,
 }
 
 impl SlashCommandCacheKey {
@@ -85,8 +135,15 @@ impl SlashCommandCacheKey {
         Self {
             path: path.into(),
             executor_id: executor.to_string(),
+            variant: None,
         }
     }
+
+    /// Keep separate entries for profiles whose settings change what is discovered.
+    pub fn with_variant(mut self, variant: impl Into<String>) -> Self {
+        self.variant = Some(variant.into());
+        self
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -130,3 +187,73 @@ impl SlashCommandCache {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_custom_prefix() {
+        let call: SlashCommandCall<'_> =
+            parse_slash_command_with_prefix("  :Review  src/lib.rs", ':').unwrap();
+        assert_eq!(call.name, "review");
+        assert_eq!(call.arguments, "src/lib.rs");
+
+        assert!(parse_slash_command_with_prefix::<SlashCommandCall<'_>>("/review", ':').is_none());
+    }
+
+    #[test]
+    fn prefix_later_in_text_does_not_match() {
+        assert!(
+            parse_slash_command_with_prefix::<SlashCommandCall<'_>>("please run :review", ':')
+                .is_none()
+        );
+        assert!(parse_slash_command::<SlashCommandCall<'_>>("see /help for details").is_none());
+    }
+
+    fn command_names(commands: Vec<SlashCommandDescription>) -> Vec<String> {
+        commands.into_iter().map(|c| c.name).collect()
+    }
+
+    fn commands() -> Vec<SlashCommandDescription> {
+        ["compact", "review", "init", "deploy"]
+            .into_iter()
+            .map(|name| SlashCommandDescription {
+                name: name.to_string(),
+                description: None,
+                scope: None,
+            })
+            .collect()
+    }
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn allow_list_keeps_only_listed_commands_in_order() {
+        let allow = names(&["/deploy", "compact", "missing"]);
+        let filtered = filter_slash_commands(commands(), Some(&allow), None);
+        assert_eq!(command_names(filtered), ["compact", "deploy"]);
+    }
+
+    #[test]
+    fn deny_list_drops_listed_commands() {
+        let deny = names(&["review", "/init"]);
+        let filtered = filter_slash_commands(commands(), None, Some(&deny));
+        assert_eq!(command_names(filtered), ["compact", "deploy"]);
+    }
+
+    #[test]
+    fn deny_wins_over_allow() {
+        let allow = names(&["compact", "review", "deploy"]);
+        let deny = names(&["deploy"]);
+        let filtered = filter_slash_commands(commands(), Some(&allow), Some(&deny));
+        assert_eq!(command_names(filtered), ["compact", "review"]);
+
+        assert_eq!(
+            command_names(filter_slash_commands(commands(), None, None)),
+            ["compact", "review", "init", "deploy"]
+        );
+    }
+}
@@ -0,0 +1,159 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use futures::StreamExt;
+use serde::Serialize;
+use tokio::{
+    fs::OpenOptions,
+    io::{AsyncWriteExt, BufWriter},
+    task::JoinHandle,
+};
+use workspace_utils::{assets::asset_dir, log_msg::LogMsg, msg_store::MsgStore};
+
+use super::{NormalizedEntry, utils::patch::extract_normalized_entry_from_patch};
+
+/// One line of the JSONL log. Entries are updated while the agent runs (e.g. a
+/// tool call gaining its result), so a later line with the same `index`
+/// supersedes earlier ones.
+#[derive(Serialize)]
+struct JsonlEntry<'a> {
+    index: usize,
+    entry: &'a NormalizedEntry,
+}
+
+/// Where a configured JSONL log path points. Relative paths resolve under the
+/// app's data directory rather than the worktree, so the file can't end up in
+/// the agent's commits.
+pub fn resolve_jsonl_path(path: &str) -> PathBuf {
+    resolve_under(Path::new(path), &asset_dir().join("logs"))
+}
+
+fn resolve_under(path: &Path, base: &Path) -> PathBuf {
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        base.join(path)
+    }
+}
+
+/// Mirror the normalized entries pushed to `msg_store` into `path`, one JSON
+/// object per line. Lines are appended, so runs sharing a file are kept one
+/// after another, and flushed once the store finishes.
+pub fn spawn_jsonl_writer(msg_store: Arc<MsgStore>, path: PathBuf) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        if let Err(e) = write_jsonl(msg_store, &path).await {
+            tracing::warn!("Failed to write normalized logs to {}: {e}", path.display());
+        }
+    })
+}
+
+async fn write_jsonl(msg_store: Arc<MsgStore>, path: &Path) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .await?;
+    let mut writer = BufWriter::new(file);
+
+    let mut messages = msg_store.history_plus_stream();
+    while let Some(Ok(msg)) = messages.next().await {
+        match msg {
+            LogMsg::JsonPatch(patch) => {
+                let Some((index, entry)) = extract_normalized_entry_from_patch(&patch) else {
+                    continue;
+                };
+                let mut line = serde_json::to_vec(&JsonlEntry {
+                    index,
+                    entry: &entry,
+                })?;
+                line.push(b'\n');
+                writer.write_all(&line).await?;
+            }
+            LogMsg::Finished => break,
+            _ => {}
+        }
+    }
+
+    writer.flush().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::{NormalizedEntryType, utils::patch::ConversationPatch};
+
+    /// Run a writer over a store holding `contents` as entries and some raw output.
+    async fn write_run(path: &Path, contents: &[&str]) {
+        let msg_store = Arc::new(MsgStore::new());
+        let writer = spawn_jsonl_writer(msg_store.clone(), path.to_path_buf());
+
+        for (index, content) in contents.iter().enumerate() {
+            msg_store.push_patch(ConversationPatch::add_normalized_entry(
+                index,
+                NormalizedEntry {
+                    timestamp: None,
+                    entry_type: NormalizedEntryType::AssistantMessage,
+                    content: content.to_string(),
+                    metadata: None,
+                },
+            ));
+        }
+        msg_store.push_stdout("not an entry");
+        msg_store.push_finished();
+        writer.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn writes_one_line_per_normalized_entry() {
+        let path = std::env::temp_dir().join(format!("vk-jsonl-{}.jsonl", uuid::Uuid::new_v4()));
+        write_run(&path, &["hello", "world"]).await;
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<serde_json::Value> = written
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["index"], 0);
+        assert_eq!(lines[0]["entry"]["content"], "hello");
+        assert_eq!(lines[1]["index"], 1);
+        assert_eq!(lines[1]["entry"]["content"], "world");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn later_runs_are_appended() {
+        let path = std::env::temp_dir().join(format!("vk-jsonl-{}.jsonl", uuid::Uuid::new_v4()));
+        write_run(&path, &["first run"]).await;
+        write_run(&path, &["second run"]).await;
+
+        let written = std::fs::read_to_string(&path).unwrap();
+        let contents: Vec<String> = written
+            .lines()
+            .map(|line| {
+                let line: serde_json::Value = serde_json::from_str(line).unwrap();
+                line["entry"]["content"].as_str().unwrap().to_string()
+            })
+            .collect();
+        assert_eq!(contents, ["first run", "second run"]);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn relative_paths_resolve_outside_the_worktree() {
+        let base = Path::new("/data/logs");
+        assert_eq!(
+            resolve_under(Path::new("opencode.jsonl"), base),
+            base.join("opencode.jsonl")
+        );
+        let absolute = std::env::temp_dir().join("opencode.jsonl");
+        assert_eq!(resolve_under(&absolute, base), absolute);
+    }
+}
@@ -0,0 +1,197 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+};
+
+use futures::StreamExt;
+use tokio::task::JoinHandle;
+use uuid::Uuid;
+use workspace_utils::{log_msg::LogMsg, msg_store::MsgStore};
+
+use super::{NormalizedEntry, utils::patch::extract_normalized_entry_from_patch};
+
+/// How many normalized entries a workspace keeps for tail requests.
+pub const DEFAULT_TAIL_CAPACITY: usize = 200;
+
+/// Entries are keyed by process and entry index, since indices restart for
+/// every process.
+type TailKey = (Uuid, usize);
+
+/// The most recent normalized entries of a workspace, across its execution
+/// processes, so "last N lines" doesn't need to replay a whole `MsgStore`.
+#[derive(Debug)]
+pub struct NormalizedTail {
+    capacity: usize,
+    buffer: Mutex<TailBuffer>,
+}
+
+#[derive(Debug, Default)]
+struct TailBuffer {
+    entries: VecDeque<(TailKey, NormalizedEntry)>,
+    // Position of every retained key, counted from the first entry ever pushed,
+    // so updates find their slot without a scan
+    positions: HashMap<TailKey, u64>,
+    evicted: u64,
+}
+
+impl Default for NormalizedTail {
+    fn default() -> Self {
+        Self::new(DEFAULT_TAIL_CAPACITY)
+    }
+}
+
+impl NormalizedTail {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            buffer: Mutex::new(TailBuffer {
+                entries: VecDeque::with_capacity(capacity),
+                ..Default::default()
+            }),
+        }
+    }
+
+    /// A tail holding the number of entries set in `var`, or
+    /// `default_capacity` when it is unset or invalid.
+    pub fn from_env(var: &str, default_capacity: usize) -> Self {
+        let capacity = match std::env::var(var) {
+            Ok(raw) => raw.trim().parse().unwrap_or_else(|_| {
+                tracing::warn!("ignoring invalid {var}={raw}; using {default_capacity}");
+                default_capacity
+            }),
+            Err(_) => default_capacity,
+        };
+        Self::new(capacity)
+    }
+
+    /// Record an entry, replacing it in place if it's an update to one still
+    /// in the buffer and evicting the oldest entry once full.
+    pub fn push(&self, process_id: Uuid, index: usize, entry: NormalizedEntry) {
+        let key = (process_id, index);
+        let mut buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(&position) = buffer.positions.get(&key) {
+            let slot = (position - buffer.evicted) as usize;
+            buffer.entries[slot].1 = entry;
+            return;
+        }
+        if buffer.entries.len() == self.capacity
+            && let Some((oldest, _)) = buffer.entries.pop_front()
+        {
+            buffer.positions.remove(&oldest);
+            buffer.evicted += 1;
+        }
+        let position = buffer.evicted + buffer.entries.len() as u64;
+        buffer.entries.push_back((key, entry));
+        buffer.positions.insert(key, position);
+    }
+
+    /// The retained entries, oldest first.
+    pub fn entries(&self) -> Vec<NormalizedEntry> {
+        let buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+        buffer
+            .entries
+            .iter()
+            .map(|(_, entry)| entry.clone())
+            .collect()
+    }
+
+    /// The retained entries of `process_id`, in entry order.
+    pub fn process_entries(&self, process_id: Uuid) -> Vec<NormalizedEntry> {
+        let buffer = self.buffer.lock().unwrap_or_else(|e| e.into_inner());
+        let mut entries: Vec<_> = buffer
+            .entries
+            .iter()
+            .filter(|((id, _), _)| *id == process_id)
+            .collect();
+        entries.sort_by_key(|((_, index), _)| *index);
+        entries
+            .into_iter()
+            .map(|(_, entry)| entry.clone())
+            .collect()
+    }
+}
+
+/// Feed the normalized entries pushed to `msg_store` by `process_id` into
+/// `tail` until the store finishes.
+pub fn spawn_tail_writer(
+    msg_store: Arc<MsgStore>,
+    process_id: Uuid,
+    tail: Arc<NormalizedTail>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut messages = msg_store.history_plus_stream();
+        while let Some(Ok(msg)) = messages.next().await {
+            match msg {
+                LogMsg::JsonPatch(patch) => {
+                    if let Some((index, entry)) = extract_normalized_entry_from_patch(&patch) {
+                        tail.push(process_id, index, entry);
+                    }
+                }
+                LogMsg::Finished => break,
+                _ => {}
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logs::NormalizedEntryType;
+
+    fn message(content: &str) -> NormalizedEntry {
+        NormalizedEntry {
+            timestamp: None,
+            entry_type: NormalizedEntryType::AssistantMessage,
+            content: content.to_string(),
+            metadata: None,
+        }
+    }
+
+    fn contents(tail: &NormalizedTail) -> Vec<String> {
+        tail.entries().into_iter().map(|e| e.content).collect()
+    }
+
+    #[test]
+    fn keeps_only_the_last_entries_in_order() {
+        let tail = NormalizedTail::new(3);
+        let process_id = Uuid::new_v4();
+        for index in 0..5 {
+            tail.push(process_id, index, message(&format!("line {index}")));
+        }
+        assert_eq!(contents(&tail), ["line 2", "line 3", "line 4"]);
+
+        // An update to a retained entry replaces it rather than evicting another
+        tail.push(process_id, 3, message("line 3 (done)"));
+        assert_eq!(contents(&tail), ["line 2", "line 3 (done)", "line 4"]);
+
+        // A new process starts its indices again without clobbering the old one
+        tail.push(Uuid::new_v4(), 4, message("next process"));
+        assert_eq!(contents(&tail), ["line 3 (done)", "line 4", "next process"]);
+    }
+
+    #[test]
+    fn updates_after_many_evictions_land_in_place() {
+        let tail = NormalizedTail::new(2);
+        let first = Uuid::new_v4();
+        let second = Uuid::new_v4();
+        for index in 0..100 {
+            tail.push(first, index, message(&format!("line {index}")));
+        }
+        tail.push(second, 0, message("other"));
+        tail.push(first, 99, message("line 99 (done)"));
+        assert_eq!(contents(&tail), ["line 99 (done)", "other"]);
+
+        // An update to an evicted entry comes back as the newest one
+        tail.push(first, 98, message("line 98 (late)"));
+        assert_eq!(contents(&tail), ["other", "line 98 (late)"]);
+
+        let first_only: Vec<String> = tail
+            .process_entries(first)
+            .into_iter()
+            .map(|e| e.content)
+            .collect();
+        assert_eq!(first_only, ["line 98 (late)"]);
+    }
+}
@@ -1,7 +1,6 @@
 use std::{
     collections::HashMap,
     fs,
-    str::FromStr,
     sync::{LazyLock, RwLock},
 };
 
@@ -75,10 +74,8 @@ where
     D: Deserializer<'de>,
 {
     let raw = String::deserialize(de)?;
-    // kebab-case -> SCREAMING_SNAKE_CASE
-    let norm = raw.replace('-', "_").to_ascii_uppercase();
-    BaseCodingAgent::from_str(&norm)
-        .map_err(|_| D::Error::custom(format!("unknown executor '{raw}' (normalized to '{norm}')")))
+    BaseCodingAgent::parse_flexible(&raw)
+        .ok_or_else(|| D::Error::custom(format!("unknown executor '{raw}'")))
 }
 
 impl ExecutorProfileId {
@@ -105,6 +102,24 @@ impl ExecutorProfileId {
             None => self.executor.clone().to_string(),
         }
     }
+
+    /// Check that `configs` can run this profile, falling back to the default
+    /// variant the same way `ExecutorConfigs::get_coding_agent_or_default` does.
+    pub fn resolve(&self, configs: &ExecutorConfigs) -> Result<(), String> {
+        let Some(executor) = configs.executors.get(&self.executor) else {
+            return Err(format!("Executor {} is not configured", self.executor));
+        };
+        let variant = self
+            .variant
+            .as_deref()
+            .and_then(|variant| executor.get_variant(variant));
+        if variant.is_none() && executor.get_default().is_none() {
+            return Err(format!(
+                "Executor profile {self} has no configuration to run"
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl std::fmt::Display for ExecutorProfileId {
@@ -482,3 +497,25 @@ pub fn to_default_variant(id: &ExecutorProfileId) -> ExecutorProfileId {
         variant: None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_rejects_unconfigured_executor() {
+        let mut configs = ExecutorConfigs::from_defaults();
+        let amp = ExecutorProfileId::new(BaseCodingAgent::Amp);
+        assert!(amp.resolve(&configs).is_ok());
+        // Unknown variants fall back to the default
+        assert!(
+            ExecutorProfileId::with_variant(BaseCodingAgent::Amp, "MISSING".to_string())
+                .resolve(&configs)
+                .is_ok()
+        );
+
+        configs.executors.remove(&BaseCodingAgent::Amp);
+        let err = amp.resolve(&configs).unwrap_err();
+        assert_eq!(err, "Executor AMP is not configured");
+    }
+}
@@ -1,6 +1,6 @@
 //! Cross-platform stdout duplication utility for child processes
 //!
-//! Provides a single function to duplicate a child process's stdout stream.
+//! Provides functions to duplicate a child process's stdout (and stderr) streams.
 //! Supports Unix and Windows platforms.
 
 #[cfg(unix)]
@@ -76,6 +76,48 @@ pub fn duplicate_stdout(
     Ok(Box::pin(UnboundedReceiverStream::new(dup_reader)))
 }
 
+/// Duplicate stderr from AsyncGroupChild, like [`duplicate_stdout`].
+pub fn duplicate_stderr(
+    child: &mut AsyncGroupChild,
+) -> Result<BoxStream<'static, std::io::Result<String>>, ExecutorError> {
+    let original_stderr = child.inner().stderr.take().ok_or_else(|| {
+        ExecutorError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Child process has no stderr",
+        ))
+    })?;
+
+    let (pipe_reader, pipe_writer) = os_pipe::pipe().map_err(|e| {
+        ExecutorError::Io(std::io::Error::other(format!("Failed to create pipe: {e}")))
+    })?;
+    child.inner().stderr = Some(wrap_fd_as_child_stderr(pipe_reader)?);
+
+    let mut fd_writer = wrap_fd_as_tokio_writer(pipe_writer)?;
+    let (dup_writer, dup_reader) =
+        tokio::sync::mpsc::unbounded_channel::<std::io::Result<String>>();
+
+    tokio::spawn(async move {
+        let mut stderr_stream = ReaderStream::new(original_stderr);
+
+        while let Some(res) = stderr_stream.next().await {
+            match res {
+                Ok(data) => {
+                    let _ = fd_writer.write_all(&data).await;
+
+                    let string_chunk = String::from_utf8_lossy(&data).into_owned();
+                    let _ = dup_writer.send(Ok(string_chunk));
+                }
+                Err(err) => {
+                    tracing::error!("Error reading from child stderr: {}", err);
+                    let _ = dup_writer.send(Err(err));
+                }
+            }
+        }
+    });
+
+    Ok(Box::pin(UnboundedReceiverStream::new(dup_reader)))
+}
+
 /// Handle to append additional lines into the child's stdout stream.
 #[derive(Clone)]
 pub struct StdoutAppender {
@@ -262,6 +304,27 @@ fn wrap_fd_as_child_stdout(
     }
 }
 
+/// Convert os_pipe::PipeReader to tokio::process::ChildStderr
+fn wrap_fd_as_child_stderr(
+    pipe_reader: os_pipe::PipeReader,
+) -> Result<tokio::process::ChildStderr, ExecutorError> {
+    #[cfg(unix)]
+    {
+        let raw_fd = pipe_reader.into_raw_fd();
+        let owned_fd = unsafe { OwnedFd::from_raw_fd(raw_fd) };
+        let std_stderr = std::process::ChildStderr::from(owned_fd);
+        tokio::process::ChildStderr::from_std(std_stderr).map_err(ExecutorError::Io)
+    }
+
+    #[cfg(windows)]
+    {
+        let raw_handle = pipe_reader.into_raw_handle();
+        let owned_handle = unsafe { OwnedHandle::from_raw_handle(raw_handle) };
+        let std_stderr = std::process::ChildStderr::from(owned_handle);
+        tokio::process::ChildStderr::from_std(std_stderr).map_err(ExecutorError::Io)
+    }
+}
+
 /// Convert os_pipe::PipeWriter to a tokio file for async writing
 fn wrap_fd_as_tokio_writer(
     pipe_writer: os_pipe::PipeWriter,
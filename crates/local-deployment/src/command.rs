@@ -5,10 +5,22 @@ use nix::{
     unistd::{Pid, getpgid},
 };
 use services::services::container::ContainerError;
-#[cfg(unix)]
 use tokio::time::Duration;
 
-pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), ContainerError> {
+/// How long each signal gets before escalating to the next one.
+const KILL_GRACE: Duration = Duration::from_secs(2);
+
+/// Stop the child and its whole process group, escalating SIGINT, SIGTERM,
+/// SIGKILL. Returns whether the child actually exited; a child still around
+/// after the final kill is logged rather than waited on forever.
+pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<bool, ContainerError> {
+    kill_process_group_with_grace(child, KILL_GRACE).await
+}
+
+async fn kill_process_group_with_grace(
+    child: &mut AsyncGroupChild,
+    grace: Duration,
+) -> Result<bool, ContainerError> {
     // hit the whole process group, not just the leader
     #[cfg(unix)]
     {
@@ -26,8 +38,8 @@ pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), Conta
                         e
                     );
                 }
-                tracing::info!("Waiting 2s for process group {} to exit", pgid);
-                tokio::time::sleep(Duration::from_secs(2)).await;
+                tracing::info!("Waiting {:?} for process group {} to exit", grace, pgid);
+                tokio::time::sleep(grace).await;
                 if child
                     .inner()
                     .try_wait()
@@ -42,6 +54,41 @@ pub async fn kill_process_group(child: &mut AsyncGroupChild) -> Result<(), Conta
     }
 
     let _ = child.kill().await;
-    let _ = child.wait().await;
-    Ok(())
+    match tokio::time::timeout(grace, child.wait()).await {
+        Ok(_) => Ok(true),
+        Err(_) => {
+            tracing::warn!(
+                "Process {:?} did not exit within {:?} of being killed",
+                child.id(),
+                grace
+            );
+            Ok(false)
+        }
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::os::unix::process::ExitStatusExt;
+
+    use command_group::AsyncCommandGroup;
+    use tokio::process::Command;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn process_ignoring_sigterm_is_killed_and_reported() {
+        let mut child = Command::new("sh")
+            .args(["-c", "trap '' INT TERM; sleep 30"])
+            .group_spawn()
+            .unwrap();
+
+        let exited = kill_process_group_with_grace(&mut child, Duration::from_millis(200))
+            .await
+            .unwrap();
+        assert!(exited);
+
+        let status = child.wait().await.unwrap();
+        assert_eq!(status.signal(), Some(Signal::SIGKILL as i32));
+    }
 }
@@ -35,7 +35,10 @@ use executors::{
     approvals::{ExecutorApprovalService, NoopExecutorApprovalService},
     env::{ExecutionEnv, RepoContext},
     executors::{BaseCodingAgent, ExecutorExitResult, ExecutorExitSignal, InterruptSender},
-    logs::{NormalizedEntryType, utils::patch::extract_normalized_entry_from_patch},
+    logs::{
+        NormalizedEntryType, tail::NormalizedTail,
+        utils::patch::extract_normalized_entry_from_patch,
+    },
 };
 use futures::{FutureExt, TryStreamExt, stream::select};
 use serde_json::json;
@@ -68,6 +71,7 @@ pub struct LocalContainerService {
     child_store: Arc<RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>>,
     interrupt_senders: Arc<RwLock<HashMap<Uuid, InterruptSender>>>,
     msg_stores: Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>,
+    log_tails: Arc<RwLock<HashMap<Uuid, Arc<NormalizedTail>>>>,
     config: Arc<RwLock<Config>>,
     git: GitService,
     image_service: ImageService,
@@ -91,6 +95,7 @@ impl LocalContainerService {
     ) -> Self {
         let child_store = Arc::new(RwLock::new(HashMap::new()));
         let interrupt_senders = Arc::new(RwLock::new(HashMap::new()));
+        let log_tails = Arc::new(RwLock::new(HashMap::new()));
         let notification_service = NotificationService::new(config.clone());
 
         let container = LocalContainerService {
@@ -98,6 +103,7 @@ impl LocalContainerService {
             child_store,
             interrupt_senders,
             msg_stores,
+            log_tails,
             config,
             git,
             image_service,
@@ -189,8 +195,48 @@ impl LocalContainerService {
         Ok(())
     }
 
+    /// Ids of the stored children that haven't exited. A child stays in the
+    /// store until its exit monitor has finalized it, so ask the process itself.
+    async fn running_children(
+        child_store: &RwLock<HashMap<Uuid, Arc<RwLock<AsyncGroupChild>>>>,
+    ) -> Vec<Uuid> {
+        let children: Vec<_> = child_store
+            .read()
+            .await
+            .iter()
+            .map(|(id, child)| (*id, child.clone()))
+            .collect();
+        let mut running = Vec::new();
+        for (id, child) in children {
+            if matches!(child.write().await.try_wait(), Ok(None)) {
+                running.push(id);
+            }
+        }
+        running
+    }
+
+    /// Drop the log tails of workspaces that were deleted or whose worktree was
+    /// cleaned up. Workspaces that can't be looked up right now keep theirs.
+    pub async fn prune_log_tails(
+        db: &DBService,
+        log_tails: &RwLock<HashMap<Uuid, Arc<NormalizedTail>>>,
+    ) {
+        let workspace_ids: Vec<Uuid> = log_tails.read().await.keys().copied().collect();
+        for workspace_id in workspace_ids {
+            let keep = match Workspace::find_by_id(&db.pool, workspace_id).await {
+                Ok(Some(workspace)) => workspace.container_ref.is_some(),
+                Ok(None) => false,
+                Err(_) => true,
+            };
+            if !keep {
+                log_tails.write().await.remove(&workspace_id);
+            }
+        }
+    }
+
     pub fn spawn_workspace_cleanup(&self) {
         let db = self.db.clone();
+        let log_tails = self.log_tails.clone();
         let cleanup_expired = Self::cleanup_expired_workspaces;
         tokio::spawn(async move {
             WorkspaceManager::cleanup_orphan_workspaces(&db.pool).await;
@@ -203,6 +249,7 @@ impl LocalContainerService {
                 cleanup_expired(&db).await.unwrap_or_else(|e| {
                     tracing::error!("Failed to clean up expired workspaces: {}", e)
                 });
+                Self::prune_log_tails(&db, &log_tails).await;
             }
         });
     }
@@ -875,6 +922,7 @@ impl LocalContainerService {
                 prompt: queued_data.message.clone(),
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
+                executor_config: None,
             })
         };
 
@@ -909,6 +957,14 @@ impl ContainerService for LocalContainerService {
         &self.msg_stores
     }
 
+    fn log_tails(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<NormalizedTail>>>> {
+        &self.log_tails
+    }
+
+    async fn running_execution_ids(&self) -> Vec<Uuid> {
+        Self::running_children(&self.child_store).await
+    }
+
     fn db(&self) -> &DBService {
         &self.db
     }
@@ -992,6 +1048,7 @@ impl ContainerService for LocalContainerService {
 
     async fn delete(&self, workspace: &Workspace) -> Result<(), ContainerError> {
         self.try_stop(workspace, true).await;
+        self.log_tails.write().await.remove(&workspace.id);
         Self::cleanup_workspace(&self.db, workspace).await;
         Ok(())
     }
@@ -1102,7 +1159,10 @@ impl ContainerService for LocalContainerService {
         let repo_names: Vec<String> = repos.iter().map(|r| r.name.clone()).collect();
         let repo_context = RepoContext::new(current_dir.clone(), repo_names);
 
-        let commit_reminder = self.config.read().await.commit_reminder;
+        let (commit_reminder, user_env) = {
+            let config = self.config.read().await;
+            (config.commit_reminder, config.executor_env.clone())
+        };
         let mut env = ExecutionEnv::new(repo_context, commit_reminder);
 
         // Load task and project context for environment variables
@@ -1122,6 +1182,7 @@ impl ContainerService for LocalContainerService {
         env.insert("VK_TASK_ID", task.id.to_string());
         env.insert("VK_WORKSPACE_ID", workspace.id.to_string());
         env.insert("VK_WORKSPACE_BRANCH", &workspace.branch);
+        env.apply_user_env(&user_env);
 
         // Create the child and stream, add to execution tracker with timeout
         let mut spawned = tokio::time::timeout(
@@ -1413,3 +1474,145 @@ fn success_exit_status() -> std::process::ExitStatus {
         ExitStatusExt::from_raw(0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use command_group::AsyncCommandGroup;
+    use db::models::{
+        execution_process::CreateExecutionProcess,
+        project::{CreateProject, Project},
+        session::CreateSession,
+        task::CreateTask,
+        workspace::CreateWorkspace,
+    };
+    use executors::profile::ExecutorProfileId;
+    use services::services::container::ActiveSession;
+
+    use super::*;
+
+    async fn task(db: &DBService) -> Uuid {
+        let project_id = Uuid::new_v4();
+        Project::create(
+            &db.pool,
+            &CreateProject {
+                name: "project".to_string(),
+                repositories: Vec::new(),
+            },
+            project_id,
+        )
+        .await
+        .unwrap();
+        let task_id = Uuid::new_v4();
+        Task::create(
+            &db.pool,
+            &CreateTask::from_title_description(project_id, "task".to_string(), None),
+            task_id,
+        )
+        .await
+        .unwrap();
+        task_id
+    }
+
+    async fn workspace(db: &DBService, task_id: Uuid, container_ref: Option<&str>) -> Uuid {
+        let id = Uuid::new_v4();
+        Workspace::create(
+            &db.pool,
+            &CreateWorkspace {
+                branch: format!("vk/{id}"),
+                agent_working_dir: None,
+            },
+            id,
+            task_id,
+        )
+        .await
+        .unwrap();
+        if let Some(container_ref) = container_ref {
+            Workspace::update_container_ref(&db.pool, id, container_ref)
+                .await
+                .unwrap();
+        }
+        id
+    }
+
+    #[tokio::test]
+    async fn tails_of_removed_or_cleaned_up_workspaces_are_pruned() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let task_id = task(&db).await;
+
+        let live = workspace(&db, task_id, Some("/tmp/vk-worktree")).await;
+        let cleaned_up = workspace(&db, task_id, None).await;
+        let deleted = Uuid::new_v4();
+        let log_tails = RwLock::new(HashMap::from(
+            [live, cleaned_up, deleted].map(|id| (id, Arc::new(NormalizedTail::default()))),
+        ));
+
+        LocalContainerService::prune_log_tails(&db, &log_tails).await;
+
+        let kept: Vec<Uuid> = log_tails.read().await.keys().copied().collect();
+        assert_eq!(kept, vec![live]);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn sessions_are_listed_until_their_child_exits() {
+        let db = DBService::new_in_memory().await.unwrap();
+        let workspace_id = workspace(&db, task(&db).await, None).await;
+        let session = Session::create(
+            &db.pool,
+            &CreateSession { executor: None },
+            Uuid::new_v4(),
+            workspace_id,
+        )
+        .await
+        .unwrap();
+        let action = ExecutorAction::new(
+            ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+                prompt: "hello".to_string(),
+                executor_profile_id: ExecutorProfileId::new(BaseCodingAgent::ClaudeCode),
+                working_dir: None,
+                executor_config: None,
+            }),
+            None,
+        );
+        let process = ExecutionProcess::create(
+            &db.pool,
+            &CreateExecutionProcess {
+                session_id: session.id,
+                executor_action: action,
+                run_reason: ExecutionProcessRunReason::CodingAgent,
+            },
+            Uuid::new_v4(),
+            &[],
+        )
+        .await
+        .unwrap();
+
+        let child = tokio::process::Command::new("sleep")
+            .arg("30")
+            .group_spawn()
+            .unwrap();
+        let child_store = RwLock::new(HashMap::from([(process.id, Arc::new(RwLock::new(child)))]));
+
+        let running = LocalContainerService::running_children(&child_store).await;
+        let sessions = ActiveSession::for_executions(&db.pool, running)
+            .await
+            .unwrap();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].workspace_id, workspace_id);
+        assert_eq!(sessions[0].executor, BaseCodingAgent::ClaudeCode);
+
+        // Exited but not yet finalized, so still in the store
+        {
+            let store = child_store.read().await;
+            let mut child = store[&process.id].write().await;
+            command::kill_process_group(&mut child).await.unwrap();
+            child.wait().await.unwrap();
+        }
+        let running = LocalContainerService::running_children(&child_store).await;
+        assert!(running.is_empty());
+        let sessions = ActiveSession::for_executions(&db.pool, running)
+            .await
+            .unwrap();
+        assert!(sessions.is_empty());
+    }
+}
@@ -20,7 +20,7 @@ use services::services::{
     queued_message::QueuedMessageService,
     remote_client::{RemoteClient, RemoteClientError},
     repo::RepoService,
-    scheduler::SchedulerService,
+    scheduler::{SchedulerControl, SchedulerService},
     worktree_manager::WorktreeManager,
 };
 use tokio::sync::RwLock;
@@ -53,6 +53,7 @@ pub struct LocalDeployment {
     file_search_cache: Arc<FileSearchCache>,
     approvals: Approvals,
     queued_message_service: QueuedMessageService,
+    scheduler_control: SchedulerControl,
     remote_client: Result<RemoteClient, RemoteClientNotConfigured>,
     auth_context: AuthContext,
     oauth_handoffs: Arc<RwLock<HashMap<Uuid, PendingHandoff>>>,
@@ -149,6 +150,16 @@ impl Deployment for LocalDeployment {
         let remote_client = match api_base {
             Some(url) => match RemoteClient::new(&url, auth_context.clone()) {
                 Ok(client) => {
+                    let client = match std::env::var("VK_SHARED_API_HEADERS") {
+                        Ok(raw) => match RemoteClient::parse_header_list(&raw) {
+                            Ok(headers) => client.with_headers(headers),
+                            Err(e) => {
+                                tracing::warn!(?e, "ignoring invalid VK_SHARED_API_HEADERS");
+                                client
+                            }
+                        },
+                        Err(_) => client,
+                    };
                     tracing::info!("Remote client initialized with URL: {}", url);
                     Ok(client)
                 }
@@ -204,6 +215,7 @@ impl Deployment for LocalDeployment {
             file_search_cache,
             approvals,
             queued_message_service,
+            scheduler_control: SchedulerControl::default(),
             remote_client,
             auth_context,
             oauth_handoffs,
@@ -273,8 +285,16 @@ impl Deployment for LocalDeployment {
         &self.auth_context
     }
 
+    fn scheduler_control(&self) -> &SchedulerControl {
+        &self.scheduler_control
+    }
+
     fn spawn_scheduler_service(&self) -> tokio::task::JoinHandle<()> {
-        SchedulerService::spawn(self.db.clone(), self.container.clone())
+        SchedulerService::spawn(
+            self.db.clone(),
+            self.container.clone(),
+            self.scheduler_control.clone(),
+        )
     }
 }
 
@@ -0,0 +1,3 @@
+-- Add archived column so old projects can be hidden from project listings
+ALTER TABLE projects
+ADD COLUMN archived BOOLEAN NOT NULL DEFAULT false;
@@ -102,9 +102,11 @@ impl IssueRepository {
         Ok(record)
     }
 
+    /// Lists issues in a project, optionally restricted to a single status column.
     pub async fn list_by_project(
         pool: &PgPool,
         project_id: Uuid,
+        status_id: Option<Uuid>,
     ) -> Result<Vec<Issue>, IssueError> {
         let records = sqlx::query_as!(
             Issue,
@@ -128,8 +130,10 @@ impl IssueRepository {
                 updated_at          AS "updated_at!: DateTime<Utc>"
             FROM issues
             WHERE project_id = $1
+              AND ($2::uuid IS NULL OR status_id = $2)
             "#,
-            project_id
+            project_id,
+            status_id
         )
         .fetch_all(pool)
         .await?;
@@ -423,3 +427,80 @@ impl IssueRepository {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::projects::{INITIAL_PROJECT_COLOR, ProjectRepository};
+
+    async fn issue(pool: &PgPool, project_id: Uuid, status_id: Uuid, title: &str) -> Uuid {
+        IssueRepository::create(
+            pool,
+            None,
+            project_id,
+            status_id,
+            title.to_string(),
+            None,
+            IssuePriority::Medium,
+            None,
+            None,
+            None,
+            0.0,
+            None,
+            Value::Object(Default::default()),
+        )
+        .await
+        .unwrap()
+        .data
+        .id
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres DATABASE_URL"]
+    async fn test_list_by_project_filters_by_status(pool: PgPool) {
+        let organization_id: Uuid = sqlx::query_scalar(
+            "INSERT INTO organizations (name, slug) VALUES ($1, $2) RETURNING id",
+        )
+        .bind("Test Org")
+        .bind(format!("test-org-{}", Uuid::new_v4()))
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        let project = ProjectRepository::create_with_defaults(
+            &pool,
+            None,
+            organization_id,
+            "Project".to_string(),
+            INITIAL_PROJECT_COLOR.to_string(),
+        )
+        .await
+        .unwrap()
+        .data;
+        let statuses = ProjectStatusRepository::list_by_project(&pool, project.id)
+            .await
+            .unwrap();
+        let (first, second) = (statuses[0].id, statuses[1].id);
+
+        let first_issue = issue(&pool, project.id, first, "First").await;
+        let second_issue = issue(&pool, project.id, second, "Second").await;
+
+        let ids = |issues: Vec<Issue>| issues.into_iter().map(|i| i.id).collect::<Vec<_>>();
+
+        let listed = IssueRepository::list_by_project(&pool, project.id, Some(first))
+            .await
+            .unwrap();
+        assert_eq!(ids(listed), vec![first_issue]);
+
+        let listed = IssueRepository::list_by_project(&pool, project.id, Some(second))
+            .await
+            .unwrap();
+        assert_eq!(ids(listed), vec![second_issue]);
+
+        let listed = ids(IssueRepository::list_by_project(&pool, project.id, None)
+            .await
+            .unwrap());
+        assert_eq!(listed.len(), 2);
+        assert!(listed.contains(&first_issue));
+        assert!(listed.contains(&second_issue));
+    }
+}
@@ -216,6 +216,7 @@ impl ProjectStatusRepository {
                 created_at      AS "created_at!: DateTime<Utc>"
             FROM project_statuses
             WHERE project_id = $1
+            ORDER BY sort_order, created_at
             "#,
             project_id
         )
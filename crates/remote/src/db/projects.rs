@@ -24,6 +24,7 @@ pub struct Project {
     pub color: String,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub archived: bool,
 }
 
 #[derive(Debug, Error)]
@@ -54,7 +55,8 @@ impl ProjectRepository {
                 name             AS "name!",
                 color            AS "color!",
                 created_at       AS "created_at!: DateTime<Utc>",
-                updated_at       AS "updated_at!: DateTime<Utc>"
+                updated_at       AS "updated_at!: DateTime<Utc>",
+                archived         AS "archived!: bool"
             FROM projects
             WHERE id = $1
             "#,
@@ -92,7 +94,8 @@ impl ProjectRepository {
                 name             AS "name!",
                 color            AS "color!",
                 created_at       AS "created_at!: DateTime<Utc>",
-                updated_at       AS "updated_at!: DateTime<Utc>"
+                updated_at       AS "updated_at!: DateTime<Utc>",
+                archived         AS "archived!: bool"
             "#,
             id,
             organization_id,
@@ -107,9 +110,12 @@ impl ProjectRepository {
         Ok(record)
     }
 
+    /// Projects of an organization, newest first. Archived projects are left
+    /// out unless `include_archived` is set.
     pub async fn list_by_organization<'e, E>(
         executor: E,
         organization_id: Uuid,
+        include_archived: bool,
     ) -> Result<Vec<Project>, ProjectError>
     where
         E: Executor<'e, Database = Postgres>,
@@ -123,12 +129,15 @@ impl ProjectRepository {
                 name             AS "name!",
                 color            AS "color!",
                 created_at       AS "created_at!: DateTime<Utc>",
-                updated_at       AS "updated_at!: DateTime<Utc>"
+                updated_at       AS "updated_at!: DateTime<Utc>",
+                archived         AS "archived!: bool"
             FROM projects
             WHERE organization_id = $1
+              AND ($2 OR NOT archived)
             ORDER BY created_at DESC
             "#,
-            organization_id
+            organization_id,
+            include_archived
         )
         .fetch_all(executor)
         .await?;
@@ -161,7 +170,8 @@ impl ProjectRepository {
                 name             AS "name!",
                 color            AS "color!",
                 created_at       AS "created_at!: DateTime<Utc>",
-                updated_at       AS "updated_at!: DateTime<Utc>"
+                updated_at       AS "updated_at!: DateTime<Utc>",
+                archived         AS "archived!: bool"
             "#,
             name,
             color,
@@ -176,6 +186,43 @@ impl ProjectRepository {
         Ok(MutationResponse { data, txid })
     }
 
+    /// Archive or unarchive a project. Archived projects keep their issues but
+    /// are hidden from project listings by default.
+    pub async fn set_archived(
+        pool: &PgPool,
+        id: Uuid,
+        archived: bool,
+    ) -> Result<MutationResponse<Project>, ProjectError> {
+        let mut tx = pool.begin().await?;
+        let data = sqlx::query_as!(
+            Project,
+            r#"
+            UPDATE projects
+            SET
+                archived = $1,
+                updated_at = $2
+            WHERE id = $3
+            RETURNING
+                id               AS "id!: Uuid",
+                organization_id  AS "organization_id!: Uuid",
+                name             AS "name!",
+                color            AS "color!",
+                created_at       AS "created_at!: DateTime<Utc>",
+                updated_at       AS "updated_at!: DateTime<Utc>",
+                archived         AS "archived!: bool"
+            "#,
+            archived,
+            Utc::now(),
+            id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let txid = get_txid(&mut *tx).await?;
+        tx.commit().await?;
+        Ok(MutationResponse { data, txid })
+    }
+
     pub async fn delete(pool: &PgPool, id: Uuid) -> Result<DeleteResponse, ProjectError> {
         let mut tx = pool.begin().await?;
         sqlx::query!("DELETE FROM projects WHERE id = $1", id)
@@ -260,3 +307,59 @@ impl ProjectRepository {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn organization(pool: &PgPool) -> Uuid {
+        sqlx::query_scalar("INSERT INTO organizations (name, slug) VALUES ($1, $2) RETURNING id")
+            .bind("Test Org")
+            .bind(format!("test-org-{}", Uuid::new_v4()))
+            .fetch_one(pool)
+            .await
+            .unwrap()
+    }
+
+    #[sqlx::test(migrations = "./migrations")]
+    #[ignore = "needs a Postgres DATABASE_URL"]
+    async fn test_list_by_organization_hides_archived_projects(pool: PgPool) {
+        let organization_id = organization(&pool).await;
+        let active = ProjectRepository::create(
+            &pool,
+            None,
+            organization_id,
+            "Active".to_string(),
+            INITIAL_PROJECT_COLOR.to_string(),
+        )
+        .await
+        .unwrap();
+        let archived = ProjectRepository::create(
+            &pool,
+            None,
+            organization_id,
+            "Archived".to_string(),
+            INITIAL_PROJECT_COLOR.to_string(),
+        )
+        .await
+        .unwrap();
+        ProjectRepository::set_archived(&pool, archived.id, true)
+            .await
+            .unwrap();
+
+        let ids = |projects: Vec<Project>| projects.into_iter().map(|p| p.id).collect::<Vec<_>>();
+
+        let listed = ProjectRepository::list_by_organization(&pool, organization_id, false)
+            .await
+            .unwrap();
+        assert_eq!(ids(listed), vec![active.id]);
+
+        let listed = ProjectRepository::list_by_organization(&pool, organization_id, true)
+            .await
+            .unwrap();
+        let listed = ids(listed);
+        assert_eq!(listed.len(), 2);
+        assert!(listed.contains(&active.id));
+        assert!(listed.contains(&archived.id));
+    }
+}
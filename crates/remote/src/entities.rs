@@ -34,13 +34,14 @@ use crate::{
 // Organization-scoped entities
 // =============================================================================
 
-// Project: simple case - same scope for mutations and streaming
+// Project: same scope for mutations and streaming; archived projects are not
+// streamed, matching the REST listing default (use `include_archived` there)
 crate::define_entity!(
     Project,
     table: "projects",
     mutation_scope: Organization,
     shape: {
-        where_clause: r#""organization_id" = $1"#,
+        where_clause: r#""organization_id" = $1 AND NOT "archived""#,
         params: ["organization_id"],
         url: "/shape/projects",
     },
@@ -3,6 +3,7 @@ use axum::{
     extract::{Extension, Path, Query, State},
     http::StatusCode,
 };
+use serde::Deserialize;
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -19,19 +20,28 @@ use crate::{
 // Generate router that references handlers below
 define_mutation_router!(Issue, table: "issues");
 
+/// Optional filters accepted alongside [`ListIssuesQuery`].
+#[derive(Debug, Default, Deserialize)]
+pub struct ListIssuesFilter {
+    /// Only return issues in this status column. Defaults to all statuses.
+    #[serde(default)]
+    pub status_id: Option<Uuid>,
+}
+
 #[instrument(
     name = "issues.list_issues",
     skip(state, ctx),
-    fields(project_id = %query.project_id, user_id = %ctx.user.id)
+    fields(project_id = %query.project_id, status_id = ?filter.status_id, user_id = %ctx.user.id)
 )]
 async fn list_issues(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ListIssuesQuery>,
+    Query(filter): Query<ListIssuesFilter>,
 ) -> Result<Json<ListIssuesResponse>, ErrorResponse> {
     ensure_project_access(state.pool(), ctx.user.id, query.project_id).await?;
 
-    let issues = IssueRepository::list_by_project(state.pool(), query.project_id)
+    let issues = IssueRepository::list_by_project(state.pool(), query.project_id, filter.status_id)
         .await
         .map_err(|error| {
             tracing::error!(?error, project_id = %query.project_id, "failed to list issues");
@@ -173,3 +183,36 @@ async fn delete_issue(
 
     Ok(Json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Uri;
+
+    use super::*;
+
+    #[test]
+    fn test_status_filter_is_read_alongside_project_id() {
+        let project_id = Uuid::new_v4();
+        let status_id = Uuid::new_v4();
+        let uri: Uri = format!("/issues?project_id={project_id}&status_id={status_id}")
+            .parse()
+            .unwrap();
+
+        let Query(query) = Query::<ListIssuesQuery>::try_from_uri(&uri).unwrap();
+        let Query(filter) = Query::<ListIssuesFilter>::try_from_uri(&uri).unwrap();
+
+        assert_eq!(query.project_id, project_id);
+        assert_eq!(filter.status_id, Some(status_id));
+    }
+
+    #[test]
+    fn test_status_filter_defaults_to_all_statuses() {
+        let uri: Uri = format!("/issues?project_id={}", Uuid::new_v4())
+            .parse()
+            .unwrap();
+
+        let Query(filter) = Query::<ListIssuesFilter>::try_from_uri(&uri).unwrap();
+
+        assert_eq!(filter.status_id, None);
+    }
+}
@@ -1,8 +1,10 @@
 use axum::{
-    Json,
+    Json, Router,
     extract::{Extension, Path, Query, State},
     http::StatusCode,
+    routing::post,
 };
+use serde::Deserialize;
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -24,24 +26,43 @@ use crate::{
 // Generate router that references handlers below
 define_mutation_router!(Project, table: "projects");
 
+/// Archiving, which the generated CRUD routes don't cover.
+pub fn archive_router() -> Router<AppState> {
+    Router::new().route(
+        "/projects/{project_id}/archive",
+        post(archive_project).delete(unarchive_project),
+    )
+}
+
+/// Optional filters accepted alongside [`ListProjectsQuery`].
+#[derive(Debug, Default, Deserialize)]
+pub struct ListProjectsFilter {
+    /// Also return archived projects. Defaults to false.
+    #[serde(default)]
+    pub include_archived: Option<bool>,
+}
+
 #[instrument(
     name = "projects.list_projects",
     skip(state, ctx),
-    fields(organization_id = %query.organization_id, user_id = %ctx.user.id)
+    fields(organization_id = %query.organization_id, include_archived = ?filter.include_archived, user_id = %ctx.user.id)
 )]
 async fn list_projects(
     State(state): State<AppState>,
     Extension(ctx): Extension<RequestContext>,
     Query(query): Query<ListProjectsQuery>,
+    Query(filter): Query<ListProjectsFilter>,
 ) -> Result<Json<ListProjectsResponse>, ErrorResponse> {
     ensure_member_access(state.pool(), query.organization_id, ctx.user.id).await?;
 
-    let projects = ProjectRepository::list_by_organization(state.pool(), query.organization_id)
-        .await
-        .map_err(|error| {
-            tracing::error!(?error, organization_id = %query.organization_id, "failed to list projects");
-            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list projects")
-        })?;
+    let include_archived = filter.include_archived.unwrap_or(false);
+    let projects =
+        ProjectRepository::list_by_organization(state.pool(), query.organization_id, include_archived)
+            .await
+            .map_err(|error| {
+                tracing::error!(?error, organization_id = %query.organization_id, "failed to list projects");
+                ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to list projects")
+            })?;
 
     Ok(Json(ListProjectsResponse { projects }))
 }
@@ -144,6 +165,58 @@ async fn update_project(
     Ok(Json(response))
 }
 
+#[instrument(
+    name = "projects.archive_project",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn archive_project(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<MutationResponse<Project>>, ErrorResponse> {
+    set_project_archived(&state, &ctx, project_id, true).await
+}
+
+#[instrument(
+    name = "projects.unarchive_project",
+    skip(state, ctx),
+    fields(project_id = %project_id, user_id = %ctx.user.id)
+)]
+async fn unarchive_project(
+    State(state): State<AppState>,
+    Extension(ctx): Extension<RequestContext>,
+    Path(project_id): Path<Uuid>,
+) -> Result<Json<MutationResponse<Project>>, ErrorResponse> {
+    set_project_archived(&state, &ctx, project_id, false).await
+}
+
+async fn set_project_archived(
+    state: &AppState,
+    ctx: &RequestContext,
+    project_id: Uuid,
+    archived: bool,
+) -> Result<Json<MutationResponse<Project>>, ErrorResponse> {
+    let project = ProjectRepository::find_by_id(state.pool(), project_id)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, %project_id, "failed to load project");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "failed to load project")
+        })?
+        .ok_or_else(|| ErrorResponse::new(StatusCode::NOT_FOUND, "project not found"))?;
+
+    ensure_member_access(state.pool(), project.organization_id, ctx.user.id).await?;
+
+    let response = ProjectRepository::set_archived(state.pool(), project_id, archived)
+        .await
+        .map_err(|error| {
+            tracing::error!(?error, archived, "failed to archive project");
+            ErrorResponse::new(StatusCode::INTERNAL_SERVER_ERROR, "internal server error")
+        })?;
+
+    Ok(Json(response))
+}
+
 #[instrument(
     name = "projects.delete_project",
     skip(state, ctx),
@@ -173,3 +246,28 @@ async fn delete_project(
 
     Ok(Json(response))
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::http::Uri;
+
+    use super::*;
+
+    #[test]
+    fn test_include_archived_defaults_to_false() {
+        let organization_id = Uuid::new_v4();
+        let uri: Uri = format!("/projects?organization_id={organization_id}")
+            .parse()
+            .unwrap();
+        let Query(query) = Query::<ListProjectsQuery>::try_from_uri(&uri).unwrap();
+        let Query(filter) = Query::<ListProjectsFilter>::try_from_uri(&uri).unwrap();
+        assert_eq!(query.organization_id, organization_id);
+        assert!(!filter.include_archived.unwrap_or(false));
+
+        let uri: Uri = format!("/projects?organization_id={organization_id}&include_archived=true")
+            .parse()
+            .unwrap();
+        let Query(filter) = Query::<ListProjectsFilter>::try_from_uri(&uri).unwrap();
+        assert_eq!(filter.include_archived, Some(true));
+    }
+}
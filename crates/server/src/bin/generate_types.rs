@@ -34,8 +34,11 @@ fn generate_types_content() -> String {
         db::models::task::UpdateTask::decl(),
         db::models::scheduled_execution::ScheduledExecution::decl(),
         db::models::scheduled_execution::ScheduledExecutionStatus::decl(),
+        db::models::scheduled_execution::ScheduledExecutionEvent::decl(),
         server::routes::scheduled_executions::CreateScheduledExecutionRequest::decl(),
-        server::routes::scheduled_executions::ScheduledRepoInput::decl(),
+        db::models::scheduled_execution::ScheduledRepoInput::decl(),
+        services::services::scheduler::SchedulerState::decl(),
+        services::services::container::ActiveSession::decl(),
         db::models::scratch::DraftFollowUpData::decl(),
         db::models::scratch::DraftWorkspaceData::decl(),
         db::models::scratch::DraftWorkspaceRepo::decl(),
@@ -93,8 +96,11 @@ fn generate_types_content() -> String {
         utils::api::organizations::UpdateMemberRoleResponse::decl(),
         utils::api::projects::RemoteProject::decl(),
         utils::api::projects::ListProjectsResponse::decl(),
+        utils::api::projects::RemoteProjectStatus::decl(),
+        utils::api::projects::ListProjectStatusesResponse::decl(),
         utils::api::projects::RemoteProjectMembersResponse::decl(),
         server::routes::projects::CreateRemoteProjectRequest::decl(),
+        server::routes::projects::CancelAllAgentsResponse::decl(),
         server::routes::projects::LinkToExistingRequest::decl(),
         server::routes::repo::RegisterRepoRequest::decl(),
         server::routes::repo::InitRepoRequest::decl(),
@@ -108,7 +114,12 @@ fn generate_types_content() -> String {
         server::routes::config::CheckEditorAvailabilityQuery::decl(),
         server::routes::config::CheckEditorAvailabilityResponse::decl(),
         server::routes::config::CheckAgentAvailabilityQuery::decl(),
+        server::routes::config::ExecutorProfileSummary::decl(),
+        server::routes::config::ExecutorProfilesResponse::decl(),
+        server::routes::config::CommandPreview::decl(),
         server::routes::oauth::CurrentUserResponse::decl(),
+        server::routes::health::DbHealth::decl(),
+        server::error::ApiErrorCode::decl(),
         server::routes::sessions::CreateFollowUpAttempt::decl(),
         server::routes::task_attempts::ChangeTargetBranchRequest::decl(),
         server::routes::task_attempts::ChangeTargetBranchResponse::decl(),
@@ -120,6 +131,7 @@ fn generate_types_content() -> String {
         server::routes::sessions::review::ReviewError::decl(),
         server::routes::task_attempts::OpenEditorRequest::decl(),
         server::routes::task_attempts::OpenEditorResponse::decl(),
+        server::routes::task_attempts::WorkspaceLogsResponse::decl(),
         server::routes::tasks::CreateAndStartTaskRequest::decl(),
         server::routes::task_attempts::pr::CreatePrApiRequest::decl(),
         server::routes::images::ImageResponse::decl(),
@@ -181,6 +193,7 @@ fn generate_types_content() -> String {
         executors::executors::BaseCodingAgent::decl(),
         executors::executors::CodingAgent::decl(),
         executors::executors::SlashCommandDescription::decl(),
+        executors::executors::SlashCommandScope::decl(),
         executors::executors::AvailabilityInfo::decl(),
         executors::command::CommandBuilder::decl(),
         executors::profile::ExecutorProfileId::decl(),
@@ -188,6 +201,7 @@ fn generate_types_content() -> String {
         executors::profile::ExecutorConfigs::decl(),
         executors::executors::BaseAgentCapability::decl(),
         executors::executors::claude::ClaudeCode::decl(),
+        executors::executors::claude::CommandScanScope::decl(),
         executors::executors::gemini::Gemini::decl(),
         executors::executors::amp::Amp::decl(),
         executors::executors::codex::Codex::decl(),
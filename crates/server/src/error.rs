@@ -13,6 +13,7 @@ use deployment::{DeploymentError, RemoteClientNotConfigured};
 use executors::{command::CommandBuildError, executors::ExecutorError};
 use git2::Error as Git2Error;
 use local_deployment::pty::PtyError;
+use serde::Serialize;
 use services::services::{
     config::{ConfigError, EditorOpenError},
     container::ContainerError,
@@ -22,11 +23,20 @@ use services::services::{
     project::ProjectServiceError,
     remote_client::RemoteClientError,
     repo::RepoError as RepoServiceError,
+    scheduler::RerunError,
     worktree_manager::WorktreeError,
 };
 use thiserror::Error;
 use utils::response::ApiResponse;
 
+/// Machine-readable `error_data` on error responses the frontend reacts to,
+/// e.g. `auth_required` to prompt sign-in instead of showing a generic error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ts_rs::TS)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorCode {
+    AuthRequired,
+}
+
 #[derive(Debug, Error, ts_rs::TS)]
 #[ts(type = "string")]
 pub enum ApiError {
@@ -80,6 +90,8 @@ pub enum ApiError {
     CommandBuilder(#[from] CommandBuildError),
     #[error(transparent)]
     Pty(#[from] PtyError),
+    #[error(transparent)]
+    Rerun(#[from] RerunError),
 }
 
 impl From<&'static str> for ApiError {
@@ -126,6 +138,9 @@ impl IntoResponse for ApiError {
             },
             ApiError::GitHost(_) => (StatusCode::INTERNAL_SERVER_ERROR, "GitHostError"),
             ApiError::Deployment(_) => (StatusCode::INTERNAL_SERVER_ERROR, "DeploymentError"),
+            ApiError::Container(ContainerError::WorkspaceBusy(_)) => {
+                (StatusCode::CONFLICT, "ContainerError")
+            }
             ApiError::Container(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ContainerError"),
             ApiError::Executor(_) => (StatusCode::INTERNAL_SERVER_ERROR, "ExecutorError"),
             ApiError::CommandBuilder(_) => (StatusCode::INTERNAL_SERVER_ERROR, "CommandBuildError"),
@@ -150,6 +165,9 @@ impl IntoResponse for ApiError {
                 RemoteClientError::Auth => (StatusCode::UNAUTHORIZED, "RemoteClientError"),
                 RemoteClientError::Timeout => (StatusCode::GATEWAY_TIMEOUT, "RemoteClientError"),
                 RemoteClientError::Transport(_) => (StatusCode::BAD_GATEWAY, "RemoteClientError"),
+                RemoteClientError::Unavailable => {
+                    (StatusCode::SERVICE_UNAVAILABLE, "RemoteClientError")
+                }
                 RemoteClientError::Http { status, .. } => (
                     StatusCode::from_u16(*status).unwrap_or(StatusCode::BAD_GATEWAY),
                     "RemoteClientError",
@@ -171,7 +189,7 @@ impl IntoResponse for ApiError {
                     }
                     _ => (StatusCode::BAD_REQUEST, "RemoteClientError"),
                 },
-                RemoteClientError::Storage(_) => {
+                RemoteClientError::Storage(_) | RemoteClientError::Header(_) => {
                     (StatusCode::INTERNAL_SERVER_ERROR, "RemoteClientError")
                 }
                 RemoteClientError::Serde(_) | RemoteClientError::Url(_) => {
@@ -187,6 +205,14 @@ impl IntoResponse for ApiError {
                 PtyError::SessionClosed => (StatusCode::GONE, "PtyError"),
                 _ => (StatusCode::INTERNAL_SERVER_ERROR, "PtyError"),
             },
+            ApiError::Rerun(err) => match err {
+                RerunError::NotFound | RerunError::TaskNotFound(_) => {
+                    (StatusCode::NOT_FOUND, "RerunError")
+                }
+                RerunError::NotFired(_) => (StatusCode::BAD_REQUEST, "RerunError"),
+                RerunError::TaskInProgress(_) => (StatusCode::CONFLICT, "RerunError"),
+                RerunError::Sqlx(_) => (StatusCode::INTERNAL_SERVER_ERROR, "RerunError"),
+            },
         };
 
         let error_message = match &self {
@@ -216,6 +242,7 @@ impl IntoResponse for ApiError {
                 RemoteClientError::Auth => "Unauthorized. Please sign in again.".to_string(),
                 RemoteClientError::Timeout => "Remote service timeout. Please try again.".to_string(),
                 RemoteClientError::Transport(_) => "Remote service unavailable. Please try again.".to_string(),
+                RemoteClientError::Unavailable => "Remote service unavailable. Please try again shortly.".to_string(),
                 RemoteClientError::Http { body, .. } => {
                     if body.is_empty() {
                         "Remote service error. Please try again.".to_string()
@@ -260,14 +287,26 @@ impl IntoResponse for ApiError {
                 },
                 RemoteClientError::Serde(_) => "Unexpected response from remote service.".to_string(),
                 RemoteClientError::Url(_) => "Remote service URL is invalid.".to_string(),
+                RemoteClientError::Header(_) => "Custom remote request headers are invalid.".to_string(),
             },
             ApiError::Unauthorized => "Unauthorized. Please sign in again.".to_string(),
             ApiError::BadRequest(msg) => msg.clone(),
             ApiError::Conflict(msg) => msg.clone(),
             ApiError::Forbidden(msg) => msg.clone(),
+            ApiError::Container(err @ ContainerError::WorkspaceBusy(_)) => err.to_string(),
+            ApiError::Rerun(err) if !matches!(err, RerunError::Sqlx(_)) => err.to_string(),
             _ => format!("{}: {}", error_type, self),
         };
-        let response = ApiResponse::<()>::error(&error_message);
+        let error_code = match &self {
+            ApiError::Unauthorized | ApiError::RemoteClient(RemoteClientError::Auth) => {
+                Some(ApiErrorCode::AuthRequired)
+            }
+            _ => None,
+        };
+        let mut response = ApiResponse::<(), ApiErrorCode>::error(&error_message);
+        if let Some(code) = error_code {
+            response = response.with_error_data(code);
+        }
         (status_code, Json(response)).into_response()
     }
 }
@@ -347,3 +386,42 @@ impl From<ProjectRepoError> for ApiError {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use axum::body::to_bytes;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn unauthenticated_remote_requests_carry_auth_code() {
+        let response = ApiError::RemoteClient(RemoteClientError::Auth).into_response();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["error_data"], "auth_required");
+        assert_eq!(body["message"], "Unauthorized. Please sign in again.");
+
+        let response = ApiError::BadRequest("nope".to_string()).into_response();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert!(body["error_data"].is_null());
+    }
+
+    #[test]
+    fn rerun_errors_map_to_their_status() {
+        let status = |err| ApiError::Rerun(err).into_response().status();
+        assert_eq!(status(RerunError::NotFound), StatusCode::NOT_FOUND);
+        assert_eq!(
+            status(RerunError::NotFired(
+                db::models::scheduled_execution::ScheduledExecutionStatus::Pending
+            )),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            status(RerunError::TaskInProgress(uuid::Uuid::nil())),
+            StatusCode::CONFLICT
+        );
+    }
+}
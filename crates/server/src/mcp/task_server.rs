@@ -19,11 +19,12 @@ use rmcp::{
 };
 use serde::{Deserialize, Serialize, de::DeserializeOwned};
 use serde_json;
+use thiserror::Error;
 use uuid::Uuid;
 
 use crate::routes::{
     containers::ContainerQuery,
-    task_attempts::{CreateTaskAttemptBody, WorkspaceRepoInput},
+    task_attempts::{CreateTaskAttemptBody, WorkspaceLogsResponse, WorkspaceRepoInput},
 };
 
 #[derive(Debug, Deserialize, schemars::JsonSchema)]
@@ -275,14 +276,31 @@ pub struct McpWorkspaceRepoInput {
 pub struct StartWorkspaceSessionRequest {
     #[schemars(description = "The ID of the task to start")]
     pub task_id: Uuid,
-    #[schemars(
-        description = "The coding agent executor to run ('CLAUDE_CODE', 'AMP', 'GEMINI', 'CODEX', 'OPENCODE', 'CURSOR_AGENT', 'QWEN_CODE', 'COPILOT', 'DROID')"
-    )]
+    #[schemars(schema_with = "executor_schema")]
     pub executor: String,
     #[schemars(description = "Optional executor variant, if needed")]
     pub variant: Option<String>,
     #[schemars(description = "Base branch for each repository in the project")]
     pub repos: Vec<McpWorkspaceRepoInput>,
+    #[schemars(
+        description = "Check that each base branch exists in its repository before starting (default: true)"
+    )]
+    pub validate_branches: Option<bool>,
+    #[schemars(description = "Validate the request without creating a workspace (default: false)")]
+    pub dry_run: Option<bool>,
+}
+
+/// Lists the executors from [`BaseCodingAgent`] so the description can't drift from the enum.
+fn executor_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+    let executors = BaseCodingAgent::all()
+        .into_iter()
+        .map(|agent| format!("'{agent}' ({})", agent.display_name()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    schemars::json_schema!({
+        "type": "string",
+        "description": format!("The coding agent executor to run ({executors})"),
+    })
 }
 
 #[derive(Debug, Serialize, schemars::JsonSchema)]
@@ -291,6 +309,14 @@ pub struct StartWorkspaceSessionResponse {
     pub workspace_id: String,
 }
 
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct StartWorkspaceSessionDryRunResponse {
+    pub would_create: bool,
+    pub task_id: String,
+    pub project_id: String,
+    pub executor: String,
+}
+
 #[derive(Debug, Serialize, schemars::JsonSchema)]
 pub struct DeleteTaskResponse {
     pub deleted_task_id: Option<String>,
@@ -307,6 +333,33 @@ pub struct GetTaskResponse {
     pub task: TaskDetails,
 }
 
+/// Number of log entries returned by `get_workspace_logs` when `tail` is omitted.
+const DEFAULT_LOG_TAIL: usize = 50;
+
+#[derive(Debug, Deserialize, schemars::JsonSchema)]
+pub struct GetWorkspaceLogsRequest {
+    #[schemars(description = "The ID of the workspace whose agent logs to read")]
+    pub workspace_id: Uuid,
+    #[schemars(description = "Return only the last N entries (default 50)")]
+    pub tail: Option<usize>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct WorkspaceLogEntry {
+    #[schemars(description = "Entry kind, e.g. 'assistant_message', 'tool_use', 'error_message'")]
+    pub entry_type: String,
+    pub content: String,
+    pub timestamp: Option<String>,
+}
+
+#[derive(Debug, Serialize, schemars::JsonSchema)]
+pub struct GetWorkspaceLogsResponse {
+    pub workspace_id: String,
+    pub execution_process_id: Option<String>,
+    pub entries: Vec<WorkspaceLogEntry>,
+    pub count: usize,
+}
+
 #[derive(Debug, Clone)]
 pub struct TaskServer {
     client: reqwest::Client,
@@ -421,6 +474,88 @@ struct ApiResponseEnvelope<T> {
     message: Option<String>,
 }
 
+/// Errors returned by MCP tools. Each variant maps to a stable `code` so clients can
+/// branch on the failure kind instead of matching on the human-readable message.
+#[derive(Debug, Error)]
+pub enum McpTaskError {
+    #[error("At least one repository must be specified.")]
+    NoRepos,
+    #[error("Executor must not be empty.")]
+    EmptyExecutor,
+    #[error("Unknown executor '{0}'.")]
+    UnknownExecutor(String),
+    #[error(
+        "Invalid status filter. Valid values: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'"
+    )]
+    InvalidStatus(String),
+    #[error("Failed to connect to VK API")]
+    ApiUnreachable(String),
+    #[error("VK API returned error status: {0}")]
+    ApiStatus(reqwest::StatusCode),
+    #[error("Failed to parse VK API response")]
+    InvalidApiResponse(String),
+    #[error("VK API returned error")]
+    Api(String),
+    #[error("VK API response missing data field")]
+    MissingData,
+    #[error("Base branch not found in repository")]
+    UnknownBranches(Vec<String>),
+    #[error("Repository not found")]
+    UnknownRepos(Vec<String>),
+}
+
+impl McpTaskError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::NoRepos => "NO_REPOS",
+            Self::EmptyExecutor => "EMPTY_EXECUTOR",
+            Self::UnknownExecutor(_) => "UNKNOWN_EXECUTOR",
+            Self::InvalidStatus(_) => "INVALID_STATUS",
+            Self::ApiUnreachable(_) => "API_UNREACHABLE",
+            Self::ApiStatus(_) => "API_STATUS",
+            Self::InvalidApiResponse(_) => "INVALID_API_RESPONSE",
+            Self::Api(_) => "API_ERROR",
+            Self::MissingData => "MISSING_DATA",
+            Self::UnknownBranches(_) => "UNKNOWN_BRANCHES",
+            Self::UnknownRepos(_) => "UNKNOWN_REPOS",
+        }
+    }
+
+    fn details(&self) -> Option<String> {
+        match self {
+            Self::InvalidStatus(d)
+            | Self::ApiUnreachable(d)
+            | Self::InvalidApiResponse(d)
+            | Self::Api(d) => Some(d.clone()),
+            Self::UnknownBranches(pairs) => Some(pairs.join(", ")),
+            Self::UnknownRepos(ids) => Some(ids.join(", ")),
+            _ => None,
+        }
+    }
+
+    /// JSON payload sent to the client: `{ success, code, error, details? }`.
+    pub fn to_value(&self) -> serde_json::Value {
+        let mut v = serde_json::json!({
+            "success": false,
+            "code": self.code(),
+            "error": self.to_string(),
+        });
+        if let Some(d) = self.details() {
+            v["details"] = serde_json::json!(d);
+        }
+        v
+    }
+}
+
+impl From<McpTaskError> for CallToolResult {
+    fn from(err: McpTaskError) -> Self {
+        CallToolResult::error(vec![Content::text(
+            serde_json::to_string_pretty(&err.to_value())
+                .unwrap_or_else(|_| "Failed to serialize error".to_string()),
+        )])
+    }
+}
+
 impl TaskServer {
     fn success<T: Serialize>(data: &T) -> Result<CallToolResult, ErrorData> {
         Ok(CallToolResult::success(vec![Content::text(
@@ -429,19 +564,8 @@ impl TaskServer {
         )]))
     }
 
-    fn err_value(v: serde_json::Value) -> Result<CallToolResult, ErrorData> {
-        Ok(CallToolResult::error(vec![Content::text(
-            serde_json::to_string_pretty(&v)
-                .unwrap_or_else(|_| "Failed to serialize error".to_string()),
-        )]))
-    }
-
-    fn err<S: Into<String>>(msg: S, details: Option<S>) -> Result<CallToolResult, ErrorData> {
-        let mut v = serde_json::json!({"success": false, "error": msg.into()});
-        if let Some(d) = details {
-            v["details"] = serde_json::json!(d.into());
-        };
-        Self::err_value(v)
+    fn err(error: McpTaskError) -> Result<CallToolResult, ErrorData> {
+        Ok(error.into())
     }
 
     async fn send_json<T: DeserializeOwned>(
@@ -451,40 +575,37 @@ impl TaskServer {
         let resp = rb
             .send()
             .await
-            .map_err(|e| Self::err("Failed to connect to VK API", Some(&e.to_string())).unwrap())?;
+            .map_err(|e| McpTaskError::ApiUnreachable(e.to_string()))?;
 
         if !resp.status().is_success() {
             let status = resp.status();
-            return Err(
-                Self::err(format!("VK API returned error status: {}", status), None).unwrap(),
-            );
+            return Err(McpTaskError::ApiStatus(status).into());
         }
 
-        let api_response = resp.json::<ApiResponseEnvelope<T>>().await.map_err(|e| {
-            Self::err("Failed to parse VK API response", Some(&e.to_string())).unwrap()
-        })?;
+        let api_response = resp
+            .json::<ApiResponseEnvelope<T>>()
+            .await
+            .map_err(|e| McpTaskError::InvalidApiResponse(e.to_string()))?;
 
         if !api_response.success {
             let msg = api_response.message.as_deref().unwrap_or("Unknown error");
-            return Err(Self::err("VK API returned error", Some(msg)).unwrap());
+            return Err(McpTaskError::Api(msg.to_string()).into());
         }
 
         api_response
             .data
-            .ok_or_else(|| Self::err("VK API response missing data field", None).unwrap())
+            .ok_or_else(|| McpTaskError::MissingData.into())
     }
 
     async fn send_empty_json(&self, rb: reqwest::RequestBuilder) -> Result<(), CallToolResult> {
         let resp = rb
             .send()
             .await
-            .map_err(|e| Self::err("Failed to connect to VK API", Some(&e.to_string())).unwrap())?;
+            .map_err(|e| McpTaskError::ApiUnreachable(e.to_string()))?;
 
         if !resp.status().is_success() {
             let status = resp.status();
-            return Err(
-                Self::err(format!("VK API returned error status: {}", status), None).unwrap(),
-            );
+            return Err(McpTaskError::ApiStatus(status).into());
         }
 
         #[derive(Deserialize)]
@@ -493,18 +614,57 @@ impl TaskServer {
             message: Option<String>,
         }
 
-        let api_response = resp.json::<EmptyApiResponse>().await.map_err(|e| {
-            Self::err("Failed to parse VK API response", Some(&e.to_string())).unwrap()
-        })?;
+        let api_response = resp
+            .json::<EmptyApiResponse>()
+            .await
+            .map_err(|e| McpTaskError::InvalidApiResponse(e.to_string()))?;
 
         if !api_response.success {
             let msg = api_response.message.as_deref().unwrap_or("Unknown error");
-            return Err(Self::err("VK API returned error", Some(msg)).unwrap());
+            return Err(McpTaskError::Api(msg.to_string()).into());
         }
 
         Ok(())
     }
 
+    /// Returns `repo_id:branch` for every requested base branch that doesn't exist in its repo.
+    async fn find_missing_repos(
+        &self,
+        repos: &[McpWorkspaceRepoInput],
+    ) -> Result<Vec<String>, CallToolResult> {
+        let ids: Vec<Uuid> = repos.iter().map(|r| r.repo_id).collect();
+        let url = self.url("/api/repos/batch");
+        let payload = serde_json::json!({ "ids": ids });
+        let found: Vec<Repo> = self
+            .send_json(self.client.post(&url).json(&payload))
+            .await?;
+        Ok(ids
+            .into_iter()
+            .filter(|id| !found.iter().any(|repo| repo.id == *id))
+            .map(|id| id.to_string())
+            .collect())
+    }
+
+    async fn find_missing_branches(
+        &self,
+        repos: &[McpWorkspaceRepoInput],
+    ) -> Result<Vec<String>, CallToolResult> {
+        #[derive(Deserialize)]
+        struct BranchName {
+            name: String,
+        }
+
+        let mut missing = Vec::new();
+        for repo in repos {
+            let url = self.url(&format!("/api/repos/{}/branches", repo.repo_id));
+            let branches: Vec<BranchName> = self.send_json(self.client.get(&url)).await?;
+            if !branches.iter().any(|b| b.name == repo.base_branch) {
+                missing.push(format!("{}:{}", repo.repo_id, repo.base_branch));
+            }
+        }
+        Ok(missing)
+    }
+
     fn url(&self, path: &str) -> String {
         format!(
             "{}/{}",
@@ -791,10 +951,7 @@ impl TaskServer {
             match TaskStatus::from_str(status_str) {
                 Ok(s) => Some(s),
                 Err(_) => {
-                    return Self::err(
-                        "Invalid status filter. Valid values: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'".to_string(),
-                        Some(status_str.to_string()),
-                    );
+                    return Self::err(McpTaskError::InvalidStatus(status_str.to_string()));
                 }
             }
         } else {
@@ -837,7 +994,7 @@ impl TaskServer {
     }
 
     #[tool(
-        description = "Start working on a task by creating and launching a new workspace session."
+        description = "Start working on a task by creating and launching a new workspace session. Set `dry_run` to validate the request without creating anything."
     )]
     async fn start_workspace_session(
         &self,
@@ -846,29 +1003,21 @@ impl TaskServer {
             executor,
             variant,
             repos,
+            validate_branches,
+            dry_run,
         }): Parameters<StartWorkspaceSessionRequest>,
     ) -> Result<CallToolResult, ErrorData> {
         if repos.is_empty() {
-            return Self::err(
-                "At least one repository must be specified.".to_string(),
-                None::<String>,
-            );
+            return Self::err(McpTaskError::NoRepos);
         }
 
         let executor_trimmed = executor.trim();
         if executor_trimmed.is_empty() {
-            return Self::err("Executor must not be empty.".to_string(), None::<String>);
+            return Self::err(McpTaskError::EmptyExecutor);
         }
 
-        let normalized_executor = executor_trimmed.replace('-', "_").to_ascii_uppercase();
-        let base_executor = match BaseCodingAgent::from_str(&normalized_executor) {
-            Ok(exec) => exec,
-            Err(_) => {
-                return Self::err(
-                    format!("Unknown executor '{executor_trimmed}'."),
-                    None::<String>,
-                );
-            }
+        let Some(base_executor) = BaseCodingAgent::parse_flexible(executor_trimmed) else {
+            return Self::err(McpTaskError::UnknownExecutor(executor_trimmed.to_string()));
         };
 
         let variant = variant.and_then(|v| {
@@ -885,6 +1034,39 @@ impl TaskServer {
             variant,
         };
 
+        match self.find_missing_repos(&repos).await {
+            Ok(missing) if !missing.is_empty() => {
+                return Self::err(McpTaskError::UnknownRepos(missing));
+            }
+            Ok(_) => {}
+            Err(e) => return Ok(e),
+        }
+
+        if validate_branches.unwrap_or(true) {
+            match self.find_missing_branches(&repos).await {
+                Ok(missing) if !missing.is_empty() => {
+                    return Self::err(McpTaskError::UnknownBranches(missing));
+                }
+                Ok(_) => {}
+                Err(e) => return Ok(e),
+            }
+        }
+
+        if dry_run.unwrap_or(false) {
+            let url = self.url(&format!("/api/tasks/{}", task_id));
+            let task: Task = match self.send_json(self.client.get(&url)).await {
+                Ok(t) => t,
+                Err(e) => return Ok(e),
+            };
+
+            return TaskServer::success(&StartWorkspaceSessionDryRunResponse {
+                would_create: true,
+                task_id: task.id.to_string(),
+                project_id: task.project_id.to_string(),
+                executor: executor_profile_id.to_string(),
+            });
+        }
+
         let workspace_repos: Vec<WorkspaceRepoInput> = repos
             .into_iter()
             .map(|r| WorkspaceRepoInput {
@@ -930,10 +1112,7 @@ impl TaskServer {
             match TaskStatus::from_str(status_str) {
                 Ok(s) => Some(s),
                 Err(_) => {
-                    return Self::err(
-                        "Invalid status filter. Valid values: 'todo', 'inprogress', 'inreview', 'done', 'cancelled'".to_string(),
-                        Some(status_str.to_string()),
-                    );
+                    return Self::err(McpTaskError::InvalidStatus(status_str.to_string()));
                 }
             }
         } else {
@@ -999,12 +1178,54 @@ impl TaskServer {
 
         TaskServer::success(&response)
     }
+
+    #[tool(
+        description = "Read the latest coding agent logs of a workspace as structured entries. `workspace_id` is required; `tail` limits the result to the last N entries (default 50)."
+    )]
+    async fn get_workspace_logs(
+        &self,
+        Parameters(GetWorkspaceLogsRequest { workspace_id, tail }): Parameters<
+            GetWorkspaceLogsRequest,
+        >,
+    ) -> Result<CallToolResult, ErrorData> {
+        let tail = tail.unwrap_or(DEFAULT_LOG_TAIL);
+        let url = self.url(&format!("/api/task-attempts/{}/logs", workspace_id));
+        let logs: WorkspaceLogsResponse = match self
+            .send_json(self.client.get(&url).query(&[("tail", tail)]))
+            .await
+        {
+            Ok(logs) => logs,
+            Err(e) => return Ok(e),
+        };
+
+        let entries: Vec<WorkspaceLogEntry> = logs
+            .entries
+            .into_iter()
+            .map(|entry| WorkspaceLogEntry {
+                entry_type: serde_json::to_value(&entry.entry_type)
+                    .ok()
+                    .and_then(|v| v.get("type")?.as_str().map(str::to_string))
+                    .unwrap_or_default(),
+                content: entry.content,
+                timestamp: entry.timestamp,
+            })
+            .collect();
+
+        let response = GetWorkspaceLogsResponse {
+            workspace_id: workspace_id.to_string(),
+            execution_process_id: logs.execution_process_id.map(|id| id.to_string()),
+            count: entries.len(),
+            entries,
+        };
+
+        TaskServer::success(&response)
+    }
 }
 
 #[tool_handler]
 impl ServerHandler for TaskServer {
     fn get_info(&self) -> ServerInfo {
-        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script'. Make sure to pass `project_id`, `task_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
+        let mut instruction = "A task and project management server. If you need to create or update tickets or tasks then use these tools. Most of them absolutely require that you pass the `project_id` of the project that you are currently working on. You can get project ids by using `list projects`. Call `list_tasks` to fetch the `task_ids` of all the tasks in a project. TOOLS: 'list_projects', 'list_tasks', 'create_task', 'start_workspace_session', 'get_task', 'update_task', 'delete_task', 'get_workspace_logs', 'list_repos', 'get_repo', 'update_setup_script', 'update_cleanup_script', 'update_dev_server_script'. Make sure to pass `project_id`, `task_id`, or `repo_id` where required. You can use list tools to get the available ids.".to_string();
         if self.context.is_some() {
             let context_instruction = "Use 'get_context' to fetch project/task/workspace metadata for the active Vibe Kanban workspace session when available.";
             instruction = format!("{} {}", context_instruction, instruction);
@@ -1021,3 +1242,257 @@ impl ServerHandler for TaskServer {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+
+    use super::*;
+
+    #[test]
+    fn executor_schema_labels_each_agent() {
+        let schema = executor_schema(&mut schemars::SchemaGenerator::default());
+        let description = schema.get("description").unwrap().as_str().unwrap();
+        assert!(description.contains("'CLAUDE_CODE' (Claude Code)"));
+        assert!(description.contains("'OPENCODE' (OpenCode)"));
+    }
+
+    #[test]
+    fn errors_carry_stable_codes_and_messages() {
+        let value = McpTaskError::UnknownExecutor("foo".to_string()).to_value();
+        assert_eq!(value["success"], false);
+        assert_eq!(value["code"], "UNKNOWN_EXECUTOR");
+        assert_eq!(value["error"], "Unknown executor 'foo'.");
+        assert!(value.get("details").is_none());
+
+        let value = McpTaskError::InvalidStatus("bogus".to_string()).to_value();
+        assert_eq!(value["code"], "INVALID_STATUS");
+        assert_eq!(value["details"], "bogus");
+    }
+
+    /// Serves `router` on an ephemeral port and returns its base URL.
+    async fn mock_api(router: axum::Router) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+        format!("http://{addr}")
+    }
+
+    /// `POST /api/repos/batch` handler that only knows the `known` repositories.
+    fn repos_batch(known: Vec<Uuid>) -> axum::routing::MethodRouter {
+        axum::routing::post(move || {
+            let repos: Vec<_> = known
+                .iter()
+                .map(|id| {
+                    serde_json::json!({
+                        "id": id,
+                        "path": "/tmp/repo",
+                        "name": "repo",
+                        "display_name": "repo",
+                        "parallel_setup_script": false,
+                        "created_at": "2026-01-01T00:00:00Z",
+                        "updated_at": "2026-01-01T00:00:00Z",
+                    })
+                })
+                .collect();
+            async move { axum::Json(serde_json::json!({ "success": true, "data": repos })) }
+        })
+    }
+
+    #[tokio::test]
+    async fn start_workspace_session_rejects_unknown_base_branch() {
+        let repo_id = Uuid::new_v4();
+        let router = axum::Router::new()
+            .route("/api/repos/batch", repos_batch(vec![repo_id]))
+            .route(
+                "/api/repos/{repo_id}/branches",
+                axum::routing::get(|| async {
+                    axum::Json(serde_json::json!({
+                        "success": true,
+                        "data": [{ "name": "main" }, { "name": "origin/main" }],
+                    }))
+                }),
+            );
+        let server = TaskServer::new(&mock_api(router).await);
+
+        let result = server
+            .start_workspace_session(Parameters(StartWorkspaceSessionRequest {
+                task_id: Uuid::new_v4(),
+                executor: "CLAUDE_CODE".to_string(),
+                variant: None,
+                repos: vec![McpWorkspaceRepoInput {
+                    repo_id,
+                    base_branch: "mian".to_string(),
+                }],
+                validate_branches: None,
+                dry_run: None,
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(result.is_error, Some(true));
+        let body = serde_json::to_string(&result).unwrap();
+        assert!(body.contains("UNKNOWN_BRANCHES"));
+        assert!(body.contains(&format!("{repo_id}:mian")));
+    }
+
+    #[tokio::test]
+    async fn start_workspace_session_without_repos_returns_no_repos_code() {
+        let server = TaskServer::new("http://127.0.0.1:0");
+        let result = server
+            .start_workspace_session(Parameters(StartWorkspaceSessionRequest {
+                task_id: Uuid::new_v4(),
+                executor: "CLAUDE_CODE".to_string(),
+                variant: None,
+                repos: vec![],
+                validate_branches: None,
+                dry_run: None,
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(result.is_error, Some(true));
+        let body = serde_json::to_string(&result).unwrap();
+        assert!(body.contains("NO_REPOS"));
+    }
+
+    #[tokio::test]
+    async fn start_workspace_session_dry_run_does_not_create_workspace() {
+        let task_id = Uuid::new_v4();
+        let project_id = Uuid::new_v4();
+        let repo_id = Uuid::new_v4();
+        let created = Arc::new(AtomicBool::new(false));
+        let created_flag = created.clone();
+        let router = axum::Router::new()
+            .route("/api/repos/batch", repos_batch(vec![repo_id]))
+            .route(
+                "/api/tasks/{task_id}",
+                axum::routing::get(move || async move {
+                    axum::Json(serde_json::json!({
+                        "success": true,
+                        "data": {
+                            "id": task_id,
+                            "project_id": project_id,
+                            "title": "Dry run",
+                            "description": null,
+                            "status": "todo",
+                            "parent_workspace_id": null,
+                            "created_at": "2026-01-01T00:00:00Z",
+                            "updated_at": "2026-01-01T00:00:00Z",
+                        },
+                    }))
+                }),
+            )
+            .route(
+                "/api/task-attempts",
+                axum::routing::post(move || async move {
+                    created_flag.store(true, Ordering::SeqCst);
+                    axum::http::StatusCode::INTERNAL_SERVER_ERROR
+                }),
+            );
+        let server = TaskServer::new(&mock_api(router).await);
+
+        let result = server
+            .start_workspace_session(Parameters(StartWorkspaceSessionRequest {
+                task_id,
+                executor: "claude-code".to_string(),
+                variant: None,
+                repos: vec![McpWorkspaceRepoInput {
+                    repo_id,
+                    base_branch: "main".to_string(),
+                }],
+                validate_branches: Some(false),
+                dry_run: Some(true),
+            }))
+            .await
+            .unwrap();
+
+        assert_ne!(result.is_error, Some(true));
+        assert!(!created.load(Ordering::SeqCst));
+        let body = serde_json::to_string(&result).unwrap();
+        assert!(body.contains("would_create"));
+        assert!(body.contains(&project_id.to_string()));
+    }
+
+    #[tokio::test]
+    async fn start_workspace_session_dry_run_rejects_unknown_repo_without_branch_checks() {
+        let router = axum::Router::new().route("/api/repos/batch", repos_batch(vec![]));
+        let server = TaskServer::new(&mock_api(router).await);
+        let repo_id = Uuid::new_v4();
+
+        let result = server
+            .start_workspace_session(Parameters(StartWorkspaceSessionRequest {
+                task_id: Uuid::new_v4(),
+                executor: "claude-code".to_string(),
+                variant: None,
+                repos: vec![McpWorkspaceRepoInput {
+                    repo_id,
+                    base_branch: "main".to_string(),
+                }],
+                validate_branches: Some(false),
+                dry_run: Some(true),
+            }))
+            .await
+            .unwrap();
+
+        assert_eq!(result.is_error, Some(true));
+        let body = serde_json::to_string(&result).unwrap();
+        assert!(body.contains("UNKNOWN_REPOS"));
+        assert!(body.contains(&repo_id.to_string()));
+    }
+
+    #[tokio::test]
+    async fn get_workspace_logs_returns_structured_tail() {
+        let workspace_id = Uuid::new_v4();
+        let router = axum::Router::new().route(
+            "/api/task-attempts/{workspace_id}/logs",
+            axum::routing::get(
+                |axum::extract::Query(query): axum::extract::Query<
+                    std::collections::HashMap<String, String>,
+                >| async move {
+                    assert_eq!(query.get("tail").map(String::as_str), Some("2"));
+                    axum::Json(serde_json::json!({
+                        "success": true,
+                        "data": {
+                            "execution_process_id": null,
+                            "entries": [
+                                {
+                                    "timestamp": null,
+                                    "entry_type": { "type": "assistant_message" },
+                                    "content": "Working on it",
+                                    "metadata": null,
+                                },
+                                {
+                                    "timestamp": null,
+                                    "entry_type": { "type": "error_message", "error_type": { "type": "other" } },
+                                    "content": "Build failed",
+                                    "metadata": null,
+                                },
+                            ],
+                        },
+                    }))
+                },
+            ),
+        );
+        let server = TaskServer::new(&mock_api(router).await);
+
+        let result = server
+            .get_workspace_logs(Parameters(GetWorkspaceLogsRequest {
+                workspace_id,
+                tail: Some(2),
+            }))
+            .await
+            .unwrap();
+
+        assert_ne!(result.is_error, Some(true));
+        let body = serde_json::to_string(&result).unwrap();
+        assert!(body.contains("assistant_message"));
+        assert!(body.contains("Build failed"));
+        assert!(body.contains(r#"\"count\": 2"#));
+    }
+}
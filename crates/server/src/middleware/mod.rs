@@ -1,5 +1,7 @@
 pub mod model_loaders;
 pub mod origin;
+pub mod rate_limit;
 
 pub use model_loaders::*;
 pub use origin::*;
+pub use rate_limit::*;
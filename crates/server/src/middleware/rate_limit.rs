@@ -0,0 +1,120 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use axum::{
+    Json,
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode, header},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use utils::response::ApiResponse;
+
+/// Token bucket shared by every request routed through one layer.
+#[derive(Clone)]
+pub struct RateLimiter {
+    capacity: f64,
+    refill_per_sec: f64,
+    bucket: Arc<Mutex<Bucket>>,
+}
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl RateLimiter {
+    /// Allow bursts of up to `capacity` requests, refilled evenly over `period`.
+    pub fn new(capacity: u32, period: Duration) -> Self {
+        let capacity = f64::from(capacity.max(1));
+        Self {
+            capacity,
+            refill_per_sec: capacity / period.as_secs_f64().max(f64::EPSILON),
+            bucket: Arc::new(Mutex::new(Bucket {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            })),
+        }
+    }
+
+    /// Per-minute limit read from `var`, falling back to `default_per_minute` when unset or invalid.
+    pub fn per_minute_from_env(var: &str, default_per_minute: u32) -> Self {
+        let per_minute = match std::env::var(var) {
+            Ok(raw) => raw.trim().parse().unwrap_or_else(|_| {
+                tracing::warn!("ignoring invalid {var}={raw}; using {default_per_minute}");
+                default_per_minute
+            }),
+            Err(_) => default_per_minute,
+        };
+        Self::new(per_minute, Duration::from_secs(60))
+    }
+
+    /// Take a token, or return how long until one is available.
+    fn try_acquire_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut bucket = self.bucket.lock().unwrap_or_else(|e| e.into_inner());
+        let elapsed = now.saturating_duration_since(bucket.last_refill);
+        bucket.tokens =
+            (bucket.tokens + elapsed.as_secs_f64() * self.refill_per_sec).min(self.capacity);
+        bucket.last_refill = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / self.refill_per_sec,
+            ))
+        }
+    }
+}
+
+pub async fn rate_limit(
+    State(limiter): State<RateLimiter>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match limiter.try_acquire_at(Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => too_many_requests(retry_after),
+    }
+}
+
+fn too_many_requests(retry_after: Duration) -> Response {
+    // Retry-After takes whole seconds; round up so clients don't retry too early
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    let mut response = (
+        StatusCode::TOO_MANY_REQUESTS,
+        Json(ApiResponse::<()>::error(
+            "Too many requests to the remote server, please retry shortly",
+        )),
+    )
+        .into_response();
+    response
+        .headers_mut()
+        .insert(header::RETRY_AFTER, HeaderValue::from(secs.max(1)));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bursting_past_the_limit_is_rejected_until_refilled() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(10));
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire_at(start).is_ok());
+        assert!(limiter.try_acquire_at(start).is_ok());
+        let retry_after = limiter.try_acquire_at(start).unwrap_err();
+        assert_eq!(retry_after, Duration::from_secs(5));
+
+        let response = too_many_requests(retry_after);
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[header::RETRY_AFTER], "5");
+
+        assert!(limiter.try_acquire_at(start + retry_after).is_ok());
+    }
+}
@@ -0,0 +1,17 @@
+use axum::{Router, extract::State, response::Json as ResponseJson, routing::get};
+use deployment::Deployment;
+use services::services::container::{ActiveSession, ContainerService};
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_active_sessions(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Vec<ActiveSession>>>, ApiError> {
+    let sessions = deployment.container().list_active_sessions().await?;
+    Ok(ResponseJson(ApiResponse::success(sessions)))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new().route("/agents/active", get(get_active_sessions))
+}
@@ -9,12 +9,14 @@ use axum::{
     },
     http,
     response::{IntoResponse, Json as ResponseJson, Response},
-    routing::{get, put},
+    routing::{get, post, put},
 };
 use deployment::{Deployment, DeploymentError};
 use executors::{
+    env::{REDACTED_ENV_VALUE, is_sensitive_env_key},
     executors::{
-        AvailabilityInfo, BaseAgentCapability, BaseCodingAgent, StandardCodingAgentExecutor,
+        AvailabilityInfo, BaseAgentCapability, BaseCodingAgent, CodingAgent, ExecutorError,
+        StandardCodingAgentExecutor,
     },
     mcp_config::{McpConfig, read_agent_config, write_agent_config},
     profile::{ExecutorConfigs, ExecutorProfileId},
@@ -43,6 +45,11 @@ pub fn router() -> Router<DeploymentImpl> {
         .route("/sounds/{sound}", get(get_sound))
         .route("/mcp-config", get(get_mcp_servers).post(update_mcp_servers))
         .route("/profiles", get(get_profiles).put(update_profiles))
+        .route("/executors/profiles", get(list_executor_profiles))
+        .route(
+            "/executors/{executor}/preview-command",
+            post(preview_executor_command),
+        )
         .route(
             "/editors/check-availability",
             get(check_editor_availability),
@@ -416,6 +423,121 @@ async fn get_profiles(
     }))
 }
 
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct ExecutorProfileSummary {
+    pub executor_profile_id: ExecutorProfileId,
+    pub model: Option<String>,
+    pub agent: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct ExecutorProfilesResponse {
+    pub executors: HashMap<BaseCodingAgent, Vec<ExecutorProfileSummary>>,
+}
+
+/// Flatten the configured profiles into per-executor summaries, with the
+/// DEFAULT variant first and the rest sorted by name.
+fn summarize_profiles(profiles: &ExecutorConfigs) -> ExecutorProfilesResponse {
+    let executors = profiles
+        .executors
+        .iter()
+        .map(|(executor, config)| {
+            let mut variants: Vec<_> = config.configurations.iter().collect();
+            variants.sort_by_key(|(name, _)| (name.as_str() != "DEFAULT", name.as_str()));
+
+            let summaries = variants
+                .into_iter()
+                .map(|(name, coding_agent)| {
+                    // Every executor config serializes as `{ "<EXECUTOR>": { ... } }`
+                    let options = serde_json::to_value(coding_agent)
+                        .ok()
+                        .and_then(|value| value.as_object()?.values().next().cloned())
+                        .unwrap_or_default();
+                    let field = |key: &str| options.get(key)?.as_str().map(str::to_string);
+
+                    ExecutorProfileSummary {
+                        executor_profile_id: if name == "DEFAULT" {
+                            ExecutorProfileId::new(*executor)
+                        } else {
+                            ExecutorProfileId::with_variant(*executor, name.clone())
+                        },
+                        model: field("model"),
+                        agent: field("agent").or_else(|| field("mode")),
+                    }
+                })
+                .collect();
+
+            (*executor, summaries)
+        })
+        .collect();
+
+    ExecutorProfilesResponse { executors }
+}
+
+async fn list_executor_profiles(
+    State(_deployment): State<DeploymentImpl>,
+) -> ResponseJson<ApiResponse<ExecutorProfilesResponse>> {
+    ResponseJson(ApiResponse::success(summarize_profiles(
+        &ExecutorConfigs::get_cached(),
+    )))
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct CommandPreview {
+    pub program: String,
+    pub args: Vec<String>,
+    /// Environment variables from the config, with secret-looking values redacted
+    pub env: HashMap<String, String>,
+}
+
+/// Hide values of variables whose names suggest credentials.
+fn redact_env(env: HashMap<String, String>) -> HashMap<String, String> {
+    env.into_iter()
+        .map(|(name, value)| {
+            if is_sensitive_env_key(&name) {
+                (name, REDACTED_ENV_VALUE.to_string())
+            } else {
+                (name, value)
+            }
+        })
+        .collect()
+}
+
+/// Resolve the command line an executor config would launch, without spawning it.
+async fn preview_executor_command(
+    State(_deployment): State<DeploymentImpl>,
+    Path(executor): Path<String>,
+    Json(config): Json<Value>,
+) -> Result<ResponseJson<ApiResponse<CommandPreview>>, ApiError> {
+    let base = BaseCodingAgent::parse_flexible(&executor)
+        .ok_or_else(|| ApiError::BadRequest(format!("Unknown executor: {executor}")))?;
+    let env = config
+        .get("env")
+        .cloned()
+        .map(serde_json::from_value::<HashMap<String, String>>)
+        .transpose()
+        .map_err(|e| ApiError::BadRequest(format!("Invalid env: {e}")))?
+        .unwrap_or_default();
+    let agent: CodingAgent =
+        serde_json::from_value(serde_json::json!({ base.to_string(): config }))
+            .map_err(|e| ApiError::BadRequest(format!("Invalid {base} config: {e}")))?;
+
+    let builder = agent.command_preview().await?.ok_or_else(|| {
+        ApiError::BadRequest(format!("{base} does not launch a previewable command"))
+    })?;
+    let (program, args) = builder
+        .build_initial()
+        .map_err(ExecutorError::from)?
+        .into_resolved()
+        .await?;
+
+    Ok(ResponseJson(ApiResponse::success(CommandPreview {
+        program: program.to_string_lossy().to_string(),
+        args,
+        env: redact_env(env),
+    })))
+}
+
 async fn update_profiles(
     State(_deployment): State<DeploymentImpl>,
     body: String,
@@ -489,10 +611,11 @@ async fn check_agent_availability(
     let profiles = ExecutorConfigs::get_cached();
     let profile_id = ExecutorProfileId::new(query.executor);
 
-    let info = match profiles.get_coding_agent(&profile_id) {
-        Some(agent) => agent.get_availability_info(),
-        None => AvailabilityInfo::NotFound,
-    };
+    // Availability depends on the installed agent, not on the profile, so fall back to defaults
+    let info = profiles
+        .get_coding_agent(&profile_id)
+        .unwrap_or_else(|| query.executor.default_executor())
+        .get_availability_info();
 
     ResponseJson(ApiResponse::success(info))
 }
@@ -570,3 +693,38 @@ async fn handle_agent_slash_commands_ws(
         .await;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_env_redacts_secrets() {
+        let env = redact_env(HashMap::from([
+            ("OPENAI_API_KEY".to_string(), "sk-123".to_string()),
+            ("github_token".to_string(), "ghp_123".to_string()),
+            ("NODE_ENV".to_string(), "production".to_string()),
+        ]));
+
+        assert_eq!(env["OPENAI_API_KEY"], REDACTED_ENV_VALUE);
+        assert_eq!(env["github_token"], REDACTED_ENV_VALUE);
+        assert_eq!(env["NODE_ENV"], "production");
+    }
+
+    #[test]
+    fn summarize_profiles_includes_opencode_variants() {
+        let response = summarize_profiles(&ExecutorConfigs::from_defaults());
+        let opencode = &response.executors[&BaseCodingAgent::Opencode];
+
+        assert_eq!(
+            opencode[0].executor_profile_id,
+            ExecutorProfileId::new(BaseCodingAgent::Opencode)
+        );
+        let plan = opencode
+            .iter()
+            .find(|summary| summary.executor_profile_id.variant.as_deref() == Some("PLAN"))
+            .expect("PLAN variant");
+        assert_eq!(plan.agent.as_deref(), Some("plan"));
+        assert_eq!(plan.model, None);
+    }
+}
@@ -1,6 +1,76 @@
-use axum::response::Json;
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Json, Response},
+};
+use deployment::Deployment;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use ts_rs::TS;
 use utils::response::ApiResponse;
 
+use crate::DeploymentImpl;
+
+/// How long the liveness query may take before the database counts as wedged.
+const DB_HEALTH_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize, TS)]
+pub struct DbHealth {
+    pub healthy: bool,
+    pub latency_ms: u32,
+}
+
 pub async fn health_check() -> Json<ApiResponse<String>> {
     Json(ApiResponse::success("OK".to_string()))
 }
+
+/// Runs `SELECT 1` against the pool; 503 if it errors or times out.
+pub async fn db_health_check(State(deployment): State<DeploymentImpl>) -> Response {
+    let health = check_db(&deployment.db().pool, DB_HEALTH_TIMEOUT).await;
+    if health.healthy {
+        Json(ApiResponse::<DbHealth>::success(health)).into_response()
+    } else {
+        (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(ApiResponse::<(), DbHealth>::error_with_data(health)),
+        )
+            .into_response()
+    }
+}
+
+async fn check_db(pool: &SqlitePool, timeout: Duration) -> DbHealth {
+    let started = Instant::now();
+    let healthy = match tokio::time::timeout(timeout, sqlx::query("SELECT 1").execute(pool)).await {
+        Ok(Ok(_)) => true,
+        Ok(Err(e)) => {
+            tracing::warn!("Database health check failed: {e}");
+            false
+        }
+        Err(_) => {
+            tracing::warn!("Database health check timed out after {timeout:?}");
+            false
+        }
+    };
+    DbHealth {
+        healthy,
+        latency_ms: u32::try_from(started.elapsed().as_millis()).unwrap_or(u32::MAX),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn responsive_pool_is_healthy() {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        let health = check_db(&pool, DB_HEALTH_TIMEOUT).await;
+        assert!(health.healthy);
+        assert!(health.latency_ms < DB_HEALTH_TIMEOUT.as_millis() as u32);
+
+        pool.close().await;
+        assert!(!check_db(&pool, DB_HEALTH_TIMEOUT).await.healthy);
+    }
+}
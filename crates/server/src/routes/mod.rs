@@ -2,10 +2,17 @@ use axum::{
     Router,
     routing::{IntoMakeService, get},
 };
+use deployment::Deployment;
+use services::services::{auth::AuthContext, remote_client::RemoteClient};
 use tower_http::validate_request::ValidateRequestHeaderLayer;
 
-use crate::{DeploymentImpl, middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{self, RateLimiter},
+};
 
+pub mod agents;
 pub mod approvals;
 pub mod config;
 pub mod containers;
@@ -21,6 +28,7 @@ pub mod organizations;
 pub mod projects;
 pub mod repo;
 pub mod scheduled_executions;
+pub mod scheduler;
 pub mod scratch;
 pub mod sessions;
 pub mod tags;
@@ -28,24 +36,34 @@ pub mod task_attempts;
 pub mod tasks;
 pub mod terminal;
 
+/// Requests per minute forwarded to the remote server, overridable via `VK_REMOTE_RATE_LIMIT`.
+const DEFAULT_REMOTE_RATE_LIMIT: u32 = 120;
+
 pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
+    // One budget shared by every route that proxies to the remote server
+    let remote_limiter =
+        RateLimiter::per_minute_from_env("VK_REMOTE_RATE_LIMIT", DEFAULT_REMOTE_RATE_LIMIT);
+
     // Create routers with different middleware layers
     let base_routes = Router::new()
         .route("/health", get(health::health_check))
+        .route("/health/db", get(health::db_health_check))
         .merge(config::router())
         .merge(containers::router(&deployment))
-        .merge(projects::router(&deployment))
+        .merge(projects::router(&deployment, remote_limiter.clone()))
         .merge(tasks::router(&deployment))
         .merge(task_attempts::router(&deployment))
         .merge(execution_processes::router(&deployment))
         .merge(tags::router(&deployment))
         .merge(oauth::router())
-        .merge(organizations::router())
+        .merge(organizations::router(remote_limiter))
         .merge(filesystem::router())
         .merge(repo::router())
         .merge(events::router(&deployment))
+        .merge(agents::router())
         .merge(approvals::router())
         .merge(scheduled_executions::router(&deployment))
+        .merge(scheduler::router())
         .merge(scratch::router(&deployment))
         .merge(sessions::router(&deployment))
         .merge(terminal::router())
@@ -61,3 +79,55 @@ pub fn router(deployment: DeploymentImpl) -> IntoMakeService<Router> {
         .nest("/api", base_routes)
         .into_make_service()
 }
+
+/// Remote client for routes that proxy to the remote server. Fails with a 401
+/// carrying `ApiErrorCode::AuthRequired` when nobody is signed in, rather than
+/// letting the remote call fail with a less specific error.
+pub async fn authed_remote_client(deployment: &DeploymentImpl) -> Result<RemoteClient, ApiError> {
+    let client = deployment.remote_client()?;
+    ensure_signed_in(deployment.auth_context()).await?;
+    Ok(client)
+}
+
+async fn ensure_signed_in(auth_context: &AuthContext) -> Result<(), ApiError> {
+    if auth_context.get_credentials().await.is_none() {
+        return Err(ApiError::Unauthorized);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use services::services::oauth_credentials::{Credentials, OAuthCredentials};
+    use tokio::sync::RwLock;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn signed_out_requests_are_rejected_before_reaching_the_remote() {
+        let path =
+            std::env::temp_dir().join(format!("vk-credentials-{}.json", uuid::Uuid::new_v4()));
+        let auth_context = AuthContext::new(
+            Arc::new(OAuthCredentials::new(path.clone())),
+            Arc::new(RwLock::new(None)),
+        );
+        assert!(matches!(
+            ensure_signed_in(&auth_context).await,
+            Err(ApiError::Unauthorized)
+        ));
+
+        auth_context
+            .save_credentials(&Credentials {
+                access_token: Some("token".to_string()),
+                refresh_token: "refresh".to_string(),
+                expires_at: None,
+            })
+            .await
+            .unwrap();
+        assert!(ensure_signed_in(&auth_context).await.is_ok());
+
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,11 +1,13 @@
 use axum::{
     Router,
-    extract::{Json, Path, State},
+    extract::{Json, Path, Query, State},
     http::StatusCode,
+    middleware::from_fn_with_state,
     response::Json as ResponseJson,
     routing::{delete, get, patch, post},
 };
 use deployment::Deployment;
+use serde::Deserialize;
 use utils::{
     api::{
         organizations::{
@@ -15,15 +17,22 @@ use utils::{
             ListOrganizationsResponse, Organization, RevokeInvitationRequest,
             UpdateMemberRoleRequest, UpdateMemberRoleResponse, UpdateOrganizationRequest,
         },
-        projects::RemoteProject,
+        projects::{RemoteProject, RemoteProjectStatus},
     },
     response::ApiResponse,
 };
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{RateLimiter, rate_limit},
+    routes::authed_remote_client,
+};
 
-pub fn router() -> Router<DeploymentImpl> {
+/// `remote_limiter` throttles every route here, since they all forward to the
+/// remote server.
+pub fn router(remote_limiter: RateLimiter) -> Router<DeploymentImpl> {
     Router::new()
         .route("/organizations", get(list_organizations))
         .route("/organizations", post(create_organization))
@@ -34,6 +43,7 @@ pub fn router() -> Router<DeploymentImpl> {
             "/organizations/{org_id}/projects",
             get(list_organization_projects),
         )
+        .route("/project-statuses", get(list_project_statuses))
         .route(
             "/organizations/{org_id}/invitations",
             post(create_invitation),
@@ -54,23 +64,42 @@ pub fn router() -> Router<DeploymentImpl> {
             "/organizations/{org_id}/members/{user_id}/role",
             patch(update_member_role),
         )
+        .layer(from_fn_with_state(remote_limiter, rate_limit))
 }
 
 async fn list_organization_projects(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<Vec<RemoteProject>>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.list_projects(org_id).await?;
 
     Ok(ResponseJson(ApiResponse::success(response.projects)))
 }
 
+#[derive(Debug, Deserialize)]
+struct ProjectStatusesQuery {
+    project_id: Uuid,
+}
+
+async fn list_project_statuses(
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<ProjectStatusesQuery>,
+) -> Result<ResponseJson<ApiResponse<Vec<RemoteProjectStatus>>>, ApiError> {
+    let client = authed_remote_client(&deployment).await?;
+
+    let response = client.list_project_statuses(query.project_id).await?;
+
+    Ok(ResponseJson(ApiResponse::success(
+        response.project_statuses,
+    )))
+}
+
 async fn list_organizations(
     State(deployment): State<DeploymentImpl>,
 ) -> Result<ResponseJson<ApiResponse<ListOrganizationsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.list_organizations().await?;
 
@@ -81,7 +110,7 @@ async fn get_organization(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<GetOrganizationResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.get_organization(id).await?;
 
@@ -92,7 +121,7 @@ async fn create_organization(
     State(deployment): State<DeploymentImpl>,
     Json(request): Json<CreateOrganizationRequest>,
 ) -> Result<ResponseJson<ApiResponse<CreateOrganizationResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.create_organization(&request).await?;
 
@@ -113,7 +142,7 @@ async fn update_organization(
     Path(id): Path<Uuid>,
     Json(request): Json<UpdateOrganizationRequest>,
 ) -> Result<ResponseJson<ApiResponse<Organization>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.update_organization(id, &request).await?;
 
@@ -124,7 +153,7 @@ async fn delete_organization(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
 ) -> Result<StatusCode, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     client.delete_organization(id).await?;
 
@@ -136,7 +165,7 @@ async fn create_invitation(
     Path(org_id): Path<Uuid>,
     Json(request): Json<CreateInvitationRequest>,
 ) -> Result<ResponseJson<ApiResponse<CreateInvitationResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.create_invitation(org_id, &request).await?;
 
@@ -158,13 +187,15 @@ async fn list_invitations(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<ListInvitationsResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.list_invitations(org_id).await?;
 
     Ok(ResponseJson(ApiResponse::success(response)))
 }
 
+/// Public: invitees open the link before signing in, so no credentials are
+/// required here.
 async fn get_invitation(
     State(deployment): State<DeploymentImpl>,
     Path(token): Path<String>,
@@ -181,7 +212,7 @@ async fn revoke_invitation(
     Path(org_id): Path<Uuid>,
     Json(payload): Json<RevokeInvitationRequest>,
 ) -> Result<StatusCode, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     client
         .revoke_invitation(org_id, payload.invitation_id)
@@ -194,7 +225,7 @@ async fn accept_invitation(
     State(deployment): State<DeploymentImpl>,
     Path(invitation_token): Path<String>,
 ) -> Result<ResponseJson<ApiResponse<AcceptInvitationResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.accept_invitation(&invitation_token).await?;
 
@@ -205,7 +236,7 @@ async fn list_members(
     State(deployment): State<DeploymentImpl>,
     Path(org_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<ListMembersResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.list_members(org_id).await?;
 
@@ -216,7 +247,7 @@ async fn remove_member(
     State(deployment): State<DeploymentImpl>,
     Path((org_id, user_id)): Path<(Uuid, Uuid)>,
 ) -> Result<StatusCode, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     client.remove_member(org_id, user_id).await?;
 
@@ -228,7 +259,7 @@ async fn update_member_role(
     Path((org_id, user_id)): Path<(Uuid, Uuid)>,
     Json(request): Json<UpdateMemberRoleRequest>,
 ) -> Result<ResponseJson<ApiResponse<UpdateMemberRoleResponse>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let response = client.update_member_role(org_id, user_id, &request).await?;
 
@@ -1,4 +1,4 @@
-use std::path::PathBuf;
+use std::{future::Future, path::PathBuf};
 
 use anyhow;
 use axum::{
@@ -13,17 +13,19 @@ use axum::{
     routing::{get, post},
 };
 use db::models::{
+    execution_process::{ExecutionProcess, ExecutionProcessStatus},
     project::{CreateProject, Project, ProjectError, SearchResult, UpdateProject},
     project_repo::{CreateProjectRepo, ProjectRepo},
     repo::Repo,
 };
 use deployment::Deployment;
 use futures_util::{SinkExt, StreamExt, TryStreamExt};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use services::services::{
-    file_search::SearchQuery, project::ProjectServiceError,
+    container::ContainerService, file_search::SearchQuery, project::ProjectServiceError,
     remote_client::CreateRemoteProjectPayload,
 };
+use sqlx::SqlitePool;
 use ts_rs::TS;
 use utils::{
     api::projects::{RemoteProject, RemoteProjectMembersResponse},
@@ -31,7 +33,12 @@ use utils::{
 };
 use uuid::Uuid;
 
-use crate::{DeploymentImpl, error::ApiError, middleware::load_project_middleware};
+use crate::{
+    DeploymentImpl,
+    error::ApiError,
+    middleware::{RateLimiter, load_project_middleware, rate_limit},
+    routes::authed_remote_client,
+};
 
 #[derive(Deserialize, TS)]
 pub struct LinkToExistingRequest {
@@ -104,7 +111,7 @@ pub async fn link_project_to_existing_remote(
     State(deployment): State<DeploymentImpl>,
     Json(payload): Json<LinkToExistingRequest>,
 ) -> Result<ResponseJson<ApiResponse<Project>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let remote_project = client.get_project(payload.remote_project_id).await?;
 
@@ -125,7 +132,7 @@ pub async fn create_and_link_remote_project(
         ));
     }
 
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let remote_project = client
         .create_project(&CreateRemoteProjectPayload {
@@ -156,7 +163,7 @@ pub async fn get_remote_project_by_id(
     State(deployment): State<DeploymentImpl>,
     Path(remote_project_id): Path<Uuid>,
 ) -> Result<ResponseJson<ApiResponse<RemoteProject>>, ApiError> {
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let remote_project = client.get_project(remote_project_id).await?;
 
@@ -171,7 +178,7 @@ pub async fn get_project_remote_members(
         ApiError::Conflict("Project is not linked to a remote project".to_string())
     })?;
 
-    let client = deployment.remote_client()?;
+    let client = authed_remote_client(&deployment).await?;
 
     let remote_project = client.get_project(remote_project_id).await?;
     let members = client
@@ -568,20 +575,95 @@ pub async fn get_project_repository(
     }
 }
 
-pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
+#[derive(Debug, Serialize, TS)]
+pub struct CancelAllAgentsResponse {
+    pub cancelled: usize,
+}
+
+/// Kill switch that stops every running coding agent in the project.
+pub async fn cancel_all_agents(
+    Extension(project): Extension<Project>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<CancelAllAgentsResponse>>, ApiError> {
+    let container = deployment.container();
+    let cancelled = stop_running_agents(&deployment.db().pool, project.id, |process| async move {
+        container
+            .stop_execution(&process, ExecutionProcessStatus::Killed)
+            .await
+    })
+    .await?;
+
+    deployment
+        .track_if_analytics_allowed(
+            "project_agents_cancelled",
+            serde_json::json!({
+                "project_id": project.id.to_string(),
+                "cancelled": cancelled,
+            }),
+        )
+        .await;
+
+    Ok(ResponseJson(ApiResponse::success(
+        CancelAllAgentsResponse { cancelled },
+    )))
+}
+
+/// Call `stop` on every running coding agent in the project, returning how
+/// many were stopped. A failure to stop one agent does not stop the rest.
+async fn stop_running_agents<F, Fut, E>(
+    pool: &SqlitePool,
+    project_id: Uuid,
+    stop: F,
+) -> Result<usize, ApiError>
+where
+    F: Fn(ExecutionProcess) -> Fut,
+    Fut: Future<Output = Result<(), E>>,
+    E: std::fmt::Display,
+{
+    let running = ExecutionProcess::find_running_agents_by_project(pool, project_id).await?;
+
+    let mut cancelled = 0;
+    for process in running {
+        let process_id = process.id;
+        match stop(process).await {
+            Ok(()) => cancelled += 1,
+            Err(e) => tracing::warn!(
+                "Failed to stop execution process {} for project {}: {}",
+                process_id,
+                project_id,
+                e
+            ),
+        }
+    }
+    Ok(cancelled)
+}
+
+/// `remote_limiter` throttles the routes that call the remote server.
+pub fn router(deployment: &DeploymentImpl, remote_limiter: RateLimiter) -> Router<DeploymentImpl> {
+    let remote_limit = from_fn_with_state(remote_limiter, rate_limit);
+
     let project_id_router = Router::new()
         .route(
             "/",
             get(get_project).put(update_project).delete(delete_project),
         )
-        .route("/remote/members", get(get_project_remote_members))
+        .route(
+            "/remote/members",
+            get(get_project_remote_members).layer(remote_limit.clone()),
+        )
         .route("/search", get(search_project_files))
         .route("/open-editor", post(open_project_in_editor))
+        .route("/cancel-all-agents", post(cancel_all_agents))
         .route(
             "/link",
-            post(link_project_to_existing_remote).delete(unlink_project),
+            post(link_project_to_existing_remote)
+                .layer(remote_limit.clone())
+                .delete(unlink_project),
+        )
+        .route(
+            "/link/create",
+            post(create_and_link_remote_project).layer(remote_limit.clone()),
         )
-        .route("/link/create", post(create_and_link_remote_project))
         .route(
             "/repositories",
             get(get_project_repositories).post(add_project_repository),
@@ -602,6 +684,114 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     Router::new().nest("/projects", projects_router).route(
         "/remote-projects/{remote_project_id}",
-        get(get_remote_project_by_id),
+        get(get_remote_project_by_id).layer(remote_limit),
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use db::{
+        DBService,
+        models::{
+            execution_process::{CreateExecutionProcess, ExecutionProcessRunReason},
+            session::{CreateSession, Session},
+            task::{CreateTask, Task},
+            workspace::{CreateWorkspace, Workspace},
+        },
+    };
+    use executors::actions::{
+        ExecutorAction, ExecutorActionType,
+        script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
+    };
+    use tokio::sync::Mutex;
+
+    use super::*;
+
+    /// A running coding agent in its own workspace and session for `task_id`.
+    async fn running_agent(pool: &SqlitePool, task_id: Uuid) -> ExecutionProcess {
+        let workspace = Workspace::create(
+            pool,
+            &CreateWorkspace {
+                branch: "vk/agent".to_string(),
+                agent_working_dir: None,
+            },
+            Uuid::new_v4(),
+            task_id,
+        )
+        .await
+        .unwrap();
+        let session = Session::create(
+            pool,
+            &CreateSession { executor: None },
+            Uuid::new_v4(),
+            workspace.id,
+        )
+        .await
+        .unwrap();
+        let action = ExecutorAction::new(
+            ExecutorActionType::ScriptRequest(ScriptRequest {
+                script: "true".to_string(),
+                language: ScriptRequestLanguage::Bash,
+                context: ScriptContext::SetupScript,
+                working_dir: None,
+            }),
+            None,
+        );
+        ExecutionProcess::create(
+            pool,
+            &CreateExecutionProcess {
+                session_id: session.id,
+                executor_action: action,
+                run_reason: ExecutionProcessRunReason::CodingAgent,
+            },
+            Uuid::new_v4(),
+            &[],
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn every_running_agent_in_the_project_is_stopped() {
+        let pool = DBService::new_in_memory().await.unwrap().pool;
+        let project_id = Uuid::new_v4();
+        Project::create(
+            &pool,
+            &CreateProject {
+                name: "project".to_string(),
+                repositories: Vec::new(),
+            },
+            project_id,
+        )
+        .await
+        .unwrap();
+        let task_id = Uuid::new_v4();
+        Task::create(
+            &pool,
+            &CreateTask::from_title_description(project_id, "task".to_string(), None),
+            task_id,
+        )
+        .await
+        .unwrap();
+        let first = running_agent(&pool, task_id).await;
+        let second = running_agent(&pool, task_id).await;
+
+        let stopped = Mutex::new(Vec::new());
+        let cancelled = stop_running_agents(&pool, project_id, |process| {
+            let stopped = &stopped;
+            async move {
+                stopped.lock().await.push(process.id);
+                Ok::<_, ApiError>(())
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(cancelled, 2);
+        let mut stopped = stopped.into_inner();
+        let mut expected = vec![first.id, second.id];
+        stopped.sort();
+        expected.sort();
+        assert_eq!(stopped, expected);
+    }
+}
@@ -2,11 +2,18 @@ use axum::{
     Json, Router,
     extract::{Path, Query, State},
     response::Json as ResponseJson,
-    routing::get,
+    routing::{get, post},
 };
 use chrono::{DateTime, Utc};
-use db::models::scheduled_execution::{ScheduledExecution, ScheduledExecutionStatus};
+use db::models::{
+    repo::Repo,
+    scheduled_execution::{
+        ScheduledExecution, ScheduledExecutionEvent, ScheduledExecutionStatus, ScheduledRepoInput,
+    },
+};
+use executors::profile::{ExecutorConfigs, ExecutorProfileId};
 use serde::{Deserialize, Serialize};
+use services::services::scheduler;
 use ts_rs::TS;
 use utils::response::ApiResponse;
 use uuid::Uuid;
@@ -15,26 +22,67 @@ use crate::{DeploymentImpl, error::ApiError};
 
 use deployment::Deployment;
 
+/// Source recorded when a create request doesn't name one.
+const DEFAULT_SOURCE: &str = "api";
+
+/// Longest label accepted on create, in characters.
+const MAX_LABEL_LEN: usize = 100;
+
 #[derive(Debug, Serialize, Deserialize, TS)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateScheduledExecutionRequest {
     pub task_id: Uuid,
     pub project_id: Uuid,
-    pub scheduled_at: DateTime<Utc>,
+    /// When to fire. Either this or `delay_seconds` must be set, not both.
+    #[serde(default)]
+    #[ts(optional)]
+    pub scheduled_at: Option<DateTime<Utc>>,
+    /// Fire this many seconds after the request is received.
+    #[serde(default)]
+    #[ts(optional)]
+    pub delay_seconds: Option<u32>,
     pub executor_profile_id: serde_json::Value,
     pub repos: Vec<ScheduledRepoInput>,
-}
-
-#[derive(Debug, Serialize, Deserialize, TS)]
-#[serde(rename_all = "camelCase")]
-pub struct ScheduledRepoInput {
-    pub repo_id: Uuid,
-    pub target_branch: String,
+    /// Who is creating the schedule, e.g. `ui` or `mcp`. Defaults to `api`.
+    #[serde(default)]
+    #[ts(optional)]
+    pub source: Option<String>,
+    /// Free-text description shown in the schedule list, e.g. "nightly lint".
+    #[serde(default)]
+    #[ts(optional)]
+    pub label: Option<String>,
+    /// Fire with the executor profile's settings as they are now, rather than
+    /// whatever they are when the schedule fires. Defaults to false.
+    #[serde(default)]
+    #[ts(optional)]
+    pub freeze_executor_config: Option<bool>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ScheduledExecutionQuery {
     pub project_id: Uuid,
+    #[serde(default)]
+    pub status: Option<ScheduledExecutionStatus>,
+    #[serde(default)]
+    pub source: Option<String>,
+    #[serde(default)]
+    pub created_after: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub created_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CancelScheduledExecutionQuery {
+    /// Also cancel executions that already fired. The workspace they started is left alone.
+    #[serde(default)]
+    pub force: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RerunScheduledExecutionQuery {
+    /// Fire this many seconds from now instead of right away.
+    #[serde(default)]
+    pub delay_seconds: Option<u32>,
 }
 
 #[axum::debug_handler]
@@ -50,12 +98,66 @@ pub async fn create_scheduled_execution(
         ));
     }
 
-    if payload.scheduled_at <= Utc::now() {
+    let repo_ids: Vec<Uuid> = payload.repos.iter().map(|r| r.repo_id).collect();
+    let duplicates = Repo::duplicate_ids(&repo_ids);
+    if !duplicates.is_empty() {
+        let duplicates: Vec<String> = duplicates.iter().map(Uuid::to_string).collect();
+        return Err(ApiError::BadRequest(format!(
+            "Repositories listed more than once: {}",
+            duplicates.join(", ")
+        )));
+    }
+    let found = Repo::find_by_ids(pool, &repo_ids).await?;
+    let missing = Repo::missing_ids(&repo_ids, &found);
+    if !missing.is_empty() {
+        let missing: Vec<String> = missing.iter().map(Uuid::to_string).collect();
+        return Err(ApiError::BadRequest(format!(
+            "Repositories not found: {}",
+            missing.join(", ")
+        )));
+    }
+
+    let now = Utc::now();
+    let scheduled_at = resolve_scheduled_at(payload.scheduled_at, payload.delay_seconds, now)?;
+    if scheduled_at <= now {
         return Err(ApiError::BadRequest(
             "scheduled_at must be in the future".to_string(),
         ));
     }
 
+    let source = match payload.source.as_deref().map(str::trim) {
+        None => DEFAULT_SOURCE.to_string(),
+        Some("") => {
+            return Err(ApiError::BadRequest("source must not be empty".to_string()));
+        }
+        Some(source) => source.to_ascii_lowercase(),
+    };
+    let label = normalize_label(payload.label.as_deref())?;
+
+    let executor_profile_id: ExecutorProfileId =
+        serde_json::from_value(payload.executor_profile_id.clone())
+            .map_err(|e| ApiError::BadRequest(format!("Invalid executor_profile_id: {}", e)))?;
+    let configs = ExecutorConfigs::get_cached();
+    executor_profile_id
+        .resolve(&configs)
+        .map_err(ApiError::BadRequest)?;
+    let executor_config_json = if payload.freeze_executor_config.unwrap_or(false) {
+        let config = configs
+            .get_coding_agent(&executor_profile_id)
+            .ok_or_else(|| {
+                ApiError::BadRequest(format!(
+                    "Executor profile {} has no configuration",
+                    executor_profile_id
+                ))
+            })?;
+        Some(
+            serde_json::to_string(&config)
+                .map_err(|e| ApiError::BadRequest(format!("Invalid executor config: {}", e)))?,
+        )
+    } else {
+        None
+    };
+
     let id = Uuid::new_v4();
     let executor_profile_id_json = serde_json::to_string(&payload.executor_profile_id)
         .map_err(|e| ApiError::BadRequest(format!("Invalid executor_profile_id: {}", e)))?;
@@ -67,30 +169,85 @@ pub async fn create_scheduled_execution(
         id,
         payload.task_id,
         payload.project_id,
-        payload.scheduled_at,
+        scheduled_at,
         &executor_profile_id_json,
         &repos_json,
+        &source,
+        label.as_deref(),
+        executor_config_json.as_deref(),
     )
     .await?;
 
     tracing::info!(
-        "Created scheduled execution {} for task {} at {}",
+        "Created scheduled execution {} for task {} at {} (source: {})",
         scheduled.id,
         scheduled.task_id,
-        scheduled.scheduled_at
+        scheduled.scheduled_at,
+        scheduled.source
     );
 
     Ok(ResponseJson(ApiResponse::success(scheduled)))
 }
 
+/// Pick the fire time from an absolute `scheduled_at` or a delay relative to `now`.
+fn resolve_scheduled_at(
+    scheduled_at: Option<DateTime<Utc>>,
+    delay_seconds: Option<u32>,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, ApiError> {
+    match (scheduled_at, delay_seconds) {
+        (Some(scheduled_at), None) => Ok(scheduled_at),
+        (None, Some(delay)) => Ok(now + chrono::Duration::seconds(i64::from(delay))),
+        (Some(_), Some(_)) => Err(ApiError::BadRequest(
+            "Set either scheduled_at or delay_seconds, not both".to_string(),
+        )),
+        (None, None) => Err(ApiError::BadRequest(
+            "One of scheduled_at or delay_seconds is required".to_string(),
+        )),
+    }
+}
+
+/// Trim the label, treating a blank one as absent, and enforce [`MAX_LABEL_LEN`].
+fn normalize_label(label: Option<&str>) -> Result<Option<String>, ApiError> {
+    let Some(label) = label.map(str::trim).filter(|label| !label.is_empty()) else {
+        return Ok(None);
+    };
+    if label.chars().count() > MAX_LABEL_LEN {
+        return Err(ApiError::BadRequest(format!(
+            "label must be at most {} characters",
+            MAX_LABEL_LEN
+        )));
+    }
+    Ok(Some(label.to_string()))
+}
+
 #[axum::debug_handler]
 pub async fn list_scheduled_executions(
     State(deployment): State<DeploymentImpl>,
     Query(query): Query<ScheduledExecutionQuery>,
 ) -> Result<ResponseJson<ApiResponse<Vec<ScheduledExecution>>>, ApiError> {
     let pool = &deployment.db().pool;
-    let executions =
-        ScheduledExecution::find_by_project_id(pool, query.project_id).await?;
+    let executions = match query {
+        // Status alone is the common "failed/fired schedules" view; serve it from its index
+        ScheduledExecutionQuery {
+            project_id,
+            status: Some(status),
+            source: None,
+            created_after: None,
+            created_before: None,
+        } => ScheduledExecution::find_by_status(pool, project_id, status).await?,
+        query => {
+            ScheduledExecution::find_by_project_id_filtered(
+                pool,
+                query.project_id,
+                query.status,
+                query.source.as_deref(),
+                query.created_after,
+                query.created_before,
+            )
+            .await?
+        }
+    };
     Ok(ResponseJson(ApiResponse::success(executions)))
 }
 
@@ -108,10 +265,26 @@ pub async fn get_scheduled_execution(
     Ok(ResponseJson(ApiResponse::success(scheduled)))
 }
 
+#[axum::debug_handler]
+pub async fn get_scheduled_execution_history(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<Vec<ScheduledExecutionEvent>>>, ApiError> {
+    let pool = &deployment.db().pool;
+    ScheduledExecution::find_by_id(pool, id)
+        .await?
+        .ok_or(ApiError::BadRequest(
+            "Scheduled execution not found".to_string(),
+        ))?;
+    let events = ScheduledExecution::history(pool, id).await?;
+    Ok(ResponseJson(ApiResponse::success(events)))
+}
+
 #[axum::debug_handler]
 pub async fn cancel_scheduled_execution(
     State(deployment): State<DeploymentImpl>,
     Path(id): Path<Uuid>,
+    Query(query): Query<CancelScheduledExecutionQuery>,
 ) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
     let pool = &deployment.db().pool;
     let scheduled = ScheduledExecution::find_by_id(pool, id)
@@ -120,23 +293,86 @@ pub async fn cancel_scheduled_execution(
             "Scheduled execution not found".to_string(),
         ))?;
 
-    if scheduled.status != ScheduledExecutionStatus::Pending {
-        return Err(ApiError::BadRequest(format!(
+    ensure_cancellable(&scheduled.status, query.force)?;
+
+    ScheduledExecution::mark_cancelled(pool, id).await?;
+    // An abort requested earlier has nothing left to stop
+    deployment.scheduler_control().clear_abort(id);
+
+    tracing::info!(
+        "Cancelled scheduled execution {} (was {}{})",
+        id,
+        scheduled.status,
+        if query.force { ", forced" } else { "" }
+    );
+
+    Ok(ResponseJson(ApiResponse::success(())))
+}
+
+/// Pending executions can always be cancelled; fired ones only when `force` is set.
+fn ensure_cancellable(status: &ScheduledExecutionStatus, force: bool) -> Result<(), ApiError> {
+    match status {
+        ScheduledExecutionStatus::Pending => Ok(()),
+        ScheduledExecutionStatus::Fired if force => Ok(()),
+        _ => Err(ApiError::BadRequest(format!(
             "Cannot cancel a scheduled execution with status '{}'",
-            scheduled.status
-        )));
+            status
+        ))),
     }
+}
 
-    ScheduledExecution::mark_cancelled(pool, id).await?;
+/// Abort a pending execution, including one the scheduler is firing right now.
+/// The scheduler marks it cancelled at its next checkpoint instead of starting it.
+#[axum::debug_handler]
+pub async fn abort_scheduled_execution(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+) -> Result<ResponseJson<ApiResponse<()>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let scheduled = ScheduledExecution::find_by_id(pool, id)
+        .await?
+        .ok_or(ApiError::BadRequest(
+            "Scheduled execution not found".to_string(),
+        ))?;
+
+    ensure_cancellable(&scheduled.status, false)?;
+
+    deployment.scheduler_control().request_abort(id);
 
-    tracing::info!("Cancelled scheduled execution {}", id);
+    tracing::info!("Requested abort of scheduled execution {}", id);
 
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Schedule a fresh run of an execution that already fired, with the same
+/// task, repos and profile. See [`scheduler::schedule_rerun`] for how the task
+/// status is handled.
+#[axum::debug_handler]
+pub async fn rerun_scheduled_execution(
+    State(deployment): State<DeploymentImpl>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<RerunScheduledExecutionQuery>,
+) -> Result<ResponseJson<ApiResponse<ScheduledExecution>>, ApiError> {
+    let scheduled_at =
+        Utc::now() + chrono::Duration::seconds(i64::from(query.delay_seconds.unwrap_or(0)));
+    let rerun = scheduler::schedule_rerun(&deployment.db().pool, id, scheduled_at).await?;
+
+    tracing::info!(
+        "Scheduled rerun {} of scheduled execution {} at {}",
+        rerun.id,
+        id,
+        rerun.scheduled_at
+    );
+
+    Ok(ResponseJson(ApiResponse::success(rerun)))
+}
+
 pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let item_router = Router::new()
-        .route("/", get(get_scheduled_execution).delete(cancel_scheduled_execution));
+        .route("/", get(get_scheduled_execution).delete(cancel_scheduled_execution))
+        .route("/history", get(get_scheduled_execution_history))
+        .route("/abort", post(abort_scheduled_execution))
+        .route("/rerun", post(rerun_scheduled_execution));
 
     let collection_router = Router::new()
         .route("/", get(list_scheduled_executions).post(create_scheduled_execution))
@@ -144,3 +380,48 @@ pub fn router(_deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     Router::new().nest("/scheduled-executions", collection_router)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_is_trimmed_and_length_capped() {
+        assert_eq!(
+            normalize_label(Some("  nightly lint ")).unwrap(),
+            Some("nightly lint".to_string())
+        );
+        assert_eq!(normalize_label(Some("   ")).unwrap(), None);
+        assert_eq!(normalize_label(None).unwrap(), None);
+
+        let at_cap = "a".repeat(MAX_LABEL_LEN);
+        assert_eq!(normalize_label(Some(&at_cap)).unwrap(), Some(at_cap.clone()));
+        assert!(matches!(
+            normalize_label(Some(&format!("{at_cap}a"))),
+            Err(ApiError::BadRequest(_))
+        ));
+    }
+
+    #[test]
+    fn delay_is_added_to_now() {
+        let now = Utc::now();
+        assert_eq!(
+            resolve_scheduled_at(None, Some(7200), now).unwrap(),
+            now + chrono::Duration::hours(2)
+        );
+        assert_eq!(resolve_scheduled_at(Some(now), None, now).unwrap(), now);
+        assert!(resolve_scheduled_at(Some(now), Some(60), now).is_err());
+        assert!(resolve_scheduled_at(None, None, now).is_err());
+    }
+
+    #[test]
+    fn only_force_cancels_fired_executions() {
+        assert!(ensure_cancellable(&ScheduledExecutionStatus::Pending, false).is_ok());
+        assert!(matches!(
+            ensure_cancellable(&ScheduledExecutionStatus::Fired, false),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(ensure_cancellable(&ScheduledExecutionStatus::Fired, true).is_ok());
+        assert!(ensure_cancellable(&ScheduledExecutionStatus::Cancelled, true).is_err());
+    }
+}
@@ -0,0 +1,44 @@
+use axum::{
+    Router,
+    extract::State,
+    response::Json as ResponseJson,
+    routing::{get, post},
+};
+use deployment::Deployment;
+use services::services::scheduler::SchedulerState;
+use utils::response::ApiResponse;
+
+use crate::{DeploymentImpl, error::ApiError};
+
+pub async fn get_scheduler_state(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<SchedulerState>>, ApiError> {
+    Ok(ResponseJson(ApiResponse::success(
+        deployment.scheduler_control().state(),
+    )))
+}
+
+pub async fn pause_scheduler(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<SchedulerState>>, ApiError> {
+    let control = deployment.scheduler_control();
+    control.pause();
+    tracing::info!("Scheduler paused");
+    Ok(ResponseJson(ApiResponse::success(control.state())))
+}
+
+pub async fn resume_scheduler(
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<SchedulerState>>, ApiError> {
+    let control = deployment.scheduler_control();
+    control.resume();
+    tracing::info!("Scheduler resumed");
+    Ok(ResponseJson(ApiResponse::success(control.state())))
+}
+
+pub fn router() -> Router<DeploymentImpl> {
+    Router::new()
+        .route("/scheduler", get(get_scheduler_state))
+        .route("/scheduler/pause", post(pause_scheduler))
+        .route("/scheduler/resume", post(resume_scheduler))
+}
@@ -19,6 +19,7 @@ use deployment::Deployment;
 use executors::{
     actions::{
         ExecutorAction, ExecutorActionType, coding_agent_follow_up::CodingAgentFollowUpRequest,
+        coding_agent_initial::CodingAgentInitialRequest,
     },
     profile::ExecutorProfileId,
 };
@@ -188,22 +189,12 @@ pub async fn follow_up(
         .filter(|dir| !dir.is_empty())
         .cloned();
 
-    let action_type = if let Some(agent_session_id) = latest_agent_session_id {
-        ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
-            prompt: prompt.clone(),
-            session_id: agent_session_id,
-            executor_profile_id: executor_profile_id.clone(),
-            working_dir: working_dir.clone(),
-        })
-    } else {
-        ExecutorActionType::CodingAgentInitialRequest(
-            executors::actions::coding_agent_initial::CodingAgentInitialRequest {
-                prompt,
-                executor_profile_id: executor_profile_id.clone(),
-                working_dir,
-            },
-        )
-    };
+    let action_type = follow_up_action_type(
+        latest_agent_session_id,
+        prompt,
+        executor_profile_id,
+        working_dir,
+    );
 
     let action = ExecutorAction::new(action_type, cleanup_action.map(Box::new));
 
@@ -231,11 +222,69 @@ pub async fn follow_up(
     Ok(ResponseJson(ApiResponse::success(execution_process)))
 }
 
+/// Resume the agent's own session when this session has one, otherwise start fresh.
+fn follow_up_action_type(
+    latest_agent_session_id: Option<String>,
+    prompt: String,
+    executor_profile_id: ExecutorProfileId,
+    working_dir: Option<String>,
+) -> ExecutorActionType {
+    match latest_agent_session_id {
+        Some(agent_session_id) => {
+            ExecutorActionType::CodingAgentFollowUpRequest(CodingAgentFollowUpRequest {
+                prompt,
+                session_id: agent_session_id,
+                executor_profile_id,
+                working_dir,
+            })
+        }
+        None => ExecutorActionType::CodingAgentInitialRequest(CodingAgentInitialRequest {
+            prompt,
+            executor_profile_id,
+            working_dir,
+            executor_config: None,
+        }),
+    }
+}
+
+/// Abandon a stuck session: stop whatever is running in its workspace and open
+/// a new session with the same executor. The new session has no agent session
+/// to resume, so its first follow-up starts a fresh conversation while the
+/// workspace and its worktrees are kept.
+pub async fn reset_session(
+    Extension(session): Extension<Session>,
+    State(deployment): State<DeploymentImpl>,
+) -> Result<ResponseJson<ApiResponse<Session>>, ApiError> {
+    let pool = &deployment.db().pool;
+
+    let workspace = Workspace::find_by_id(pool, session.workspace_id)
+        .await?
+        .ok_or(ApiError::Workspace(WorkspaceError::ValidationError(
+            "Workspace not found".to_string(),
+        )))?;
+
+    // Stop any running processes for this workspace (except dev server)
+    deployment.container().try_stop(&workspace, false).await;
+
+    let new_session = Session::create(
+        pool,
+        &CreateSession {
+            executor: session.executor.clone(),
+        },
+        Uuid::new_v4(),
+        workspace.id,
+    )
+    .await?;
+
+    Ok(ResponseJson(ApiResponse::success(new_session)))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let session_id_router = Router::new()
         .route("/", get(get_session))
         .route("/follow-up", post(follow_up))
         .route("/review", post(review::start_review))
+        .route("/reset", post(reset_session))
         .layer(from_fn_with_state(
             deployment.clone(),
             load_session_middleware,
@@ -248,3 +297,33 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
 
     Router::new().nest("/sessions", sessions_router)
 }
+
+#[cfg(test)]
+mod tests {
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    #[test]
+    fn follow_up_without_an_agent_session_starts_fresh() {
+        let profile = ExecutorProfileId::new(BaseCodingAgent::ClaudeCode);
+
+        // What a reset session looks like: nothing to resume
+        let action = follow_up_action_type(None, "try again".to_string(), profile.clone(), None);
+        assert!(matches!(
+            action,
+            ExecutorActionType::CodingAgentInitialRequest(ref req) if req.prompt == "try again"
+        ));
+
+        let action = follow_up_action_type(
+            Some("agent-session".to_string()),
+            "continue".to_string(),
+            profile,
+            None,
+        );
+        assert!(matches!(
+            action,
+            ExecutorActionType::CodingAgentFollowUpRequest(ref req) if req.session_id == "agent-session"
+        ));
+    }
+}
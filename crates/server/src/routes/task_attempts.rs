@@ -7,8 +7,9 @@ pub mod util;
 pub mod workspace_summary;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     path::{Path, PathBuf},
+    time::Duration,
 };
 
 use axum::{
@@ -40,8 +41,10 @@ use executors::{
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
     executors::{CodingAgent, ExecutorError},
+    logs::{NormalizedEntry, utils::patch::extract_normalized_entry_from_patch},
     profile::{ExecutorConfigs, ExecutorProfileId},
 };
+use futures_util::StreamExt;
 use git2::BranchType;
 use serde::{Deserialize, Serialize};
 use services::services::{
@@ -52,7 +55,7 @@ use services::services::{
 };
 use sqlx::Error as SqlxError;
 use ts_rs::TS;
-use utils::response::ApiResponse;
+use utils::{log_msg::LogMsg, response::ApiResponse};
 use uuid::Uuid;
 
 use crate::{
@@ -1753,6 +1756,90 @@ pub async fn mark_seen(
     Ok(ResponseJson(ApiResponse::success(())))
 }
 
+/// Upper bound on re-normalizing a finished run's stored logs before
+/// returning what has been collected so far.
+const LOG_SNAPSHOT_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Debug, Deserialize)]
+pub struct WorkspaceLogsQuery {
+    #[serde(default)]
+    pub tail: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, TS)]
+pub struct WorkspaceLogsResponse {
+    pub execution_process_id: Option<Uuid>,
+    pub entries: Vec<NormalizedEntry>,
+}
+
+/// Snapshot of the normalized log entries of the workspace's latest coding
+/// agent run, optionally limited to the last `tail` entries.
+pub async fn get_workspace_logs(
+    Extension(workspace): Extension<Workspace>,
+    State(deployment): State<DeploymentImpl>,
+    Query(query): Query<WorkspaceLogsQuery>,
+) -> Result<ResponseJson<ApiResponse<WorkspaceLogsResponse>>, ApiError> {
+    let pool = &deployment.db().pool;
+    let Some(process) = ExecutionProcess::find_latest_by_workspace_and_run_reason(
+        pool,
+        workspace.id,
+        &ExecutionProcessRunReason::CodingAgent,
+    )
+    .await?
+    else {
+        return Ok(ResponseJson(ApiResponse::success(WorkspaceLogsResponse {
+            execution_process_id: None,
+            entries: vec![],
+        })));
+    };
+
+    let container = deployment.container();
+    if let Some(tail) = query.tail {
+        // Served from the workspace's tail when it holds enough of this run
+        let mut entries = container.workspace_log_tail(workspace.id, process.id).await;
+        if entries.len() >= tail {
+            entries.drain(..entries.len() - tail);
+            return Ok(ResponseJson(ApiResponse::success(WorkspaceLogsResponse {
+                execution_process_id: Some(process.id),
+                entries,
+            })));
+        }
+    }
+
+    let mut entries = BTreeMap::new();
+    let mut collect = |msg: &LogMsg| {
+        if let LogMsg::JsonPatch(patch) = msg
+            && let Some((index, entry)) = extract_normalized_entry_from_patch(patch)
+        {
+            entries.insert(index, entry);
+        }
+    };
+    if let Some(store) = container.get_msg_store_by_id(&process.id).await {
+        // Still in memory: take what has been normalized so far without
+        // waiting for the run to produce more
+        store.get_history().iter().for_each(&mut collect);
+    } else if let Some(stream) = container.stream_normalized_logs(&process.id).await {
+        // Stored logs are normalized again, which ends with `Finished`
+        let mut stream = stream.take_until(tokio::time::sleep(LOG_SNAPSHOT_TIMEOUT));
+        while let Some(Ok(msg)) = stream.next().await {
+            if matches!(msg, LogMsg::Finished) {
+                break;
+            }
+            collect(&msg);
+        }
+    }
+
+    let mut entries: Vec<NormalizedEntry> = entries.into_values().collect();
+    if let Some(tail) = query.tail {
+        entries.drain(..entries.len().saturating_sub(tail));
+    }
+
+    Ok(ResponseJson(ApiResponse::success(WorkspaceLogsResponse {
+        execution_process_id: Some(process.id),
+        entries,
+    })))
+}
+
 pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
     let task_attempt_id_router = Router::new()
         .route(
@@ -1784,6 +1871,7 @@ pub fn router(deployment: &DeploymentImpl) -> Router<DeploymentImpl> {
         .route("/repos", get(get_task_attempt_repos))
         .route("/search", get(search_workspace_files))
         .route("/first-message", get(get_first_user_message))
+        .route("/logs", get(get_workspace_logs))
         .route("/mark-seen", put(mark_seen))
         .layer(from_fn_with_state(
             deployment.clone(),
@@ -176,6 +176,7 @@ async fn trigger_pr_description_follow_up(
             prompt,
             executor_profile_id: executor_profile_id.clone(),
             working_dir,
+            executor_config: None,
         })
     };
 
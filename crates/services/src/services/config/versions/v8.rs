@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use anyhow::Error;
 use executors::{executors::BaseCodingAgent, profile::ExecutorProfileId};
 use serde::{Deserialize, Serialize};
@@ -56,6 +58,9 @@ pub struct Config {
     pub commit_reminder: bool,
     #[serde(default)]
     pub send_message_shortcut: SendMessageShortcut,
+    /// Environment variables passed to every coding agent. Executor profile env takes precedence.
+    #[serde(default)]
+    pub executor_env: HashMap<String, String>,
 }
 
 impl Config {
@@ -85,6 +90,7 @@ impl Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
             send_message_shortcut: SendMessageShortcut::default(),
+            executor_env: HashMap::new(),
         }
     }
 
@@ -139,6 +145,7 @@ impl Default for Config {
             beta_workspaces_invitation_sent: false,
             commit_reminder: false,
             send_message_shortcut: SendMessageShortcut::default(),
+            executor_env: HashMap::new(),
         }
     }
 }
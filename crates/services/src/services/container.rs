@@ -1,11 +1,12 @@
 use std::{
     collections::HashMap,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{Arc, LazyLock, Mutex},
 };
 
 use anyhow::{Error as AnyhowError, anyhow};
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use db::{
     DBService,
     models::{
@@ -35,15 +36,24 @@ use executors::{
         coding_agent_initial::CodingAgentInitialRequest,
         script::{ScriptContext, ScriptRequest, ScriptRequestLanguage},
     },
-    executors::{ExecutorError, StandardCodingAgentExecutor},
-    logs::{NormalizedEntry, NormalizedEntryError, NormalizedEntryType, utils::ConversationPatch},
+    executors::{BaseCodingAgent, CodingAgent, ExecutorError, StandardCodingAgentExecutor},
+    logs::{
+        NormalizedEntry, NormalizedEntryError, NormalizedEntryType,
+        tail::{DEFAULT_TAIL_CAPACITY, NormalizedTail, spawn_tail_writer},
+        utils::ConversationPatch,
+    },
     profile::ExecutorProfileId,
 };
 use futures::{StreamExt, future, stream::BoxStream};
 use json_patch::Patch;
-use sqlx::Error as SqlxError;
+use serde::Serialize;
+use sqlx::{Error as SqlxError, SqlitePool};
 use thiserror::Error;
-use tokio::{sync::RwLock, task::JoinHandle};
+use tokio::{
+    sync::{OwnedMutexGuard, RwLock},
+    task::JoinHandle,
+};
+use ts_rs::TS;
 use utils::{
     log_msg::LogMsg,
     msg_store::MsgStore,
@@ -59,6 +69,12 @@ use crate::services::{
 };
 pub type ContainerRef = String;
 
+// Held while a coding agent is being spawned so two callers can't start agents in the same
+// worktree at once. Entries nobody holds are pruned on the next lock, so the map only grows
+// with concurrent spawns
+static WORKSPACE_SPAWN_LOCKS: LazyLock<Mutex<HashMap<Uuid, Arc<tokio::sync::Mutex<()>>>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
 #[derive(Debug, Error)]
 pub enum ContainerError {
     #[error(transparent)]
@@ -81,14 +97,77 @@ pub enum ContainerError {
     Io(#[from] std::io::Error),
     #[error("Failed to kill process: {0}")]
     KillFailed(std::io::Error),
+    #[error("Workspace {0} is busy: a coding agent is already starting or running in it")]
+    WorkspaceBusy(Uuid),
     #[error(transparent)]
     Other(#[from] AnyhowError), // Catches any unclassified errors
 }
 
+/// Take the spawn lock for `workspace_id` without waiting; a second caller gets
+/// [`ContainerError::WorkspaceBusy`] instead of queueing behind the first.
+fn try_lock_workspace_spawn(workspace_id: Uuid) -> Result<OwnedMutexGuard<()>, ContainerError> {
+    let lock = {
+        let mut locks = WORKSPACE_SPAWN_LOCKS.lock().unwrap();
+        // Only the map refers to an unheld lock, so dropping it is safe
+        locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+        locks
+            .entry(workspace_id)
+            .or_insert_with(|| Arc::new(tokio::sync::Mutex::new(())))
+            .clone()
+    };
+    lock.try_lock_owned()
+        .map_err(|_| ContainerError::WorkspaceBusy(workspace_id))
+}
+
+/// A coding agent whose child process is still running.
+#[derive(Debug, Clone, Serialize, TS)]
+pub struct ActiveSession {
+    pub workspace_id: Uuid,
+    pub executor: BaseCodingAgent,
+    pub started_at: DateTime<Utc>,
+}
+
+impl ActiveSession {
+    /// The coding agent runs among `execution_ids`, longest running first.
+    /// Processes that aren't coding agents or are gone from the database are
+    /// skipped.
+    pub async fn for_executions(
+        pool: &SqlitePool,
+        execution_ids: impl IntoIterator<Item = Uuid>,
+    ) -> Result<Vec<Self>, SqlxError> {
+        let mut sessions = Vec::new();
+        for execution_id in execution_ids {
+            let Some(process) = ExecutionProcess::find_by_id(pool, execution_id).await? else {
+                continue;
+            };
+            let Some(executor) = process
+                .executor_action()
+                .ok()
+                .and_then(|action| action.base_executor())
+            else {
+                continue;
+            };
+            let Some(session) = process.parent_session(pool).await? else {
+                continue;
+            };
+            sessions.push(ActiveSession {
+                workspace_id: session.workspace_id,
+                executor,
+                started_at: process.started_at,
+            });
+        }
+        sessions.sort_by_key(|s| s.started_at);
+        Ok(sessions)
+    }
+}
+
 #[async_trait]
 pub trait ContainerService {
     fn msg_stores(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<MsgStore>>>>;
 
+    /// Recent normalized entries per workspace, keyed by workspace id.
+    fn log_tails(&self) -> &Arc<RwLock<HashMap<Uuid, Arc<NormalizedTail>>>>;
+
     fn db(&self) -> &DBService;
 
     fn git(&self) -> &GitService;
@@ -580,6 +659,29 @@ pub trait ContainerService {
         stats_only: bool,
     ) -> Result<futures::stream::BoxStream<'static, Result<LogMsg, std::io::Error>>, ContainerError>;
 
+    /// The last normalized entries of the coding agent `process_id` in
+    /// `workspace_id`, in entry order. Empty if the tail holds none of them,
+    /// e.g. because the server restarted since the process ran.
+    async fn workspace_log_tail(
+        &self,
+        workspace_id: Uuid,
+        process_id: Uuid,
+    ) -> Vec<NormalizedEntry> {
+        match self.log_tails().read().await.get(&workspace_id) {
+            Some(tail) => tail.process_entries(process_id),
+            None => Vec::new(),
+        }
+    }
+
+    /// Execution processes whose child process is still running.
+    async fn running_execution_ids(&self) -> Vec<Uuid>;
+
+    /// Every coding agent currently running, across all workspaces.
+    async fn list_active_sessions(&self) -> Result<Vec<ActiveSession>, ContainerError> {
+        let running = self.running_execution_ids().await;
+        Ok(ActiveSession::for_executions(&self.db().pool, running).await?)
+    }
+
     /// Fetch the MsgStore for a given execution ID, panicking if missing.
     async fn get_msg_store_by_id(&self, uuid: &Uuid) -> Option<Arc<MsgStore>> {
         let map = self.msg_stores().read().await;
@@ -900,6 +1002,18 @@ pub trait ContainerService {
         &self,
         workspace: &Workspace,
         executor_profile_id: ExecutorProfileId,
+    ) -> Result<ExecutionProcess, ContainerError> {
+        self.start_workspace_with_config(workspace, executor_profile_id, None)
+            .await
+    }
+
+    /// Like [`Self::start_workspace`], but the coding agent runs with
+    /// `executor_config` when given instead of the profile's current settings.
+    async fn start_workspace_with_config(
+        &self,
+        workspace: &Workspace,
+        executor_profile_id: ExecutorProfileId,
+        executor_config: Option<CodingAgent>,
     ) -> Result<ExecutionProcess, ContainerError> {
         // Create container
         self.create(workspace).await?;
@@ -946,6 +1060,7 @@ pub trait ContainerService {
                 prompt,
                 executor_profile_id: executor_profile_id.clone(),
                 working_dir,
+                executor_config,
             }),
             cleanup_action.map(Box::new),
         );
@@ -995,6 +1110,23 @@ pub trait ContainerService {
         executor_action: &ExecutorAction,
         run_reason: &ExecutionProcessRunReason,
     ) -> Result<ExecutionProcess, ContainerError> {
+        // Only one coding agent per workspace; the lock covers the window between the
+        // running check and the new process row existing
+        let _spawn_guard = if run_reason == &ExecutionProcessRunReason::CodingAgent {
+            let guard = try_lock_workspace_spawn(workspace.id)?;
+            if ExecutionProcess::has_running_coding_agent_for_workspace(
+                &self.db().pool,
+                workspace.id,
+            )
+            .await?
+            {
+                return Err(ContainerError::WorkspaceBusy(workspace.id));
+            }
+            Some(guard)
+        } else {
+            None
+        };
+
         // Update task status to InProgress when starting an execution
         let task = workspace
             .parent_task(&self.db().pool)
@@ -1137,22 +1269,40 @@ pub trait ContainerService {
         let workspace_root = self.workspace_to_current_dir(workspace);
         #[cfg_attr(feature = "qa-mode", allow(unused_variables))]
         if let Some(msg_store) = self.get_msg_store_by_id(&execution_process.id).await
-            && let Some((executor_profile_id, working_dir)) = match executor_action.typ() {
-                ExecutorActionType::CodingAgentInitialRequest(request) => Some((
-                    &request.executor_profile_id,
-                    request.effective_dir(&workspace_root),
-                )),
-                ExecutorActionType::CodingAgentFollowUpRequest(request) => Some((
-                    &request.executor_profile_id,
-                    request.effective_dir(&workspace_root),
-                )),
-                ExecutorActionType::ReviewRequest(request) => Some((
-                    &request.executor_profile_id,
-                    request.effective_dir(&workspace_root),
-                )),
-                _ => None,
-            }
+            && let Some((executor_profile_id, executor_config, working_dir)) =
+                match executor_action.typ() {
+                    ExecutorActionType::CodingAgentInitialRequest(request) => Some((
+                        &request.executor_profile_id,
+                        request.executor_config.as_ref(),
+                        request.effective_dir(&workspace_root),
+                    )),
+                    ExecutorActionType::CodingAgentFollowUpRequest(request) => Some((
+                        &request.executor_profile_id,
+                        None,
+                        request.effective_dir(&workspace_root),
+                    )),
+                    ExecutorActionType::ReviewRequest(request) => Some((
+                        &request.executor_profile_id,
+                        None,
+                        request.effective_dir(&workspace_root),
+                    )),
+                    _ => None,
+                }
         {
+            let tail = self
+                .log_tails()
+                .write()
+                .await
+                .entry(workspace.id)
+                .or_insert_with(|| {
+                    Arc::new(NormalizedTail::from_env(
+                        "VK_LOG_TAIL_CAPACITY",
+                        DEFAULT_TAIL_CAPACITY,
+                    ))
+                })
+                .clone();
+            spawn_tail_writer(msg_store.clone(), execution_process.id, tail);
+
             #[cfg(feature = "qa-mode")]
             {
                 let executor = QaMockExecutor;
@@ -1160,9 +1310,11 @@ pub trait ContainerService {
             }
             #[cfg(not(feature = "qa-mode"))]
             {
-                if let Some(executor) =
-                    ExecutorConfigs::get_cached().get_coding_agent(executor_profile_id)
+                if let Some(executor) = executor_config
+                    .cloned()
+                    .or_else(|| ExecutorConfigs::get_cached().get_coding_agent(executor_profile_id))
                 {
+                    executor.mirror_live_logs(msg_store.clone(), &working_dir);
                     executor.normalize_logs(msg_store, &working_dir);
                 } else {
                     tracing::error!(
@@ -1212,3 +1364,39 @@ pub trait ContainerService {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn second_spawn_into_a_busy_workspace_is_rejected() {
+        let workspace_id = Uuid::new_v4();
+
+        let first = try_lock_workspace_spawn(workspace_id).unwrap();
+        let concurrent = tokio::spawn(async move { try_lock_workspace_spawn(workspace_id) })
+            .await
+            .unwrap();
+        assert!(matches!(
+            concurrent,
+            Err(ContainerError::WorkspaceBusy(id)) if id == workspace_id
+        ));
+        // Other workspaces are unaffected
+        assert!(try_lock_workspace_spawn(Uuid::new_v4()).is_ok());
+
+        drop(first);
+        assert!(try_lock_workspace_spawn(workspace_id).is_ok());
+    }
+
+    #[tokio::test]
+    async fn released_spawn_locks_are_pruned() {
+        let released = Uuid::new_v4();
+        let held = Uuid::new_v4();
+        drop(try_lock_workspace_spawn(released).unwrap());
+        let _guard = try_lock_workspace_spawn(held).unwrap();
+
+        let locks = WORKSPACE_SPAWN_LOCKS.lock().unwrap();
+        assert!(!locks.contains_key(&released));
+        assert!(locks.contains_key(&held));
+    }
+}
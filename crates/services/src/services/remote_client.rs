@@ -1,10 +1,16 @@
 //! OAuth client for authorization-code handoffs with automatic retries.
 
-use std::time::Duration;
+use std::{
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
 
 use backon::{ExponentialBuilder, Retryable};
 use chrono::Duration as ChronoDuration;
-use reqwest::{Client, StatusCode};
+use reqwest::{
+    Client, StatusCode,
+    header::{AUTHORIZATION, HeaderMap, HeaderName, HeaderValue},
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use thiserror::Error;
@@ -23,9 +29,10 @@ use utils::{
             ListOrganizationsResponse, Organization, RevokeInvitationRequest,
             UpdateMemberRoleRequest, UpdateMemberRoleResponse, UpdateOrganizationRequest,
         },
-        projects::{ListProjectsResponse, RemoteProject},
+        projects::{ListProjectStatusesResponse, ListProjectsResponse, RemoteProject},
     },
     jwt::extract_expiration,
+    tls,
 };
 use uuid::Uuid;
 
@@ -51,6 +58,10 @@ pub enum RemoteClientError {
     Storage(String),
     #[error("invalid access token: {0}")]
     Token(String),
+    #[error("invalid header: {0}")]
+    Header(String),
+    #[error("remote unavailable: too many consecutive failures")]
+    Unavailable,
 }
 
 impl RemoteClientError {
@@ -96,11 +107,88 @@ struct ApiErrorResponse {
     error: String,
 }
 
+/// Whether calls to the remote are let through, see [`RemoteClient::breaker_state`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BreakerState {
+    /// Calls go through.
+    Closed,
+    /// The remote looks down; calls fail fast with [`RemoteClientError::Unavailable`].
+    Open,
+    /// The cooldown is over; calls go through again and the next failure reopens.
+    HalfOpen,
+}
+
+/// Stops calling the remote after `threshold` consecutive failed calls, for
+/// `cooldown`, so an outage isn't met with a stream of doomed retries.
+#[derive(Debug)]
+struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    inner: Mutex<BreakerInner>,
+}
+
+#[derive(Debug, Default)]
+struct BreakerInner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            inner: Mutex::new(BreakerInner::default()),
+        }
+    }
+
+    fn state(&self) -> BreakerState {
+        let inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        match inner.opened_at {
+            None => BreakerState::Closed,
+            Some(opened_at) if opened_at.elapsed() < self.cooldown => BreakerState::Open,
+            Some(_) => BreakerState::HalfOpen,
+        }
+    }
+
+    fn check(&self) -> Result<(), RemoteClientError> {
+        match self.state() {
+            BreakerState::Open => Err(RemoteClientError::Unavailable),
+            BreakerState::Closed | BreakerState::HalfOpen => Ok(()),
+        }
+    }
+
+    fn record<T>(&self, result: &Result<T, RemoteClientError>) {
+        let mut inner = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        match result {
+            // Only failures that suggest an outage count; the remote answering with
+            // e.g. a 404 or 401 shows it's up
+            Err(err) if err.should_retry() => {
+                inner.consecutive_failures += 1;
+                if inner.consecutive_failures >= self.threshold {
+                    if inner.opened_at.is_none() {
+                        warn!(
+                            "Remote failed {} times in a row, pausing calls for {:?}",
+                            inner.consecutive_failures, self.cooldown
+                        );
+                    }
+                    inner.opened_at = Some(Instant::now());
+                }
+            }
+            _ => *inner = BreakerInner::default(),
+        }
+    }
+}
+
 /// HTTP client for the remote OAuth server with automatic retries.
 pub struct RemoteClient {
     base: Url,
     http: Client,
     auth_context: AuthContext,
+    headers: HeaderMap,
+    // Shared between clones so every caller sees the same outage
+    breaker: Arc<CircuitBreaker>,
 }
 
 impl std::fmt::Debug for RemoteClient {
@@ -109,6 +197,7 @@ impl std::fmt::Debug for RemoteClient {
             .field("base", &self.base)
             .field("http", &self.http)
             .field("auth_context", &"<present>")
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
             .finish()
     }
 }
@@ -119,6 +208,8 @@ impl Clone for RemoteClient {
             base: self.base.clone(),
             http: self.http.clone(),
             auth_context: self.auth_context.clone(),
+            headers: self.headers.clone(),
+            breaker: self.breaker.clone(),
         }
     }
 }
@@ -126,10 +217,13 @@ impl Clone for RemoteClient {
 impl RemoteClient {
     const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
     const TOKEN_REFRESH_LEEWAY_SECS: i64 = 20;
+    const BREAKER_FAILURE_THRESHOLD: u32 = 5;
+    const BREAKER_COOLDOWN: Duration = Duration::from_secs(60);
 
     pub fn new(base_url: &str, auth_context: AuthContext) -> Result<Self, RemoteClientError> {
         let base = Url::parse(base_url).map_err(|e| RemoteClientError::Url(e.to_string()))?;
-        let http = Client::builder()
+        let http = tls::with_custom_ca(Client::builder())
+            .map_err(|e| RemoteClientError::Transport(e.to_string()))?
             .timeout(Self::REQUEST_TIMEOUT)
             .user_agent(concat!("remote-client/", env!("CARGO_PKG_VERSION")))
             .build()
@@ -138,9 +232,43 @@ impl RemoteClient {
             base,
             http,
             auth_context,
+            headers: HeaderMap::new(),
+            breaker: Arc::new(CircuitBreaker::new(
+                Self::BREAKER_FAILURE_THRESHOLD,
+                Self::BREAKER_COOLDOWN,
+            )),
         })
     }
 
+    /// Attaches extra headers (e.g. tracing ids or proxy auth) to every request.
+    /// An `Authorization` header is ignored so it can't replace the session token.
+    pub fn with_headers(mut self, mut headers: HeaderMap) -> Self {
+        headers.remove(AUTHORIZATION);
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Parses a `Name: value; Other-Name: value` list, as used by the
+    /// `VK_SHARED_API_HEADERS` environment variable.
+    pub fn parse_header_list(raw: &str) -> Result<HeaderMap, RemoteClientError> {
+        let mut headers = HeaderMap::new();
+        for pair in raw
+            .split(';')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let (name, value) = pair
+                .split_once(':')
+                .ok_or_else(|| RemoteClientError::Header(format!("missing ':' in '{pair}'")))?;
+            let name = HeaderName::from_bytes(name.trim().as_bytes())
+                .map_err(|e| RemoteClientError::Header(e.to_string()))?;
+            let value = HeaderValue::from_str(value.trim())
+                .map_err(|e| RemoteClientError::Header(e.to_string()))?;
+            headers.append(name, value);
+        }
+        Ok(headers)
+    }
+
     /// Returns a valid access token, refreshing when it's about to expire.
     fn require_token(
         &self,
@@ -221,6 +349,11 @@ impl RemoteClient {
             .map_err(|e| self.map_api_error(e))
     }
 
+    /// Whether calls are currently let through to the remote.
+    pub fn breaker_state(&self) -> BreakerState {
+        self.breaker.state()
+    }
+
     /// Returns the base URL for the client.
     pub fn base_url(&self) -> &str {
         self.base.as_str()
@@ -274,9 +407,13 @@ impl RemoteClient {
             .base
             .join(path)
             .map_err(|e| RemoteClientError::Url(e.to_string()))?;
+        self.breaker.check()?;
 
-        (|| async {
-            let mut req = self.http.request(method.clone(), url.clone());
+        let result = (|| async {
+            let mut req = self
+                .http
+                .request(method.clone(), url.clone())
+                .headers(self.headers.clone());
 
             if requires_auth {
                 let token = self.require_token().await?;
@@ -314,7 +451,9 @@ impl RemoteClient {
                 e
             )
         })
-        .await
+        .await;
+        self.breaker.record(&result);
+        result
     }
 
     // Public endpoint helpers (no auth required)
@@ -417,6 +556,15 @@ impl RemoteClient {
             .await
     }
 
+    /// Lists the statuses (board columns) of a project, in the remote's order.
+    pub async fn list_project_statuses(
+        &self,
+        project_id: Uuid,
+    ) -> Result<ListProjectStatusesResponse, RemoteClientError> {
+        self.get_authed(&format!("/v1/project_statuses?project_id={project_id}"))
+            .await
+    }
+
     pub async fn get_project(&self, project_id: Uuid) -> Result<RemoteProject, RemoteClientError> {
         self.get_authed(&format!("/v1/projects/{project_id}")).await
     }
@@ -557,3 +705,147 @@ fn map_reqwest_error(e: reqwest::Error) -> RemoteClientError {
         RemoteClientError::Transport(e.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use axum::{Router, http::HeaderMap as AxumHeaderMap, routing::get};
+    use tokio::sync::RwLock;
+
+    use super::*;
+    use crate::services::oauth_credentials::OAuthCredentials;
+
+    #[test]
+    fn parse_header_list_reads_pairs() {
+        let headers =
+            RemoteClient::parse_header_list("X-Trace-Id: abc123; Proxy-Authorization: Basic xyz")
+                .unwrap();
+        assert_eq!(headers["x-trace-id"], "abc123");
+        assert_eq!(headers["proxy-authorization"], "Basic xyz");
+        assert!(RemoteClient::parse_header_list("missing-colon").is_err());
+    }
+
+    #[test]
+    fn breaker_opens_after_repeated_failures_and_half_opens_after_cooldown() {
+        let breaker = CircuitBreaker::new(3, Duration::from_millis(100));
+        let outage = || Err::<(), _>(RemoteClientError::Timeout);
+
+        breaker.record(&outage());
+        breaker.record(&outage());
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        breaker.record(&outage());
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert!(matches!(
+            breaker.check(),
+            Err(RemoteClientError::Unavailable)
+        ));
+
+        std::thread::sleep(Duration::from_millis(150));
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        assert!(breaker.check().is_ok());
+
+        // A failed probe reopens straight away
+        breaker.record(&outage());
+        assert_eq!(breaker.state(), BreakerState::Open);
+
+        std::thread::sleep(Duration::from_millis(150));
+        breaker.record(&Ok(()));
+        assert_eq!(breaker.state(), BreakerState::Closed);
+
+        // Errors that show the remote is up don't count towards opening
+        for _ in 0..5 {
+            breaker.record(&Err::<(), _>(RemoteClientError::Auth));
+        }
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+
+    #[tokio::test]
+    async fn custom_headers_are_sent_without_overriding_auth() {
+        let captured = Arc::new(Mutex::new(None));
+        let captured_headers = captured.clone();
+        let router = Router::new().route(
+            "/v1/invitations/{token}",
+            get(move |headers: AxumHeaderMap| async move {
+                *captured_headers.lock().unwrap() = Some(headers);
+                StatusCode::NOT_FOUND
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let auth_context = AuthContext::new(
+            Arc::new(OAuthCredentials::new(dir.path().join("credentials.json"))),
+            Arc::new(RwLock::new(None)),
+        );
+        let headers =
+            RemoteClient::parse_header_list("X-Trace-Id: abc123; Authorization: Bearer nope")
+                .unwrap();
+        let client = RemoteClient::new(&format!("http://{addr}"), auth_context)
+            .unwrap()
+            .with_headers(headers);
+
+        let _ = client.get_invitation("token").await;
+
+        let headers = captured.lock().unwrap().take().expect("request received");
+        assert_eq!(headers["x-trace-id"], "abc123");
+        assert!(headers.get("authorization").is_none());
+    }
+
+    #[tokio::test]
+    async fn project_statuses_keep_remote_order() {
+        let project_id = Uuid::new_v4();
+        let status = |name: &str, sort_order: i32| {
+            serde_json::json!({
+                "id": Uuid::new_v4(),
+                "project_id": project_id,
+                "name": name,
+                "color": "220 9% 46%",
+                "sort_order": sort_order,
+                "hidden": false,
+                "created_at": "2026-01-01T00:00:00Z",
+            })
+        };
+        let body = serde_json::json!({
+            "project_statuss": [status("Backlog", 0), status("In progress", 2), status("To do", 1)],
+        });
+        let router = Router::new().route(
+            "/v1/project_statuses",
+            get(move || async move { axum::Json(body) }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, router).await.unwrap();
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let auth_context = AuthContext::new(
+            Arc::new(OAuthCredentials::new(dir.path().join("credentials.json"))),
+            Arc::new(RwLock::new(None)),
+        );
+        auth_context
+            .save_credentials(&Credentials {
+                access_token: Some("token".to_string()),
+                refresh_token: "refresh".to_string(),
+                expires_at: Some(chrono::Utc::now() + ChronoDuration::hours(1)),
+            })
+            .await
+            .unwrap();
+        let client = RemoteClient::new(&format!("http://{addr}"), auth_context).unwrap();
+
+        let names: Vec<_> = client
+            .list_project_statuses(project_id)
+            .await
+            .unwrap()
+            .project_statuses
+            .into_iter()
+            .map(|status| status.name)
+            .collect();
+        assert_eq!(names, ["Backlog", "In progress", "To do"]);
+    }
+}
@@ -1,24 +1,41 @@
-use std::time::Duration;
+use std::{
+    collections::HashSet,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
 
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use db::{
     DBService,
     models::{
         repo::Repo,
-        scheduled_execution::ScheduledExecution,
+        scheduled_execution::{ScheduledExecution, ScheduledExecutionStatus, ScheduledRepoInput},
         task::{Task, TaskStatus},
         workspace::{CreateWorkspace, Workspace},
         workspace_repo::{CreateWorkspaceRepo, WorkspaceRepo},
     },
 };
-use executors::profile::ExecutorProfileId;
+use executors::{
+    executors::CodingAgent,
+    profile::{ExecutorConfigs, ExecutorProfileId},
+};
 use serde::{Deserialize, Serialize};
-use sqlx::error::Error as SqlxError;
+use sqlx::{SqlitePool, error::Error as SqlxError};
 use thiserror::Error;
 use tokio::time::interval;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use ts_rs::TS;
 use uuid::Uuid;
 
-use crate::services::container::ContainerService;
+use crate::services::container::{ContainerError, ContainerService};
+
+/// Pending executions this far past their `scheduled_at` indicate the
+/// scheduler was not running when they were due.
+const OVERDUE_THRESHOLD: Duration = Duration::from_secs(10 * 60);
 
 #[derive(Debug, Error)]
 enum SchedulerError {
@@ -28,38 +45,173 @@ enum SchedulerError {
     TaskNotFound(Uuid),
     #[error("Failed to deserialize executor_profile_id: {0}")]
     DeserializeExecutorProfile(String),
+    #[error("{0}")]
+    UnresolvedExecutorProfile(String),
+    #[error("Failed to deserialize executor_config: {0}")]
+    DeserializeExecutorConfig(String),
     #[error("Failed to deserialize repos: {0}")]
     DeserializeRepos(String),
     #[error("Repo not found: {0}")]
     RepoNotFound(Uuid),
+    #[error("Repo listed more than once: {0}")]
+    DuplicateRepo(Uuid),
     #[error(transparent)]
     Other(#[from] anyhow::Error),
 }
 
-/// Repo input stored as JSON in the scheduled_execution record.
-/// Uses camelCase to match the frontend's serialization format.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ScheduledRepoInput {
-    pub repo_id: Uuid,
-    pub target_branch: String,
+#[derive(Debug, Error)]
+pub enum RerunError {
+    #[error(transparent)]
+    Sqlx(#[from] SqlxError),
+    #[error("Scheduled execution not found")]
+    NotFound,
+    #[error("Cannot rerun a scheduled execution with status '{0}'")]
+    NotFired(ScheduledExecutionStatus),
+    #[error("Task not found: {0}")]
+    TaskNotFound(Uuid),
+    #[error("Task {0} is in progress; wait for it to finish before rerunning")]
+    TaskInProgress(Uuid),
+}
+
+/// Schedule a fresh run of an execution that already fired, with the same
+/// task, repos and profile, firing at `scheduled_at`. The scheduler only fires
+/// for tasks in Todo, so a finished task is moved back there; one that is still
+/// in progress is rejected instead. The original execution is left untouched.
+pub async fn schedule_rerun(
+    pool: &SqlitePool,
+    id: Uuid,
+    scheduled_at: DateTime<Utc>,
+) -> Result<ScheduledExecution, RerunError> {
+    let scheduled = ScheduledExecution::find_by_id(pool, id)
+        .await?
+        .ok_or(RerunError::NotFound)?;
+    if scheduled.status != ScheduledExecutionStatus::Fired {
+        return Err(RerunError::NotFired(scheduled.status));
+    }
+
+    let task = Task::find_by_id(pool, scheduled.task_id)
+        .await?
+        .ok_or(RerunError::TaskNotFound(scheduled.task_id))?;
+    match task.status {
+        TaskStatus::Todo => {}
+        TaskStatus::InProgress => return Err(RerunError::TaskInProgress(task.id)),
+        _ => Task::update_status(pool, task.id, TaskStatus::Todo).await?,
+    }
+
+    Ok(ScheduledExecution::create_rerun(pool, &scheduled, scheduled_at).await?)
+}
+
+/// Shared switch that lets the API pause and resume the scheduler without
+/// restarting the service, and abort individual executions while they fire.
+/// Cloning shares the underlying state.
+#[derive(Debug, Clone, Default)]
+pub struct SchedulerControl {
+    paused: Arc<AtomicBool>,
+    aborted: Arc<Mutex<HashSet<Uuid>>>,
+}
+
+impl SchedulerControl {
+    pub fn pause(&self) {
+        self.paused.store(true, Ordering::SeqCst);
+    }
+
+    pub fn resume(&self) {
+        self.paused.store(false, Ordering::SeqCst);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+
+    /// Ask the scheduler to stop firing `id` at its next checkpoint. The
+    /// execution is then marked cancelled instead of started.
+    pub fn request_abort(&self, id: Uuid) {
+        self.aborted
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(id);
+    }
+
+    /// Drop a pending abort request for `id`, e.g. once it was cancelled
+    /// another way and will never reach a checkpoint.
+    pub fn clear_abort(&self, id: Uuid) {
+        self.take_abort(id);
+    }
+
+    /// Whether an abort was requested for `id`, clearing the request.
+    fn take_abort(&self, id: Uuid) -> bool {
+        self.aborted
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&id)
+    }
+
+    pub fn state(&self) -> SchedulerState {
+        SchedulerState {
+            paused: self.is_paused(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct SchedulerState {
+    pub paused: bool,
+}
+
+/// The part of the container the scheduler uses to fire an execution.
+#[async_trait]
+pub trait ScheduledExecutionStarter {
+    /// Branch name for a workspace created for `task_title`.
+    async fn branch_name_for(&self, workspace_id: &Uuid, task_title: &str) -> String;
+
+    /// Start the coding agent in `workspace`.
+    async fn start_scheduled(
+        &self,
+        workspace: &Workspace,
+        executor_profile_id: ExecutorProfileId,
+        executor_config: Option<CodingAgent>,
+    ) -> Result<(), ContainerError>;
+}
+
+#[async_trait]
+impl<C: ContainerService + Sync> ScheduledExecutionStarter for C {
+    async fn branch_name_for(&self, workspace_id: &Uuid, task_title: &str) -> String {
+        self.git_branch_from_workspace(workspace_id, task_title).await
+    }
+
+    async fn start_scheduled(
+        &self,
+        workspace: &Workspace,
+        executor_profile_id: ExecutorProfileId,
+        executor_config: Option<CodingAgent>,
+    ) -> Result<(), ContainerError> {
+        self.start_workspace_with_config(workspace, executor_profile_id, executor_config)
+            .await
+            .map(|_| ())
+    }
 }
 
 /// Service that polls for pending scheduled executions and fires them.
 ///
-/// Generic over `C: ContainerService` so it can be instantiated from the
-/// deployment layer (which knows the concrete container type).
-pub struct SchedulerService<C: ContainerService> {
+/// Generic over the container so it can be instantiated from the deployment
+/// layer (which knows the concrete container type).
+pub struct SchedulerService<C: ScheduledExecutionStarter> {
     db: DBService,
     container: C,
+    control: SchedulerControl,
     poll_interval: Duration,
 }
 
-impl<C: ContainerService + Send + Sync + 'static> SchedulerService<C> {
-    pub fn spawn(db: DBService, container: C) -> tokio::task::JoinHandle<()> {
+impl<C: ScheduledExecutionStarter + Send + Sync + 'static> SchedulerService<C> {
+    pub fn spawn(
+        db: DBService,
+        container: C,
+        control: SchedulerControl,
+    ) -> tokio::task::JoinHandle<()> {
         let service = Self {
             db,
             container,
+            control,
             poll_interval: Duration::from_secs(15),
         };
         tokio::spawn(async move {
@@ -84,6 +236,22 @@ impl<C: ContainerService + Send + Sync + 'static> SchedulerService<C> {
     }
 
     async fn check_pending(&self) -> Result<(), SchedulerError> {
+        // Due executions stay pending while paused and fire on the first tick
+        // after resuming.
+        if self.control.is_paused() {
+            return Ok(());
+        }
+
+        let threshold = chrono::Duration::seconds(OVERDUE_THRESHOLD.as_secs() as i64);
+        let overdue = ScheduledExecution::find_overdue(&self.db.pool, threshold).await?;
+        if let Some(lateness) = longest_overdue(&overdue, Utc::now()) {
+            warn!(
+                "{} scheduled executions are overdue (longest by {} minutes); the scheduler may have been down",
+                overdue.len(),
+                lateness.num_minutes()
+            );
+        }
+
         let due = ScheduledExecution::find_pending_due(&self.db.pool).await?;
 
         if due.is_empty() {
@@ -93,7 +261,10 @@ impl<C: ContainerService + Send + Sync + 'static> SchedulerService<C> {
         info!("Found {} pending scheduled executions to fire", due.len());
 
         for scheduled in due {
-            if let Err(e) = self.fire_scheduled_task(&scheduled).await {
+            let result = self.fire_scheduled_task(&scheduled).await;
+            // An abort that arrived too late for a checkpoint has nothing left to stop
+            self.control.clear_abort(scheduled.id);
+            if let Err(e) = result {
                 error!(
                     "Error firing scheduled execution {} for task {}: {}",
                     scheduled.id, scheduled.task_id, e
@@ -120,15 +291,15 @@ impl<C: ContainerService + Send + Sync + 'static> SchedulerService<C> {
         let pool = &self.db.pool;
 
         // 1. Deserialize executor_profile_id and repos from JSON
-        let executor_profile_id: ExecutorProfileId =
-            serde_json::from_str(&scheduled.executor_profile_id).map_err(|e| {
-                SchedulerError::DeserializeExecutorProfile(format!("{}", e))
-            })?;
+        let executor_profile_id = scheduled.parsed_executor_profile().map_err(|e| {
+            SchedulerError::DeserializeExecutorProfile(format!("{}", e))
+        })?;
+        let executor_config = frozen_executor_config(scheduled, &executor_profile_id)?;
 
-        let repos: Vec<ScheduledRepoInput> =
-            serde_json::from_str(&scheduled.repos).map_err(|e| {
-                SchedulerError::DeserializeRepos(format!("{}", e))
-            })?;
+        let repos = scheduled.parsed_repos().map_err(|e| {
+            SchedulerError::DeserializeRepos(format!("{}", e))
+        })?;
+        ensure_unique_repos(&repos)?;
 
         // 2. Verify task still exists and is in Todo status
         let task = Task::find_by_id(pool, scheduled.task_id)
@@ -143,12 +314,15 @@ impl<C: ContainerService + Send + Sync + 'static> SchedulerService<C> {
             ScheduledExecution::mark_cancelled(pool, scheduled.id).await?;
             return Ok(());
         }
+        if self.abort_if_requested(scheduled).await? {
+            return Ok(());
+        }
 
         // 3. Create workspace (same logic as create_task_and_start)
         let workspace_id = Uuid::new_v4();
         let git_branch_name = self
             .container
-            .git_branch_from_workspace(&workspace_id, &task.title)
+            .branch_name_for(&workspace_id, &task.title)
             .await;
 
         // Compute agent_working_dir based on repo count
@@ -181,11 +355,16 @@ impl<C: ContainerService + Send + Sync + 'static> SchedulerService<C> {
             })
             .collect();
         WorkspaceRepo::create_many(pool, workspace.id, &workspace_repos).await?;
+        if self.abort_if_requested(scheduled).await? {
+            // Nothing was started in it yet, so nothing else refers to it
+            Workspace::delete(pool, workspace.id).await?;
+            return Ok(());
+        }
 
         // 4. Start workspace
         match self
             .container
-            .start_workspace(&workspace, executor_profile_id)
+            .start_scheduled(&workspace, executor_profile_id, executor_config)
             .await
         {
             Ok(_) => {
@@ -207,4 +386,383 @@ impl<C: ContainerService + Send + Sync + 'static> SchedulerService<C> {
 
         Ok(())
     }
+
+    /// Mark `scheduled` cancelled if an abort was requested for it.
+    async fn abort_if_requested(
+        &self,
+        scheduled: &ScheduledExecution,
+    ) -> Result<bool, SchedulerError> {
+        if !self.control.take_abort(scheduled.id) {
+            return Ok(false);
+        }
+        info!("Aborting scheduled execution {} while firing", scheduled.id);
+        ScheduledExecution::mark_cancelled(&self.db.pool, scheduled.id).await?;
+        Ok(true)
+    }
+}
+
+/// How far past its `scheduled_at` the most overdue execution is.
+fn longest_overdue(
+    executions: &[ScheduledExecution],
+    now: DateTime<Utc>,
+) -> Option<chrono::Duration> {
+    executions
+        .iter()
+        .map(|scheduled| now - scheduled.scheduled_at)
+        .max()
+}
+
+/// The settings frozen when `scheduled` was created, if any. Without a snapshot
+/// the profile must still resolve, since it is looked up again at spawn time.
+fn frozen_executor_config(
+    scheduled: &ScheduledExecution,
+    executor_profile_id: &ExecutorProfileId,
+) -> Result<Option<CodingAgent>, SchedulerError> {
+    match &scheduled.executor_config {
+        Some(json) => serde_json::from_str(json)
+            .map(Some)
+            .map_err(|e| SchedulerError::DeserializeExecutorConfig(e.to_string())),
+        None => {
+            executor_profile_id
+                .resolve(&ExecutorConfigs::get_cached())
+                .map_err(SchedulerError::UnresolvedExecutorProfile)?;
+            Ok(None)
+        }
+    }
+}
+
+/// Reject schedules that list the same repo twice, which would create
+/// conflicting workspace repos.
+fn ensure_unique_repos(repos: &[ScheduledRepoInput]) -> Result<(), SchedulerError> {
+    let repo_ids: Vec<Uuid> = repos.iter().map(|r| r.repo_id).collect();
+    match Repo::duplicate_ids(&repo_ids).first() {
+        Some(id) => Err(SchedulerError::DuplicateRepo(*id)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use db::models::{
+        project::{CreateProject, Project},
+        task::CreateTask,
+    };
+    use executors::executors::BaseCodingAgent;
+
+    use super::*;
+
+    /// Records the workspaces it is asked to start instead of spawning agents.
+    #[derive(Default)]
+    struct RecordingStarter {
+        started: Mutex<Vec<Uuid>>,
+        /// Runs while the branch is named, between the two abort checkpoints.
+        while_branching: Option<Box<dyn Fn() + Send + Sync>>,
+    }
+
+    impl RecordingStarter {
+        fn started(&self) -> Vec<Uuid> {
+            self.started.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl ScheduledExecutionStarter for RecordingStarter {
+        async fn branch_name_for(&self, workspace_id: &Uuid, _task_title: &str) -> String {
+            if let Some(hook) = &self.while_branching {
+                hook();
+            }
+            format!("vk/{workspace_id}")
+        }
+
+        async fn start_scheduled(
+            &self,
+            workspace: &Workspace,
+            _executor_profile_id: ExecutorProfileId,
+            _executor_config: Option<CodingAgent>,
+        ) -> Result<(), ContainerError> {
+            self.started.lock().unwrap().push(workspace.id);
+            Ok(())
+        }
+    }
+
+    async fn scheduler() -> SchedulerService<RecordingStarter> {
+        SchedulerService {
+            db: DBService::new_in_memory().await.unwrap(),
+            container: RecordingStarter::default(),
+            control: SchedulerControl::default(),
+            poll_interval: Duration::from_secs(15),
+        }
+    }
+
+    /// A pending execution that is already due, for a task in Todo.
+    async fn due_execution(db: &DBService) -> ScheduledExecution {
+        let pool = &db.pool;
+        let project_id = Uuid::new_v4();
+        Project::create(
+            pool,
+            &CreateProject {
+                name: "project".to_string(),
+                repositories: Vec::new(),
+            },
+            project_id,
+        )
+        .await
+        .unwrap();
+        let task_id = Uuid::new_v4();
+        Task::create(
+            pool,
+            &CreateTask::from_title_description(project_id, "task".to_string(), None),
+            task_id,
+        )
+        .await
+        .unwrap();
+        ScheduledExecution::create(
+            pool,
+            Uuid::new_v4(),
+            task_id,
+            project_id,
+            Utc::now() - chrono::Duration::minutes(1),
+            r#"{"executor":"CLAUDE_CODE"}"#,
+            "[]",
+            "api",
+            None,
+            None,
+        )
+        .await
+        .unwrap()
+    }
+
+    async fn status_of(db: &DBService, id: Uuid) -> ScheduledExecutionStatus {
+        ScheduledExecution::find_by_id(&db.pool, id)
+            .await
+            .unwrap()
+            .unwrap()
+            .status
+    }
+
+    #[test]
+    fn control_clones_share_paused_flag() {
+        let control = SchedulerControl::default();
+        let handle = control.clone();
+        assert!(!control.is_paused());
+
+        handle.pause();
+        assert!(control.is_paused());
+        assert!(control.state().paused);
+
+        control.resume();
+        assert!(!handle.is_paused());
+    }
+
+    fn scheduled_at(scheduled_at: DateTime<Utc>) -> ScheduledExecution {
+        ScheduledExecution {
+            id: Uuid::new_v4(),
+            task_id: Uuid::new_v4(),
+            project_id: Uuid::new_v4(),
+            scheduled_at,
+            status: Default::default(),
+            executor_profile_id: "{}".to_string(),
+            repos: "[]".to_string(),
+            created_at: scheduled_at,
+            updated_at: scheduled_at,
+            fired_at: None,
+            error_message: None,
+            source: "api".to_string(),
+            label: None,
+            executor_config: None,
+        }
+    }
+
+    #[test]
+    fn hour_old_execution_exceeds_overdue_threshold() {
+        let now = Utc::now();
+        let scheduled = scheduled_at(now - chrono::Duration::hours(1));
+
+        let lateness = longest_overdue(&[scheduled], now).unwrap();
+        assert!(lateness > chrono::Duration::minutes(10));
+        assert!(lateness.to_std().unwrap() > OVERDUE_THRESHOLD);
+        assert!(longest_overdue(&[], now).is_none());
+    }
+
+    #[test]
+    fn abort_request_is_consumed_once() {
+        let control = SchedulerControl::default();
+        let id = Uuid::new_v4();
+        assert!(!control.take_abort(id));
+
+        control.clone().request_abort(id);
+        assert!(control.take_abort(id));
+        assert!(!control.take_abort(id));
+    }
+
+    #[test]
+    fn same_repo_listed_twice_is_rejected() {
+        let repo_id = Uuid::new_v4();
+        let input = |target_branch: &str| ScheduledRepoInput {
+            repo_id,
+            target_branch: target_branch.to_string(),
+        };
+
+        let err = ensure_unique_repos(&[input("main"), input("dev")]).unwrap_err();
+        assert!(matches!(err, SchedulerError::DuplicateRepo(id) if id == repo_id));
+        assert!(ensure_unique_repos(&[input("main")]).is_ok());
+    }
+
+    #[test]
+    fn frozen_config_is_used_even_if_the_profile_changed() {
+        let frozen: CodingAgent = serde_json::from_value(serde_json::json!({
+            "CLAUDE_CODE": { "model": "frozen-model" }
+        }))
+        .unwrap();
+        // The profile's current settings no longer match the snapshot
+        let profile = ExecutorProfileId::new(BaseCodingAgent::ClaudeCode);
+        assert_ne!(
+            ExecutorConfigs::get_cached().get_coding_agent(&profile),
+            Some(frozen.clone())
+        );
+
+        let mut scheduled = scheduled_at(Utc::now());
+        scheduled.executor_config = Some(serde_json::to_string(&frozen).unwrap());
+        assert_eq!(
+            frozen_executor_config(&scheduled, &profile).unwrap(),
+            Some(frozen)
+        );
+
+        // Without a snapshot the profile is resolved when firing
+        scheduled.executor_config = None;
+        assert_eq!(frozen_executor_config(&scheduled, &profile).unwrap(), None);
+    }
+
+    #[test]
+    fn stored_json_columns_are_parsed() {
+        let repo_id = Uuid::new_v4();
+        let mut scheduled = scheduled_at(Utc::now());
+        scheduled.repos = format!(r#"[{{"repoId":"{repo_id}","targetBranch":"main"}}]"#);
+        scheduled.executor_profile_id = r#"{"executor":"CLAUDE_CODE"}"#.to_string();
+
+        let repos = scheduled.parsed_repos().unwrap();
+        assert_eq!(repos.len(), 1);
+        assert_eq!(repos[0].repo_id, repo_id);
+        assert_eq!(repos[0].target_branch, "main");
+        assert_eq!(
+            scheduled.parsed_executor_profile().unwrap(),
+            ExecutorProfileId::new(BaseCodingAgent::ClaudeCode)
+        );
+
+        // snake_case keys are not the stored format
+        scheduled.repos = format!(r#"[{{"repo_id":"{repo_id}","target_branch":"main"}}]"#);
+        scheduled.executor_profile_id = r#"{"executor":"NOT_AN_AGENT"}"#.to_string();
+        assert!(scheduled.parsed_repos().is_err());
+        assert!(scheduled.parsed_executor_profile().is_err());
+    }
+
+    #[tokio::test]
+    async fn paused_scheduler_does_not_fire_until_resumed() {
+        let scheduler = scheduler().await;
+        let scheduled = due_execution(&scheduler.db).await;
+
+        scheduler.control.pause();
+        scheduler.check_pending().await.unwrap();
+        assert_eq!(
+            status_of(&scheduler.db, scheduled.id).await,
+            ScheduledExecutionStatus::Pending
+        );
+        assert!(scheduler.container.started().is_empty());
+
+        scheduler.control.resume();
+        scheduler.check_pending().await.unwrap();
+        assert_eq!(
+            status_of(&scheduler.db, scheduled.id).await,
+            ScheduledExecutionStatus::Fired
+        );
+        assert_eq!(scheduler.container.started().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn abort_during_firing_stops_the_start_and_removes_the_workspace() {
+        let mut scheduler = scheduler().await;
+        let scheduled = due_execution(&scheduler.db).await;
+        let control = scheduler.control.clone();
+        let id = scheduled.id;
+        scheduler.container.while_branching = Some(Box::new(move || control.request_abort(id)));
+
+        scheduler.check_pending().await.unwrap();
+
+        assert_eq!(
+            status_of(&scheduler.db, scheduled.id).await,
+            ScheduledExecutionStatus::Cancelled
+        );
+        assert!(scheduler.container.started().is_empty());
+        let workspaces = Workspace::fetch_all(&scheduler.db.pool, Some(scheduled.task_id))
+            .await
+            .unwrap();
+        assert!(workspaces.is_empty());
+        assert!(!scheduler.control.take_abort(scheduled.id));
+    }
+
+    #[tokio::test]
+    async fn stale_abort_request_is_dropped_once_processed() {
+        let scheduler = scheduler().await;
+        let scheduled = due_execution(&scheduler.db).await;
+        // The task is no longer in Todo, so the execution is skipped before any
+        // checkpoint and the request would otherwise linger
+        Task::update_status(&scheduler.db.pool, scheduled.task_id, TaskStatus::Done)
+            .await
+            .unwrap();
+        scheduler.control.request_abort(scheduled.id);
+
+        scheduler.check_pending().await.unwrap();
+
+        assert_eq!(
+            status_of(&scheduler.db, scheduled.id).await,
+            ScheduledExecutionStatus::Cancelled
+        );
+        assert!(!scheduler.control.take_abort(scheduled.id));
+    }
+
+    #[tokio::test]
+    async fn rerun_of_a_finished_task_is_fired() {
+        let scheduler = scheduler().await;
+        let pool = &scheduler.db.pool;
+        let scheduled = due_execution(&scheduler.db).await;
+        scheduler.check_pending().await.unwrap();
+        // The first run is done and its task has moved on from Todo
+        Task::update_status(pool, scheduled.task_id, TaskStatus::InReview)
+            .await
+            .unwrap();
+
+        let rerun = schedule_rerun(pool, scheduled.id, Utc::now()).await.unwrap();
+        scheduler.check_pending().await.unwrap();
+
+        assert_eq!(
+            status_of(&scheduler.db, rerun.id).await,
+            ScheduledExecutionStatus::Fired
+        );
+        assert_eq!(scheduler.container.started().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn rerun_is_rejected_unless_fired_and_idle() {
+        let scheduler = scheduler().await;
+        let pool = &scheduler.db.pool;
+        let scheduled = due_execution(&scheduler.db).await;
+
+        assert!(matches!(
+            schedule_rerun(pool, scheduled.id, Utc::now()).await,
+            Err(RerunError::NotFired(ScheduledExecutionStatus::Pending))
+        ));
+        assert!(matches!(
+            schedule_rerun(pool, Uuid::new_v4(), Utc::now()).await,
+            Err(RerunError::NotFound)
+        ));
+
+        scheduler.check_pending().await.unwrap();
+        Task::update_status(pool, scheduled.task_id, TaskStatus::InProgress)
+            .await
+            .unwrap();
+        assert!(matches!(
+            schedule_rerun(pool, scheduled.id, Utc::now()).await,
+            Err(RerunError::TaskInProgress(_))
+        ));
+    }
 }
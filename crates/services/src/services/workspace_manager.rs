@@ -8,6 +8,11 @@ use uuid::Uuid;
 
 use super::worktree_manager::{WorktreeCleanup, WorktreeError, WorktreeManager};
 
+/// Overrides the free space, in MB, required on the workspace volume before
+/// creating a workspace. 0 disables the check.
+pub const MIN_FREE_DISK_MB_ENV: &str = "VK_MIN_FREE_DISK_MB";
+const DEFAULT_MIN_FREE_DISK_MB: u64 = 500;
+
 #[derive(Debug, Clone)]
 pub struct RepoWorkspaceInput {
     pub repo: Repo,
@@ -33,6 +38,8 @@ pub enum WorkspaceError {
     NoRepositories,
     #[error("Partial workspace creation failed: {0}")]
     PartialCreation(String),
+    #[error("insufficient disk space: {free_mb}MB free, {required_mb}MB required")]
+    InsufficientDiskSpace { free_mb: u64, required_mb: u64 },
 }
 
 /// Info about a single repo's worktree within a workspace
@@ -72,6 +79,12 @@ impl WorkspaceManager {
         );
 
         tokio::fs::create_dir_all(workspace_dir).await?;
+        // Fail clearly up front rather than with whatever git or the agent
+        // reports once the disk fills up mid-checkout
+        if let Err(e) = Self::ensure_free_space(workspace_dir, min_free_disk_mb()) {
+            let _ = tokio::fs::remove_dir(workspace_dir).await;
+            return Err(e);
+        }
 
         let mut created_worktrees: Vec<RepoWorktree> = Vec::new();
 
@@ -137,6 +150,25 @@ impl WorkspaceManager {
         })
     }
 
+    /// Check that the volume holding `path` has at least `required_mb` MB free.
+    /// Passes when free space can't be determined on this platform.
+    pub fn ensure_free_space(path: &Path, required_mb: u64) -> Result<(), WorkspaceError> {
+        if required_mb == 0 {
+            return Ok(());
+        }
+        let Some(available) = available_space(path) else {
+            return Ok(());
+        };
+        let free_mb = available / (1024 * 1024);
+        if free_mb < required_mb {
+            return Err(WorkspaceError::InsufficientDiskSpace {
+                free_mb,
+                required_mb,
+            });
+        }
+        Ok(())
+    }
+
     /// Ensure all worktrees in a workspace exist (for cold restart scenarios)
     pub async fn ensure_workspace_exists(
         workspace_dir: &Path,
@@ -398,3 +430,46 @@ impl WorkspaceManager {
         Ok(())
     }
 }
+
+fn min_free_disk_mb() -> u64 {
+    std::env::var(MIN_FREE_DISK_MB_ENV)
+        .ok()
+        .and_then(|value| value.trim().parse().ok())
+        .unwrap_or(DEFAULT_MIN_FREE_DISK_MB)
+}
+
+/// Bytes available to unprivileged users on the volume holding `path`.
+#[cfg(unix)]
+fn available_space(path: &Path) -> Option<u64> {
+    let stat = nix::sys::statvfs::statvfs(path).ok()?;
+    Some(stat.blocks_available() as u64 * stat.fragment_size() as u64)
+}
+
+#[cfg(not(unix))]
+fn available_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn free_space_check_reports_what_is_missing() {
+        let dir = std::env::temp_dir();
+        assert!(WorkspaceManager::ensure_free_space(&dir, 1).is_ok());
+        assert!(WorkspaceManager::ensure_free_space(&dir, 0).is_ok());
+
+        let err = WorkspaceManager::ensure_free_space(&dir, u64::MAX).unwrap_err();
+        assert!(matches!(
+            err,
+            WorkspaceError::InsufficientDiskSpace {
+                required_mb: u64::MAX,
+                ..
+            }
+        ));
+        let message = err.to_string();
+        assert!(message.starts_with("insufficient disk space: "));
+        assert!(message.ends_with(&format!("MB free, {}MB required", u64::MAX)));
+    }
+}
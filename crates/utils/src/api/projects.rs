@@ -20,6 +20,25 @@ pub struct ListProjectsResponse {
     pub projects: Vec<RemoteProject>,
 }
 
+/// A board column of a remote project.
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct RemoteProjectStatus {
+    pub id: Uuid,
+    pub project_id: Uuid,
+    pub name: String,
+    pub color: String,
+    pub sort_order: i32,
+    pub hidden: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Statuses in the remote's board order (`sort_order`, then creation time).
+#[derive(Debug, Clone, Serialize, Deserialize, TS)]
+pub struct ListProjectStatusesResponse {
+    #[serde(alias = "project_statuss")]
+    pub project_statuses: Vec<RemoteProjectStatus>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct RemoteProjectMembersResponse {
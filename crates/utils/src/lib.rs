@@ -18,6 +18,7 @@ pub mod sentry;
 pub mod shell;
 pub mod stream_lines;
 pub mod text;
+pub mod tls;
 pub mod tokio;
 pub mod version;
 
@@ -39,6 +39,12 @@ impl<T, E> ApiResponse<T, E> {
         }
     }
 
+    /// Attaches `error_data` to a response, keeping its message.
+    pub fn with_error_data(mut self, data: E) -> Self {
+        self.error_data = Some(data);
+        self
+    }
+
     /// Returns true if the response was successful.
     pub fn is_success(&self) -> bool {
         self.success
@@ -1,5 +1,6 @@
 use bytes::Bytes;
 use futures::{Stream, StreamExt, TryStreamExt};
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
 use tokio_util::{
     codec::{FramedRead, LinesCodec},
     io::StreamReader,
@@ -20,3 +21,65 @@ pub trait LinesStreamExt: Stream<Item = Result<String, std::io::Error>> + Sized
 }
 
 impl<S> LinesStreamExt for S where S: Stream<Item = Result<String, std::io::Error>> {}
+
+/// Like [`tokio::io::Lines`], but invalid UTF-8 becomes U+FFFD instead of an
+/// error, so one stray byte in a child's output doesn't end the read.
+#[derive(Debug)]
+pub struct LossyLines<R> {
+    reader: R,
+    buf: Vec<u8>,
+}
+
+/// Read `reader` line by line, decoding each line lossily.
+pub fn lossy_lines<R: AsyncBufRead + Unpin>(reader: R) -> LossyLines<R> {
+    LossyLines {
+        reader,
+        buf: Vec::new(),
+    }
+}
+
+impl<R: AsyncBufRead + Unpin> LossyLines<R> {
+    /// The next line without its `\n` or `\r\n`, or `None` at end of input.
+    /// Cancel safe: a partly read line is kept for the next call.
+    pub async fn next_line(&mut self) -> std::io::Result<Option<String>> {
+        if self.reader.read_until(b'\n', &mut self.buf).await? == 0 && self.buf.is_empty() {
+            return Ok(None);
+        }
+        if self.buf.ends_with(b"\n") {
+            self.buf.pop();
+            if self.buf.ends_with(b"\r") {
+                self.buf.pop();
+            }
+        }
+        let line = String::from_utf8_lossy(&self.buf).into_owned();
+        self.buf.clear();
+        Ok(Some(line))
+    }
+
+    /// The underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn invalid_utf8_is_replaced_and_reading_continues() {
+        let input: &[u8] = b"starting\r\nbad \xff\xfe byte\nlistening on 4096";
+        let mut lines = lossy_lines(input);
+
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "starting");
+        assert_eq!(
+            lines.next_line().await.unwrap().unwrap(),
+            "bad \u{FFFD}\u{FFFD} byte"
+        );
+        assert_eq!(
+            lines.next_line().await.unwrap().unwrap(),
+            "listening on 4096"
+        );
+        assert!(lines.next_line().await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,72 @@
+use std::{io, path::Path};
+
+use reqwest::{Certificate, ClientBuilder};
+
+/// Path to a PEM bundle of extra root certificates to trust, for networks that
+/// intercept TLS with their own CA.
+pub const CA_BUNDLE_ENV: &str = "VK_CA_BUNDLE";
+
+/// Read every certificate in the PEM file at `path`. An empty or non-PEM file
+/// is an error rather than silently trusting nothing extra.
+pub fn load_ca_bundle(path: &Path) -> io::Result<Vec<Certificate>> {
+    let pem = std::fs::read(path)?;
+    let certs = Certificate::from_pem_bundle(&pem).map_err(io::Error::other)?;
+    if certs.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("no PEM certificates found in {}", path.display()),
+        ));
+    }
+    Ok(certs)
+}
+
+/// Add the certificates from [`CA_BUNDLE_ENV`] to `builder`, on top of the
+/// built-in roots. Leaves the builder untouched when the variable is unset.
+pub fn with_custom_ca(builder: ClientBuilder) -> io::Result<ClientBuilder> {
+    match std::env::var_os(CA_BUNDLE_ENV) {
+        Some(path) => add_ca_bundle(builder, Path::new(&path)),
+        None => Ok(builder),
+    }
+}
+
+fn add_ca_bundle(builder: ClientBuilder, path: &Path) -> io::Result<ClientBuilder> {
+    Ok(load_ca_bundle(path)?
+        .into_iter()
+        .fold(builder, ClientBuilder::add_root_certificate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CA: &str = "-----BEGIN CERTIFICATE-----
+MIIBkzCCATmgAwIBAgIUMn6ZVgbZxBcazqTnAjxh2IhEYVUwCgYIKoZIzj0EAwIw
+HjEcMBoGA1UEAwwTdmliZS1rYW5iYW4gdGVzdCBDQTAgFw0yNjEwMTYxMDA3NTBa
+GA8yMTI2MDkyMjEwMDc1MFowHjEcMBoGA1UEAwwTdmliZS1rYW5iYW4gdGVzdCBD
+QTBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABCmc227eJuW0LGoCsiJHYG88VVpF
+zu8Z4UHi/QUue/Q+tIhkAL2NN/4Hl/jyFRhFvTBhc+xhscHZTPfRTmhnp1ejUzBR
+MB0GA1UdDgQWBBR2xb73KTT2vo6IpzGpp5s0U4/OkTAfBgNVHSMEGDAWgBR2xb73
+KTT2vo6IpzGpp5s0U4/OkTAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0gA
+MEUCIF+UWgOj9OQhwZnY7UxCUV0xWErlOs5rnJxFCmBJDVa2AiEAwq7vJon/DGOY
+imbOgF31Ppm+uS1Zmb4phsp3X0ApVg8=
+-----END CERTIFICATE-----
+";
+
+    #[test]
+    fn ca_bundle_is_installed_into_the_client_builder() {
+        let dir = std::env::temp_dir().join(format!("vk-ca-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let bundle = dir.join("corp-ca.pem");
+        std::fs::write(&bundle, TEST_CA.repeat(2)).unwrap();
+
+        assert_eq!(load_ca_bundle(&bundle).unwrap().len(), 2);
+        assert!(add_ca_bundle(reqwest::Client::builder(), &bundle).is_ok());
+
+        let not_pem = dir.join("not-pem.txt");
+        std::fs::write(&not_pem, "not a certificate").unwrap();
+        assert!(add_ca_bundle(reqwest::Client::builder(), &not_pem).is_err());
+        assert!(load_ca_bundle(&dir.join("missing.pem")).is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -119,6 +119,7 @@ const ScheduleExecutionDialogImpl =
             repoId: r.repo_id,
             targetBranch: r.target_branch,
           })),
+          source: 'ui',
         });
       } catch (err) {
         console.error('Failed to schedule execution:', err);
@@ -224,6 +224,7 @@ const TaskFormDialogImpl = NiceModal.create<TaskFormDialogProps>((props) => {
             repoId: rb.repoId,
             targetBranch: rb.branch,
           })),
+          source: 'ui',
         });
         modal.remove();
       } else if (shouldAutoStart) {
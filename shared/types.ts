@@ -50,14 +50,54 @@ export type CreateTask = { project_id: string, title: string, description: strin
 
 export type UpdateTask = { title: string | null, description: string | null, status: TaskStatus | null, parent_workspace_id: string | null, image_ids: Array<string> | null, };
 
-export type ScheduledExecution = { id: string, task_id: string, project_id: string, scheduled_at: string, status: ScheduledExecutionStatus, executor_profile_id: string, repos: string, created_at: string, updated_at: string, fired_at: string | null, error_message: string | null, };
+export type ScheduledExecution = { id: string, task_id: string, project_id: string, scheduled_at: string, status: ScheduledExecutionStatus, executor_profile_id: string, repos: string, created_at: string, updated_at: string, fired_at: string | null, error_message: string | null, 
+/**
+ * Who created the schedule, e.g. `ui`, `api` or `mcp`.
+ */
+source: string, 
+/**
+ * Optional free-text description, e.g. "nightly lint".
+ */
+label: string | null, 
+/**
+ * JSON CodingAgent frozen at create time; fired with instead of the
+ * profile's current settings when set.
+ */
+executor_config: string | null, };
 
 export type ScheduledExecutionStatus = "pending" | "fired" | "cancelled";
 
-export type CreateScheduledExecutionRequest = { taskId: string, projectId: string, scheduledAt: string, executorProfileId: JsonValue, repos: Array<ScheduledRepoInput>, };
+export type ScheduledExecutionEvent = { id: string, scheduled_execution_id: string, from_status: ScheduledExecutionStatus, to_status: ScheduledExecutionStatus, at: string, detail: string | null, };
+
+export type CreateScheduledExecutionRequest = { taskId: string, projectId: string, 
+/**
+ * When to fire. Either this or `delay_seconds` must be set, not both.
+ */
+scheduledAt?: string, 
+/**
+ * Fire this many seconds after the request is received.
+ */
+delaySeconds?: number, executorProfileId: JsonValue, repos: Array<ScheduledRepoInput>, 
+/**
+ * Who is creating the schedule, e.g. `ui` or `mcp`. Defaults to `api`.
+ */
+source?: string, 
+/**
+ * Free-text description shown in the schedule list, e.g. "nightly lint".
+ */
+label?: string, 
+/**
+ * Fire with the executor profile's settings as they are now, rather than
+ * whatever they are when the schedule fires. Defaults to false.
+ */
+freezeExecutorConfig?: boolean, };
 
 export type ScheduledRepoInput = { repoId: string, targetBranch: string, };
 
+export type SchedulerState = { paused: boolean, };
+
+export type ActiveSession = { workspace_id: string, executor: BaseCodingAgent, started_at: string, };
+
 export type DraftFollowUpData = { message: string, executor_profile_id: ExecutorProfileId, };
 
 export type DraftWorkspaceData = { message: string, project_id: string | null, repos: Array<DraftWorkspaceRepo>, selected_profile: ExecutorProfileId | null, };
@@ -186,10 +226,16 @@ export type RemoteProject = { id: string, organization_id: string, name: string,
 
 export type ListProjectsResponse = { projects: Array<RemoteProject>, };
 
+export type RemoteProjectStatus = { id: string, project_id: string, name: string, color: string, sort_order: number, hidden: boolean, created_at: string, };
+
+export type ListProjectStatusesResponse = { project_statuses: Array<RemoteProjectStatus>, };
+
 export type RemoteProjectMembersResponse = { organization_id: string, members: Array<OrganizationMemberWithProfile>, };
 
 export type CreateRemoteProjectRequest = { organization_id: string, name: string, };
 
+export type CancelAllAgentsResponse = { cancelled: number, };
+
 export type LinkToExistingRequest = { remote_project_id: string, };
 
 export type RegisterRepoRequest = { path: string, display_name: string | null, };
@@ -220,8 +266,22 @@ export type CheckEditorAvailabilityResponse = { available: boolean, };
 
 export type CheckAgentAvailabilityQuery = { executor: BaseCodingAgent, };
 
+export type ExecutorProfileSummary = { executor_profile_id: ExecutorProfileId, model: string | null, agent: string | null, };
+
+export type ExecutorProfilesResponse = { executors: { [key in BaseCodingAgent]?: Array<ExecutorProfileSummary> }, };
+
+export type CommandPreview = { program: string, args: Array<string>, 
+/**
+ * Environment variables from the config, with secret-looking values redacted
+ */
+env: { [key in string]?: string }, };
+
 export type CurrentUserResponse = { user_id: string, };
 
+export type DbHealth = { healthy: boolean, latency_ms: number, };
+
+export type ApiErrorCode = "auth_required";
+
 export type CreateFollowUpAttempt = { prompt: string, executor_profile_id: ExecutorProfileId, retry_process_id: string | null, force_when_dirty: boolean | null, perform_git_reset: boolean | null, };
 
 export type ChangeTargetBranchRequest = { repo_id: string, new_target_branch: string, };
@@ -244,6 +304,8 @@ export type OpenEditorRequest = { editor_type: string | null, file_path: string
 
 export type OpenEditorResponse = { url: string | null, };
 
+export type WorkspaceLogsResponse = { execution_process_id: string | null, entries: Array<NormalizedEntry>, };
+
 export type CreateAndStartTaskRequest = { task: CreateTask, executor_profile_id: ExecutorProfileId, repos: Array<WorkspaceRepoInput>, };
 
 export type CreatePrApiRequest = { title: string, body: string | null, target_branch: string | null, draft: boolean | null, repo_id: string, auto_generate_description: boolean, };
@@ -374,7 +436,11 @@ export type DirectoryListResponse = { entries: Array<DirectoryEntry>, current_pa
 
 export type SearchMode = "taskform" | "settings";
 
-export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, send_message_shortcut: SendMessageShortcut, };
+export type Config = { config_version: string, theme: ThemeMode, executor_profile: ExecutorProfileId, disclaimer_acknowledged: boolean, onboarding_acknowledged: boolean, notifications: NotificationConfig, editor: EditorConfig, github: GitHubConfig, analytics_enabled: boolean, workspace_dir: string | null, last_app_version: string | null, show_release_notes: boolean, language: UiLanguage, git_branch_prefix: string, showcases: ShowcaseState, pr_auto_description_enabled: boolean, pr_auto_description_prompt: string | null, beta_workspaces: boolean, beta_workspaces_invitation_sent: boolean, commit_reminder: boolean, send_message_shortcut: SendMessageShortcut, 
+/**
+ * Environment variables passed to every coding agent. Executor profile env takes precedence.
+ */
+executor_env: { [key in string]?: string }, };
 
 export type NotificationConfig = { sound_enabled: boolean, push_enabled: boolean, sound_file: SoundFile, };
 
@@ -441,7 +507,13 @@ export type SlashCommandDescription = {
 /**
  * Command name without the leading slash, e.g. `help` for `/help`.
  */
-name: string, description?: string | null, };
+name: string, description?: string | null, 
+/**
+ * Where the command was defined, when the executor can tell.
+ */
+scope?: SlashCommandScope | null, };
+
+export type SlashCommandScope = { "type": "GLOBAL" } | { "type": "PROJECT" } | { "type": "PLUGIN", name: string, };
 
 export type AvailabilityInfo = { "type": "LOGIN_DETECTED", last_auth_timestamp: bigint, } | { "type": "INSTALLATION_FOUND" } | { "type": "NOT_FOUND" };
 
@@ -471,13 +543,38 @@ export type ExecutorConfigs = { executors: { [key in BaseCodingAgent]?: Executor
 
 export enum BaseAgentCapability { SESSION_FORK = "SESSION_FORK", SETUP_HELPER = "SETUP_HELPER" }
 
-export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type ClaudeCode = { append_prompt: AppendPrompt, claude_code_router?: boolean | null, plan?: boolean | null, approvals?: boolean | null, model?: string | null, dangerously_skip_permissions?: boolean | null, disable_api_key?: boolean | null, 
+/**
+ * Path to an instructions file copied into the working directory as
+ * `.claude/CLAUDE.md` for the run. The copy is git-ignored and removed
+ * when the session ends, including when it fails to start; an existing
+ * project CLAUDE.md is left untouched.
+ */
+instructions_file?: string | null, 
+/**
+ * Where custom slash commands are discovered from (default: all)
+ */
+command_scan_scope?: CommandScanScope | null, 
+/**
+ * Seconds to wait for slash command discovery before giving up (default: 120)
+ */
+discovery_timeout_secs?: number | null, 
+/**
+ * Slash commands offered for this profile (default: all discovered commands)
+ */
+allow_slash_commands?: Array<string> | null, 
+/**
+ * Slash commands hidden for this profile, even if also allowed
+ */
+deny_slash_commands?: Array<string> | null, base_command_override?: string | null, package_runner?: string | null, npm_registry?: string | null, npm_cache_dir?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+
+export type CommandScanScope = "project_only" | "project_and_global" | "all";
 
-export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Gemini = { append_prompt: AppendPrompt, model?: string | null, yolo?: boolean | null, acp_protocol_version?: number | null, acp_handshake_timeout_secs?: number | null, base_command_override?: string | null, package_runner?: string | null, npm_registry?: string | null, npm_cache_dir?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
-export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Amp = { append_prompt: AppendPrompt, dangerously_allow_all?: boolean | null, base_command_override?: string | null, package_runner?: string | null, npm_registry?: string | null, npm_cache_dir?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
-export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Codex = { append_prompt: AppendPrompt, sandbox?: SandboxMode | null, ask_for_approval?: AskForApproval | null, oss?: boolean | null, model?: string | null, model_reasoning_effort?: ReasoningEffort | null, model_reasoning_summary?: ReasoningSummary | null, model_reasoning_summary_format?: ReasoningSummaryFormat | null, profile?: string | null, base_instructions?: string | null, include_apply_patch_tool?: boolean | null, model_provider?: string | null, compact_prompt?: string | null, developer_instructions?: string | null, base_command_override?: string | null, package_runner?: string | null, npm_registry?: string | null, npm_cache_dir?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
 export type SandboxMode = "auto" | "read-only" | "workspace-write" | "danger-full-access";
 
@@ -489,23 +586,71 @@ export type ReasoningSummary = "auto" | "concise" | "detailed" | "none";
 
 export type ReasoningSummaryFormat = "none" | "experimental";
 
-export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type CursorAgent = { append_prompt: AppendPrompt, force?: boolean | null, model?: string | null, base_command_override?: string | null, package_runner?: string | null, npm_registry?: string | null, npm_cache_dir?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
-export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Copilot = { append_prompt: AppendPrompt, model?: string | null, allow_all_tools?: boolean | null, allow_tool?: string | null, deny_tool?: string | null, add_dir?: Array<string> | null, disable_mcp_server?: Array<string> | null, base_command_override?: string | null, package_runner?: string | null, npm_registry?: string | null, npm_cache_dir?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
-export type Opencode = { append_prompt: AppendPrompt, model?: string | null, variant?: string | null, mode?: string | null, 
+export type Opencode = { append_prompt: AppendPrompt, model?: string | null, variant?: string | null, 
+/**
+ * OpenCode agent to run the session with, e.g. `build` or `plan`. OpenCode folded its former modes into agents, so this is sent as the prompt's `agent`; the key `agent` is accepted as an alias
+ */
+mode?: string | null, 
 /**
  * Auto-approve agent actions
  */
 auto_approve: boolean, 
 /**
- * Enable auto-compaction when the context length approaches the model's context window limit
+ * Enable auto-compaction when the context length approaches the model's context window limit (null leaves OpenCode's own compaction config untouched)
+ */
+auto_compact: boolean | null, 
+/**
+ * Default timeout in seconds for shell commands run by the agent
+ */
+command_timeout_secs?: number | null, 
+/**
+ * Stop the session after the agent has taken this many turns (null means no limit)
+ */
+max_turns?: number | null, 
+/**
+ * Title for new OpenCode sessions, shown in OpenCode's own session list. Follow-ups keep the existing title
+ */
+session_title?: string | null, 
+/**
+ * Additional MCP servers merged into OpenCode's `mcp` config for this profile
+ */
+extra_mcp_servers?: JsonValue | null, 
+/**
+ * Append the normalized log entries of each run to this file as JSON lines (relative paths resolve against the app's data directory, never the worktree)
+ */
+jsonl_log_path?: string | null, 
+/**
+ * Provider ids left out of the `/models` listing, e.g. providers that always fail to enumerate their models
+ */
+skip_providers?: Array<string> | null, 
+/**
+ * Seconds to wait for slash command discovery, including starting a server for it, before giving up (null keeps only the server's own startup timeouts)
+ */
+discovery_timeout_secs?: number | null, 
+/**
+ * Respawn the server and retry slash command discovery once when a server that started fails mid-discovery (default: true). Disable to avoid doubling discovery time on persistent failures
  */
-auto_compact: boolean, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+retry_discovery_with_fresh_server?: boolean | null, 
+/**
+ * Slash commands offered for this profile (default: all discovered commands)
+ */
+allow_slash_commands?: Array<string> | null, 
+/**
+ * Slash commands hidden for this profile, even if also allowed
+ */
+deny_slash_commands?: Array<string> | null, 
+/**
+ * Regular expressions for server startup output lines to leave out of the log, e.g. npm noise. Ignored lines still show in startup error reports
+ */
+startup_log_ignore?: Array<string> | null, base_command_override?: string | null, package_runner?: string | null, npm_registry?: string | null, npm_cache_dir?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
-export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type QwenCode = { append_prompt: AppendPrompt, yolo?: boolean | null, acp_protocol_version?: number | null, acp_handshake_timeout_secs?: number | null, base_command_override?: string | null, package_runner?: string | null, npm_registry?: string | null, npm_cache_dir?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
-export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
+export type Droid = { append_prompt: AppendPrompt, autonomy: Autonomy, model?: string | null, reasoning_effort?: DroidReasoningEffort | null, base_command_override?: string | null, package_runner?: string | null, npm_registry?: string | null, npm_cache_dir?: string | null, additional_params?: Array<string> | null, env?: { [key in string]?: string } | null, };
 
 export type Autonomy = "normal" | "low" | "medium" | "high" | "skip-permissions-unsafe";
 
@@ -522,7 +667,12 @@ executor_profile_id: ExecutorProfileId,
  * Optional relative path to execute the agent in (relative to container_ref).
  * If None, uses the container_ref directory directly.
  */
-working_dir: string | null, };
+working_dir: string | null, 
+/**
+ * Settings to run with instead of resolving `executor_profile_id` at spawn time,
+ * e.g. a snapshot frozen when a run was scheduled
+ */
+executor_config?: CodingAgent, };
 
 export type CodingAgentFollowUpRequest = { prompt: string, session_id: string, 
 /**